@@ -0,0 +1,228 @@
+use std::ops::Range;
+use std::path::PathBuf;
+use std::process::exit;
+use thrust_core::sim::Pilot;
+
+const USAGE: &str = "\
+Usage: thrust [OPTIONS]
+       thrust sim [SIM OPTIONS]
+
+Options:
+  --seed <SEED>      start the game with a fixed random seed
+  --record <FILE>    save the inputs of the last run as a replay file when the game is quit
+  --replay <FILE>    play back a recorded replay file
+  --level <PATH>     play the waves of a level file, or of all level files in a directory
+  --config <FILE>    load the game config from FILE instead of the user's config directory
+  --set <KEY=VALUE>  override a single config value, e.g. --set damage.collision=40 (repeatable)
+  --host <PORT>      host a two-player game over the network and wait for a player to join on PORT
+  --versus           with --host, host a versus game for the higher score instead of co-op
+  --join <ADDR>      join the game hosted at ADDR, e.g. 192.168.0.2:7777
+  --serve <PORT>     stream every frame to spectators connecting on PORT, over TCP or WebSocket
+  --watch <ADDR>     watch the game streamed by --serve at ADDR
+  --autopilot        let the built-in pilot fly the ship and restart finished games, e.g. for soak tests
+  --bot              let a bot play over JSON lines on stdin and stdout instead of opening the terminal
+  -h, --help         print this help
+
+Sim options, to play many games without rendering and report how they went:
+  --runs <N>           the number of games, by default 100 or as many as seeds in --seed-range
+  --seed-range <A..B>  play the seeds from A up to B (excluded), by default from 0 on
+  --pilot <PILOT>      who flies the ship: autopilot (default) or idle
+  --max-frames <N>     cut games off after N frames, by default after 10 minutes of game time
+  --format <FORMAT>    json (default) for a summary, csv for a line per game
+  --config and --set apply to simulated games as well";
+
+/// Simulated games played by `thrust sim` are cut off after this long, in seconds of game time.
+const SIM_MAX_SECONDS: usize = 600;
+const SIM_RUNS: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimFormat {
+    Json,
+    Csv,
+}
+
+pub struct SimArgs {
+    pub runs: Option<usize>,
+    pub seed_range: Option<Range<u64>>,
+    pub pilot: Pilot,
+    pub max_frames: Option<usize>,
+    pub format: SimFormat,
+}
+
+impl Default for SimArgs {
+    fn default() -> Self {
+        Self { runs: None, seed_range: None, pilot: Pilot::Autopilot, max_frames: None, format: SimFormat::Json }
+    }
+}
+
+impl SimArgs {
+    /// The seeds of the games to play, `--runs` of them from the start of `--seed-range`.
+    pub fn seeds(&self) -> Range<u64> {
+        let start = self.seed_range.as_ref().map_or(0, |range| range.start);
+        let runs = self.runs.or(self.seed_range.as_ref().map(|range| (range.end - range.start) as usize)).unwrap_or(SIM_RUNS);
+        start..start + runs as u64
+    }
+
+    pub fn max_frames(&self, fps_limit: u32) -> usize {
+        self.max_frames.unwrap_or(SIM_MAX_SECONDS * fps_limit as usize)
+    }
+}
+
+#[derive(Default)]
+pub struct CliArgs {
+    pub seed: Option<u64>,
+    pub record: Option<PathBuf>,
+    pub replay: Option<PathBuf>,
+    pub level: Option<PathBuf>,
+    pub config: Option<PathBuf>,
+    pub overrides: Vec<String>,
+    pub host: Option<u16>,
+    pub versus: bool,
+    pub join: Option<String>,
+    pub serve: Option<u16>,
+    pub watch: Option<String>,
+    pub bot: bool,
+    pub autopilot: bool,
+    /// Set by the `sim` subcommand.
+    pub sim: Option<SimArgs>,
+}
+
+impl CliArgs {
+    /// Parses the process arguments, printing the usage and exiting on invalid input.
+    pub fn parse() -> Self {
+        match CliArgs::parse_from(std::env::args().skip(1)) {
+            Ok(args) => args,
+            Err(msg) => {
+                eprintln!("error: {}\n\n{}", msg, USAGE);
+                exit(2);
+            }
+        }
+    }
+
+    fn parse_from(mut args: impl Iterator<Item=String>) -> Result<Self, String> {
+        let mut cli_args = CliArgs::default();
+        let mut first = true;
+        while let Some(arg) = args.next() {
+            if std::mem::take(&mut first) && arg == "sim" {
+                cli_args.sim = Some(SimArgs::default());
+                continue;
+            }
+            if let Some(sim) = cli_args.sim.as_mut() {
+                if parse_sim_arg(sim, &arg, &mut args)? {
+                    continue;
+                }
+            }
+            match arg.as_str() {
+                "--seed" => {
+                    let value = value_of(&arg, args.next())?;
+                    cli_args.seed = Some(value.parse().map_err(|_| "--seed expects an unsigned integer")?);
+                }
+                "--record" => cli_args.record = Some(value_of(&arg, args.next())?.into()),
+                "--replay" => cli_args.replay = Some(value_of(&arg, args.next())?.into()),
+                "--level" => cli_args.level = Some(value_of(&arg, args.next())?.into()),
+                "--config" => cli_args.config = Some(value_of(&arg, args.next())?.into()),
+                "--set" => cli_args.overrides.push(value_of(&arg, args.next())?),
+                "--host" => {
+                    let value = value_of(&arg, args.next())?;
+                    cli_args.host = Some(value.parse().map_err(|_| "--host expects a port number")?);
+                }
+                "--versus" => cli_args.versus = true,
+                "--join" => cli_args.join = Some(value_of(&arg, args.next())?),
+                "--serve" => {
+                    let value = value_of(&arg, args.next())?;
+                    cli_args.serve = Some(value.parse().map_err(|_| "--serve expects a port number")?);
+                }
+                "--watch" => cli_args.watch = Some(value_of(&arg, args.next())?),
+                "--bot" => cli_args.bot = true,
+                "--autopilot" => cli_args.autopilot = true,
+                "-h" | "--help" => {
+                    println!("{}", USAGE);
+                    exit(0);
+                }
+                _ => return Err(format!("unknown argument '{}'", arg)),
+            }
+        }
+        if let Some(sim) = &cli_args.sim {
+            let play_args = cli_args.seed.is_some() || cli_args.record.is_some() || cli_args.replay.is_some()
+                || cli_args.level.is_some() || cli_args.host.is_some() || cli_args.join.is_some() || cli_args.serve.is_some()
+                || cli_args.watch.is_some() || cli_args.bot || cli_args.autopilot;
+            if play_args {
+                return Err("thrust sim only accepts --config and --set besides its own options".to_string());
+            }
+            let range_len = sim.seed_range.as_ref().map(|range| range.end - range.start);
+            if sim.runs.zip(range_len).is_some_and(|(runs, len)| runs as u64 > len) {
+                return Err("--runs is larger than the --seed-range".to_string());
+            }
+        }
+        let replay_conflicts = cli_args.seed.is_some() || cli_args.record.is_some()
+            || cli_args.config.is_some() || !cli_args.overrides.is_empty();
+        if cli_args.replay.is_some() && replay_conflicts {
+            return Err("--replay cannot be combined with --seed, --record, --config or --set".to_string());
+        }
+        if cli_args.level.is_some() && (cli_args.replay.is_some() || cli_args.record.is_some()) {
+            return Err("--level cannot be combined with --replay or --record, replays don't store the levels".to_string());
+        }
+        let network = cli_args.host.is_some() || cli_args.join.is_some();
+        if network && (cli_args.replay.is_some() || cli_args.level.is_some()) {
+            return Err("--host and --join cannot be combined with --replay or --level".to_string());
+        }
+        if cli_args.host.is_some() && cli_args.join.is_some() {
+            return Err("--host and --join cannot be combined".to_string());
+        }
+        if cli_args.versus && cli_args.host.is_none() {
+            return Err("--versus only applies to --host, the joining player plays the host's mode".to_string());
+        }
+        let watch_conflicts = cli_args.seed.is_some() || cli_args.record.is_some() || cli_args.replay.is_some()
+            || cli_args.level.is_some() || network || cli_args.serve.is_some();
+        if cli_args.watch.is_some() && watch_conflicts {
+            return Err("--watch cannot be combined with --seed, --record, --replay, --level, --host, --join or --serve".to_string());
+        }
+        let bot_conflicts = watch_conflicts || cli_args.watch.is_some();
+        if cli_args.bot && bot_conflicts {
+            return Err("--bot can only be combined with --config and --set".to_string());
+        }
+        if cli_args.autopilot && (cli_args.replay.is_some() || cli_args.watch.is_some() || cli_args.bot) {
+            return Err("--autopilot cannot be combined with --replay, --watch or --bot".to_string());
+        }
+        Ok(cli_args)
+    }
+}
+
+/// Handles the options only `thrust sim` knows, returns whether `arg` was one of them.
+fn parse_sim_arg(sim: &mut SimArgs, arg: &str, args: &mut impl Iterator<Item=String>) -> Result<bool, String> {
+    match arg {
+        "--runs" => {
+            let value = value_of(arg, args.next())?;
+            sim.runs = Some(value.parse().map_err(|_| "--runs expects an unsigned integer")?);
+        }
+        "--seed-range" => {
+            let value = value_of(arg, args.next())?;
+            let range = value.split_once("..")
+                .and_then(|(start, end)| Some(start.parse::<u64>().ok()?..end.parse::<u64>().ok()?))
+                .filter(|range| range.start < range.end)
+                .ok_or("--seed-range expects a range like 1000..2000")?;
+            sim.seed_range = Some(range);
+        }
+        "--pilot" => {
+            let value = value_of(arg, args.next())?;
+            sim.pilot = Pilot::from_name(&value).ok_or_else(|| format!("unknown pilot '{}', expected autopilot or idle", value))?;
+        }
+        "--max-frames" => {
+            let value = value_of(arg, args.next())?;
+            sim.max_frames = Some(value.parse().map_err(|_| "--max-frames expects an unsigned integer")?);
+        }
+        "--format" => {
+            sim.format = match value_of(arg, args.next())?.as_str() {
+                "json" => SimFormat::Json,
+                "csv" => SimFormat::Csv,
+                other => return Err(format!("unknown format '{}', expected json or csv", other)),
+            };
+        }
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+fn value_of(arg: &str, value: Option<String>) -> Result<String, String> {
+    value.ok_or_else(|| format!("missing value for {}", arg))
+}
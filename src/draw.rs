@@ -0,0 +1,232 @@
+use std::ops::Add;
+use ruscii::drawing::{Pencil, RectCharset};
+use ruscii::gui::FPSCounter;
+use ruscii::spatial::Vec2 as Cell;
+use ruscii::terminal::Color;
+use thrust_core::boss::{Boss, BOSS_SPRITE};
+use thrust_core::config::Glyphs;
+use thrust_core::constant::BORDER_SIZE;
+use thrust_core::game_state::GameState;
+use thrust_core::goodie::GoodieType;
+use thrust_core::ship::{FireMode, Ship};
+use thrust_core::shot::ShotType;
+use thrust_core::spatial::Vec2;
+use thrust_core::world::{Entity, Sprite};
+
+/// Renders a simulation object with the ruscii pencil, using the configured `glyphs`.
+pub trait Draw {
+    fn draw(&self, glyphs: &Glyphs, pencil: &mut Pencil);
+}
+
+pub fn cell(pos: Vec2) -> Cell {
+    Cell::xy(pos.x, pos.y)
+}
+
+/// The color of the ship and the HUD of every player.
+const PLAYER_COLORS: [Color; 2] = [Color::Cyan, Color::Xterm(219)];
+
+impl Draw for Ship {
+    fn draw(&self, _glyphs: &Glyphs, pencil: &mut Pencil) {
+        pencil.set_foreground(PLAYER_COLORS[self.player]);
+        pencil.draw_text(self.sprite(), cell(self.pos));
+    }
+}
+
+impl Draw for Boss {
+    fn draw(&self, _glyphs: &Glyphs, pencil: &mut Pencil) {
+        pencil.set_foreground(health_color(self.health, self.max_health));
+        for (y, line) in BOSS_SPRITE.iter().enumerate() {
+            pencil.draw_text(line, cell(self.pos) + Cell::y(y as i32));
+        }
+    }
+}
+
+/// Green while undamaged, then yellow and finally red once less than half of the health is left.
+pub fn health_color(health: usize, max_health: usize) -> Color {
+    if health == max_health {
+        Color::Green
+    } else if health * 2 >= max_health {
+        Color::Yellow
+    } else {
+        Color::Red
+    }
+}
+
+impl Draw for Entity {
+    fn draw(&self, glyphs: &Glyphs, pencil: &mut Pencil) {
+        let color = match self.sprite {
+            Sprite::Enemy(_) if self.as_enemy().is_some_and(|enemy| enemy.is_bursting()) => Color::Blue,
+            Sprite::Enemy(_) => self.health.map_or(Color::Green, |health| health_color(health.current, health.max)),
+            Sprite::Shot(shot_type) => shot_color(shot_type),
+            Sprite::Goodie(goodie_type) => goodie_color(goodie_type),
+        };
+        pencil.set_foreground(color);
+        pencil.draw_char(self.sprite.character(glyphs), cell(self.position.cell));
+    }
+}
+
+fn shot_color(shot_type: ShotType) -> Color {
+    match shot_type {
+        ShotType::Enemy => Color::Red,
+        ShotType::EnemyAimed => Color::Xterm(208),
+        ShotType::EnemySpread => Color::LightGrey,
+        ShotType::EnemyBurst => Color::Blue,
+        ShotType::Missile => Color::White,
+        ShotType::Boss => Color::Magenta,
+        _ => Color::Yellow,
+    }
+}
+
+fn goodie_color(goodie_type: GoodieType) -> Color {
+    match goodie_type {
+        GoodieType::RepairKit(_) => Color::Red,
+        GoodieType::ShieldBoost(_) => Color::Yellow,
+        GoodieType::ShipUpgrade(_) => Color::Cyan,
+        GoodieType::PowerUp(_) => Color::Magenta,
+        GoodieType::Bomb => Color::White,
+    }
+}
+
+pub fn draw_fps(fps_counter: &mut FPSCounter, pencil: &mut Pencil) {
+    fps_counter.update();
+    pencil.set_foreground(Color::White);
+    pencil.draw_text(&format!("FPS: {}", fps_counter.count()), Cell::xy(1, 0));
+}
+
+pub fn draw_game(game_state: &GameState, pencil: &mut Pencil) {
+    let glyphs = &game_state.config.glyphs;
+    for player in game_state.players.iter().filter(|player| !player.is_destroyed()) {
+        player.ship.draw(glyphs, pencil);
+    }
+    for entity in game_state.world.iter() {
+        entity.draw(glyphs, pencil);
+    }
+    draw_effects(game_state, pencil);
+
+    if let Some(boss) = &game_state.boss {
+        if let Some(laser) = boss.laser_hitbox(game_state.dimension) {
+            pencil.set_foreground(Color::Magenta);
+            pencil.draw_vline(glyphs.laser, cell(laser.pos), laser.size.y);
+        }
+        boss.draw(glyphs, pencil);
+    }
+}
+
+pub fn draw_border(game_state: &GameState, pencil: &mut Pencil) {
+    pencil.set_foreground(Color::Grey);
+    let border_rect = cell(game_state.dimension).add(Cell::xy(BORDER_SIZE, BORDER_SIZE));
+    pencil.draw_rect(&RectCharset::simple_round_lines(), Cell::zero(), border_rect);
+}
+
+/// `"P1 "` and `"P2 "` in co-op to tell the players apart, nothing for a single player.
+fn player_label(game_state: &GameState, player: usize) -> String {
+    if game_state.players.len() > 1 { format!("P{} ", player + 1) } else { String::new() }
+}
+
+/// Draws a vertical bar of `value` at `x` above the field, with its digits above the glyph starting the bar.
+fn draw_bar(value: usize, granularity: usize, glyph: char, bar_glyph: char, color: Color, x: i32, pencil: &mut Pencil) {
+    let digits = &format!("{}", value);
+    let mut pos = Cell::xy(x, -(digits.chars().count() as i32));
+    pencil.set_foreground(Color::White);
+    for ch in digits.chars() {
+        pencil.draw_char(ch, pos);
+        pos.y += 1;
+    }
+    pencil.set_foreground(color);
+    pencil.draw_char(glyph, pos);
+    for _ in 0..value / granularity {
+        pos.y += 1;
+        pencil.draw_char(bar_glyph, pos);
+    }
+}
+
+pub fn draw_hud(game_state: &GameState, pencil: &mut Pencil) {
+    let glyphs = &game_state.config.glyphs;
+    let hud = &game_state.config.hud;
+    for (i, player) in game_state.players.iter().enumerate() {
+        // the bars of the first ship are left of the field, the ones of the second right of it
+        let (health_x, shield_x) = match i {
+            0 => (-2, -4),
+            _ => (game_state.dimension.x + BORDER_SIZE + 1, game_state.dimension.x + BORDER_SIZE + 3),
+        };
+        draw_bar(player.health, hud.health_granularity, glyphs.health, glyphs.health_bar, Color::Red, health_x, pencil);
+        draw_bar(player.shield, hud.shield_granularity, glyphs.shield, glyphs.shield_bar, Color::Yellow, shield_x, pencil);
+        if game_state.config.fire_mode == FireMode::Charge {
+            // the second charge bar goes below the field, under the boss health bar
+            let y = if i == 0 { -1 } else { game_state.dimension.y + BORDER_SIZE + 1 };
+            let label = format!("{}Charge", player_label(game_state, i));
+            let charge = player.ship.charge.min(game_state.config.charge_frames);
+            let filled = (charge * 8 / game_state.config.charge_frames) as i32;
+            pencil.set_foreground(if filled == 8 { Color::Yellow } else { Color::White });
+            pencil.draw_text(&label, Cell::xy(1, y));
+            pencil.draw_hline(glyphs.shot_ship_strong, Cell::xy(label.len() as i32 + 2, y), filled);
+        }
+    }
+
+    pencil.set_foreground(Color::White);
+    let status_msg = match game_state.players.as_slice() {
+        [player] => format!("Score: {}", player.score),
+        players => players.iter().enumerate()
+            .map(|(i, player)| format!("{}{}", player_label(game_state, i), player.score))
+            .collect::<Vec<_>>()
+            .join("  "),
+    };
+    pencil.draw_text(&status_msg, Cell::xy(25, -1));
+
+    let combo_msg = &game_state.players.iter().enumerate().map(|(i, player)| {
+        let combo = if player.scoring.combo > 1 { format!("Combo {}  ", player.scoring.combo) } else { String::new() };
+        format!("{}{}x{}", player_label(game_state, i), combo, player.score_factor(&game_state.config))
+    }).collect::<Vec<_>>().join("  ");
+    let boosted = game_state.players.iter().any(|player| player.score_factor(&game_state.config) > 1);
+    pencil.set_foreground(if boosted { Color::Yellow } else { Color::White });
+    pencil.draw_text(combo_msg, Cell::xy(game_state.dimension.x + BORDER_SIZE - combo_msg.len() as i32, -1));
+
+    if let Some((progress, level)) = game_state.level.as_ref().and_then(|progress| Some((progress, progress.current_level()?))) {
+        let level_msg = &format!(" {} - wave {}/{} ", level.name, progress.wave_index + 1, level.waves.len());
+        pencil.draw_text(level_msg, Cell::xy(game_state.dimension.x - level_msg.chars().count() as i32, 0));
+    }
+
+    if let Some(boss) = &game_state.boss {
+        let label = "BOSS ";
+        let bar_width = game_state.dimension.x - 2 - label.len() as i32;
+        let filled = (boss.health as i32 * bar_width + boss.max_health as i32 - 1) / boss.max_health as i32;
+        let pos = Cell::xy(1, game_state.dimension.y + BORDER_SIZE);
+        pencil.set_foreground(Color::White);
+        pencil.draw_text(label, pos);
+        pencil.set_foreground(health_color(boss.health, boss.max_health));
+        pencil.draw_hline(glyphs.boss_health_bar, pos + Cell::x(label.len() as i32), filled);
+    }
+}
+
+/// Lists the active power-ups with their remaining seconds and the stored bombs right of the field,
+/// in co-op one player after the other and right of the second ship's bars.
+fn draw_effects(game_state: &GameState, pencil: &mut Pencil) {
+    let glyphs = &game_state.config.glyphs;
+    let fps = game_state.config.fps_limit as usize;
+    let x = game_state.dimension.x + BORDER_SIZE + if game_state.players.len() > 1 { 6 } else { 2 };
+    let mut y = 1;
+    for (i, player) in game_state.players.iter().enumerate() {
+        let label = player_label(game_state, i);
+        if !label.is_empty() {
+            pencil.set_foreground(PLAYER_COLORS[i]);
+            pencil.draw_text(&label, Cell::xy(x, y));
+            y += 1;
+        }
+        for effect in player.effects.active() {
+            let seconds = effect.remaining_frames.div_ceil(fps);
+            pencil.set_foreground(Color::Magenta);
+            pencil.draw_char(effect.kind.character(glyphs), Cell::xy(x, y));
+            pencil.set_foreground(Color::White);
+            pencil.draw_text(&format!("{} {}s", effect.kind.name(), seconds), Cell::xy(x + 2, y));
+            y += 1;
+        }
+        if player.bombs > 0 {
+            y += 1;
+            pencil.set_foreground(Color::White);
+            pencil.draw_char(glyphs.bomb, Cell::xy(x, y));
+            pencil.draw_text(&format!("Bombs {}", player.bombs), Cell::xy(x + 2, y));
+            y += 1;
+        }
+        y += 1;
+    }
+}
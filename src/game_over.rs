@@ -0,0 +1,161 @@
+use std::io;
+use ruscii::drawing::Pencil;
+use ruscii::keyboard::{Key, KeyEvent};
+use ruscii::spatial::Vec2;
+use ruscii::terminal::Color;
+use thrust_core::game_state::GameState;
+use thrust_core::score::ScoreSource;
+use crate::highscore::{HighScoreEntry, HighScores, MAX_NAME_LENGTH};
+
+/// The screen at the end of a game: asks for the player's name if the score made it into the high-score table
+/// and shows the table afterwards. Games of two ships do not enter the table, which ranks single ships.
+/// A versus game names its winner instead of the score of both players together.
+pub struct GameOverScreen {
+    title: &'static str,
+    /// The keys to continue, shown below the table.
+    hint: &'static str,
+    high_scores: Option<HighScores>,
+    name: Option<String>,
+    rank: Option<usize>,
+    error: Option<String>,
+}
+
+impl GameOverScreen {
+    pub fn new(title: &'static str, game_state: &GameState, allow_name_entry: bool, hint: &'static str) -> Self {
+        GameOverScreen::with_high_scores(title, game_state, HighScores::open_default(), allow_name_entry, hint)
+    }
+
+    pub fn with_high_scores(title: &'static str, game_state: &GameState, high_scores: io::Result<HighScores>,
+                            allow_name_entry: bool, hint: &'static str) -> Self {
+        let (high_scores, error) = match high_scores {
+            Ok(high_scores) => (Some(high_scores), None),
+            Err(err) => (None, Some(format!("High-scores unavailable: {}", err))),
+        };
+        let qualifies = high_scores.as_ref().is_some_and(|high_scores| high_scores.qualifies(game_state.score()));
+        let name = if allow_name_entry && game_state.players.len() == 1 && qualifies { Some(String::new()) } else { None };
+        Self { title, hint, high_scores, name, rank: None, error }
+    }
+
+    pub fn is_entering_name(&self) -> bool {
+        self.name.is_some()
+    }
+
+    /// The rank of the entered name in the high-score table, once it is saved.
+    pub fn rank(&self) -> Option<usize> {
+        self.rank
+    }
+
+    /// Handles the name entry, the keys to continue after it are left to the caller. Returns whether the `key_events`
+    /// of this frame went to the name entry, the caller must not handle them again, e.g. the Enter that saved the name.
+    pub fn handle_input(&mut self, key_events: &[KeyEvent], game_state: &GameState) -> bool {
+        let Some(name) = self.name.as_mut() else { return false; };
+        let mut submitted = false;
+        let mut skipped = false;
+        for key_event in key_events {
+            match key_event {
+                KeyEvent::Pressed(Key::Enter) => submitted = !name.trim().is_empty(),
+                KeyEvent::Pressed(Key::Esc) => skipped = true,
+                KeyEvent::Pressed(Key::Backspace) => { name.pop(); }
+                KeyEvent::Pressed(key) if name.chars().count() < MAX_NAME_LENGTH => {
+                    if let Some(ch) = name_char(*key) {
+                        name.push(ch);
+                    }
+                }
+                _ => (),
+            }
+            if submitted || skipped {
+                break;
+            }
+        }
+        if submitted {
+            self.submit(game_state);
+        } else if skipped {
+            self.name = None;
+        }
+        true
+    }
+
+    fn submit(&mut self, game_state: &GameState) {
+        let (Some(name), Some(high_scores)) = (self.name.take(), self.high_scores.as_mut()) else { return; };
+        match high_scores.insert(HighScoreEntry::new(name.trim(), game_state)) {
+            Ok(rank) => self.rank = rank,
+            Err(err) => self.error = Some(format!("Could not save high-score: {}", err)),
+        }
+    }
+
+    pub fn draw(&self, game_state: &GameState, win_size: Vec2, pencil: &mut Pencil) {
+        pencil.set_origin(Vec2::xy(win_size.x / 2, win_size.y / 2 - 8));
+        pencil.set_foreground(Color::White);
+        let result = match game_state.winner() {
+            Some(player) => format!("player {} wins", player + 1),
+            None if game_state.is_versus() => "draw".to_string(),
+            None => format!("score: {}", game_state.score()),
+        };
+        let msg = &format!("{}  -  {}  -  seed: {}", self.title, result, game_state.seed);
+        pencil.draw_center_text(msg, Vec2::zero());
+        pencil.set_foreground(Color::Grey);
+        for (i, breakdown) in score_breakdowns(game_state).iter().enumerate() {
+            pencil.draw_center_text(breakdown, Vec2::y(1 + i as i32));
+        }
+
+        if let Some(name) = &self.name {
+            pencil.set_foreground(Color::Yellow);
+            pencil.draw_center_text("New high-score! Enter your name:", Vec2::y(2));
+            pencil.draw_center_text(&format!("{:_<width$}", name, width = MAX_NAME_LENGTH), Vec2::y(4));
+            pencil.set_foreground(Color::Grey);
+            pencil.draw_center_text("Enter: save   Esc: skip", Vec2::y(6));
+            return;
+        }
+        if let Some(error) = &self.error {
+            pencil.set_foreground(Color::Red);
+            pencil.draw_center_text(error, Vec2::y(3));
+        }
+        pencil.set_foreground(Color::Grey);
+        pencil.draw_center_text(self.hint, Vec2::y(17));
+        if let Some(high_scores) = &self.high_scores {
+            draw_table(high_scores, self.rank, pencil);
+        }
+    }
+}
+
+/// The points per source of every player, e.g. `kills 120  combos 14  goodies 6`,
+/// with two ships prefixed with the player and their score, e.g. `P2 140: kills 120  combos 14  goodies 6`.
+fn score_breakdowns(game_state: &GameState) -> Vec<String> {
+    game_state.players.iter().enumerate().map(|(i, player)| {
+        let breakdown = ScoreSource::ALL.iter()
+            .filter(|source| player.scoring.points(**source) > 0)
+            .map(|source| format!("{} {}", source.name(), player.scoring.points(*source)))
+            .collect::<Vec<_>>()
+            .join("  ");
+        if game_state.players.len() > 1 { format!("P{} {}: {}", i + 1, player.score, breakdown) } else { breakdown }
+    }).collect()
+}
+
+pub fn draw_table(high_scores: &HighScores, highlighted: Option<usize>, pencil: &mut Pencil) {
+    let header = format!("{:>2}  {:<width$}  {:>6}  {:<8}  {:>6}  {:<10}", "#", "Name", "Score", "Ship", "Frames", "Date", width = MAX_NAME_LENGTH);
+    pencil.move_origin(Vec2::x(-(header.len() as i32) / 2));
+    pencil.set_foreground(Color::Grey);
+    pencil.draw_text(&header, Vec2::y(4));
+    for (i, entry) in high_scores.entries().iter().enumerate() {
+        pencil.set_foreground(if highlighted == Some(i) { Color::Yellow } else { Color::White });
+        let line = format!("{:>2}  {:<width$}  {:>6}  {:<8}  {:>6}  {:<10}",
+                           i + 1, entry.name, entry.score, entry.ship_type.name(), entry.frames, entry.date, width = MAX_NAME_LENGTH);
+        pencil.draw_text(&line, Vec2::y(5 + i as i32));
+    }
+}
+
+fn name_char(key: Key) -> Option<char> {
+    let ch = match key {
+        Key::Space => ' ',
+        Key::Minus => '-',
+        Key::A => 'A', Key::B => 'B', Key::C => 'C', Key::D => 'D', Key::E => 'E', Key::F => 'F',
+        Key::G => 'G', Key::H => 'H', Key::I => 'I', Key::J => 'J', Key::K => 'K', Key::L => 'L',
+        Key::M => 'M', Key::N => 'N', Key::O => 'O', Key::P => 'P', Key::Q => 'Q', Key::R => 'R',
+        Key::S => 'S', Key::T => 'T', Key::U => 'U', Key::V => 'V', Key::W => 'W', Key::X => 'X',
+        Key::Y => 'Y', Key::Z => 'Z',
+        Key::Num0 => '0', Key::Num1 => '1', Key::Num2 => '2', Key::Num3 => '3', Key::Num4 => '4',
+        Key::Num5 => '5', Key::Num6 => '6', Key::Num7 => '7', Key::Num8 => '8', Key::Num9 => '9',
+        _ => return None,
+    };
+    Some(ch)
+}
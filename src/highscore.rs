@@ -0,0 +1,138 @@
+use std::cmp::Reverse;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use thrust_core::game_state::GameState;
+use thrust_core::ship::ShipType;
+
+pub const MAX_ENTRIES: usize = 10;
+pub const MAX_NAME_LENGTH: usize = 12;
+
+const FILE_NAME: &str = "highscores.tsv";
+const LOCK_FILE_NAME: &str = "highscores.lock";
+
+pub struct HighScoreEntry {
+    pub name: String,
+    pub score: usize,
+    pub date: String,
+    pub ship_type: ShipType,
+    pub frames: usize,
+}
+
+impl HighScoreEntry {
+    pub fn new(name: &str, game_state: &GameState) -> Self {
+        Self {
+            name: name.to_string(),
+            score: game_state.score(),
+            date: today(),
+            ship_type: game_state.players[0].ship.ship_type,
+            frames: game_state.frame,
+        }
+    }
+
+    fn to_line(&self) -> String {
+        format!("{}\t{}\t{}\t{}\t{}", self.score, self.frames, self.date, self.ship_type.name(), self.name)
+    }
+
+    fn from_line(line: &str) -> Option<Self> {
+        let mut fields = line.splitn(5, '\t');
+        Some(Self {
+            score: fields.next()?.parse().ok()?,
+            frames: fields.next()?.parse().ok()?,
+            date: fields.next()?.to_string(),
+            ship_type: ShipType::from_name(fields.next()?)?,
+            name: fields.next()?.to_string(),
+        })
+    }
+}
+
+/// The local high-score table, stored as one tab separated line per entry in the user's data directory.
+///
+/// Writers take an exclusive lock on a separate lock file, re-read the table, and atomically replace it
+/// by renaming a temporary file. Two running games can therefore never interleave their writes or lose an entry.
+pub struct HighScores {
+    dir: PathBuf,
+    entries: Vec<HighScoreEntry>,
+}
+
+impl HighScores {
+    pub fn open_default() -> io::Result<Self> {
+        let data_dir = dirs::data_dir()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no user data directory available"))?;
+        HighScores::open(data_dir.join("thrust"))
+    }
+
+    pub fn open(dir: PathBuf) -> io::Result<Self> {
+        let entries = read_entries(&dir.join(FILE_NAME))?;
+        Ok(Self { dir, entries })
+    }
+
+    pub fn entries(&self) -> &[HighScoreEntry] {
+        &self.entries
+    }
+
+    pub fn qualifies(&self, score: usize) -> bool {
+        score > 0 && (self.entries.len() < MAX_ENTRIES || self.entries.iter().any(|entry| entry.score < score))
+    }
+
+    /// Adds `entry` to the table on disk and returns its rank (starting at 0), if it made it into the table.
+    pub fn insert(&mut self, entry: HighScoreEntry) -> io::Result<Option<usize>> {
+        fs::create_dir_all(&self.dir)?;
+        let lock = OpenOptions::new().create(true).truncate(false).write(true).open(self.dir.join(LOCK_FILE_NAME))?;
+        lock.lock()?;
+
+        let path = self.dir.join(FILE_NAME);
+        self.entries = read_entries(&path)?;
+        let rank = self.entries.iter().position(|other| other.score < entry.score).unwrap_or(self.entries.len());
+        self.entries.insert(rank, entry);
+        self.entries.truncate(MAX_ENTRIES);
+
+        let tmp_path = self.dir.join(format!("{}.{}.tmp", FILE_NAME, std::process::id()));
+        let written = write_entries(&tmp_path, &self.entries).and_then(|()| fs::rename(&tmp_path, &path));
+        if written.is_err() {
+            // don't leave the partial table behind, the next save creates it anew anyway
+            let _ = fs::remove_file(&tmp_path);
+        }
+        written?;
+        lock.unlock()?;
+
+        Ok(if rank < MAX_ENTRIES { Some(rank) } else { None })
+    }
+}
+
+fn read_entries(path: &Path) -> io::Result<Vec<HighScoreEntry>> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    let mut entries: Vec<HighScoreEntry> = content.lines().filter_map(HighScoreEntry::from_line).collect();
+    entries.sort_by_key(|entry| Reverse(entry.score));
+    entries.truncate(MAX_ENTRIES);
+    Ok(entries)
+}
+
+fn write_entries(path: &Path, entries: &[HighScoreEntry]) -> io::Result<()> {
+    let mut file = File::create(path)?;
+    for entry in entries {
+        writeln!(file, "{}", entry.to_line())?;
+    }
+    file.sync_all()
+}
+
+/// The current UTC date formatted as `YYYY-MM-DD`.
+fn today() -> String {
+    let secs = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |duration| duration.as_secs());
+    // civil-from-days conversion, see http://howardhinnant.github.io/date_algorithms.html
+    let days = (secs / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
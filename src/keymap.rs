@@ -0,0 +1,131 @@
+use ruscii::app::State;
+use ruscii::keyboard::{Key, KeyEvent};
+use thrust_core::action::Action;
+use thrust_core::config::KeyBindings;
+use thrust_core::input::FrameInput;
+use thrust_core::ship::FireMode;
+
+/// The actions that always need a key, as the game couldn't be played or paused without them.
+const REQUIRED_ACTIONS: [Action; 5] = [Action::MoveUp, Action::MoveDown, Action::MoveLeft, Action::MoveRight, Action::Pause];
+
+const KEY_NAMES: [(Key, &str); 75] = [
+    (Key::Esc, "Esc"), (Key::Space, "Space"), (Key::Enter, "Enter"), (Key::Backspace, "Backspace"),
+    (Key::CapsLock, "CapsLock"), (Key::Tab, "Tab"),
+    (Key::Up, "Up"), (Key::Down, "Down"), (Key::Left, "Left"), (Key::Right, "Right"),
+    (Key::Home, "Home"), (Key::End, "End"), (Key::PageUp, "PageUp"), (Key::PageDown, "PageDown"),
+    (Key::Insert, "Insert"), (Key::Delete, "Delete"),
+    (Key::A, "A"), (Key::B, "B"), (Key::C, "C"), (Key::D, "D"), (Key::E, "E"), (Key::F, "F"), (Key::G, "G"),
+    (Key::H, "H"), (Key::I, "I"), (Key::J, "J"), (Key::K, "K"), (Key::L, "L"), (Key::M, "M"), (Key::N, "N"),
+    (Key::O, "O"), (Key::P, "P"), (Key::Q, "Q"), (Key::R, "R"), (Key::S, "S"), (Key::T, "T"), (Key::U, "U"),
+    (Key::V, "V"), (Key::W, "W"), (Key::X, "X"), (Key::Y, "Y"), (Key::Z, "Z"),
+    (Key::Num0, "0"), (Key::Num1, "1"), (Key::Num2, "2"), (Key::Num3, "3"), (Key::Num4, "4"),
+    (Key::Num5, "5"), (Key::Num6, "6"), (Key::Num7, "7"), (Key::Num8, "8"), (Key::Num9, "9"),
+    (Key::F1, "F1"), (Key::F2, "F2"), (Key::F3, "F3"), (Key::F4, "F4"), (Key::F5, "F5"), (Key::F6, "F6"),
+    (Key::F7, "F7"), (Key::F8, "F8"), (Key::F9, "F9"), (Key::F10, "F10"), (Key::F11, "F11"), (Key::F12, "F12"),
+    (Key::Grave, "Grave"), (Key::Minus, "Minus"), (Key::Equal, "Equal"),
+    (Key::LeftBracket, "LeftBracket"), (Key::RightBracket, "RightBracket"), (Key::BackSlash, "BackSlash"),
+    (Key::Semicolon, "Semicolon"), (Key::Apostrophe, "Apostrophe"), (Key::Comma, "Comma"), (Key::Dot, "Dot"),
+    (Key::Slash, "Slash"),
+];
+
+pub fn parse_key(name: &str) -> Option<Key> {
+    KEY_NAMES.iter().find(|(_, key_name)| key_name.eq_ignore_ascii_case(name.trim())).map(|(key, _)| *key)
+}
+
+pub fn key_name(key: Key) -> &'static str {
+    KEY_NAMES.iter().find(|(other, _)| *other == key).map_or("?", |(_, name)| name)
+}
+
+/// The keys bound to every [`Action`], built from the `[keys]` table of the config.
+#[derive(Clone)]
+pub struct KeyMap {
+    bindings: Vec<(Action, Vec<Key>)>,
+}
+
+impl KeyMap {
+    pub fn new(key_bindings: &KeyBindings) -> Result<KeyMap, String> {
+        let mut keymap = KeyMap { bindings: Action::ALL.iter().map(|action| (*action, Vec::new())).collect() };
+        for action in Action::ALL {
+            for name in key_bindings.get(action) {
+                let key = parse_key(name)
+                    .ok_or_else(|| format!("unknown key '{}' in keys.{}", name, action.name()))?;
+                keymap.bind(action, key).map_err(|other| {
+                    format!("key '{}' is bound to both keys.{} and keys.{}", name, other.name(), action.name())
+                })?;
+            }
+        }
+        Ok(keymap)
+    }
+
+    pub fn keys(&self, action: Action) -> &[Key] {
+        self.bindings.iter().find(|(other, _)| *other == action).map_or(&[], |(_, keys)| keys)
+    }
+
+    /// The action `key` is bound to, if any.
+    pub fn action(&self, key: Key) -> Option<Action> {
+        self.bindings.iter().find(|(_, keys)| keys.contains(&key)).map(|(action, _)| *action)
+    }
+
+    /// Adds `key` to the keys of `action`. Fails with the other action if the key is already bound to a different one.
+    pub fn bind(&mut self, action: Action, key: Key) -> Result<(), Action> {
+        match self.action(key) {
+            Some(other) if other != action => Err(other),
+            Some(_) => Ok(()),
+            None => {
+                if let Some((_, keys)) = self.bindings.iter_mut().find(|(other, _)| *other == action) {
+                    keys.push(key);
+                }
+                Ok(())
+            }
+        }
+    }
+
+    pub fn clear(&mut self, action: Action) {
+        if let Some((_, keys)) = self.bindings.iter_mut().find(|(other, _)| *other == action) {
+            keys.clear();
+        }
+    }
+
+    /// An action that always needs a key but has none.
+    pub fn unbound_required_action(&self) -> Option<Action> {
+        REQUIRED_ACTIONS.into_iter().find(|action| self.keys(*action).is_empty())
+    }
+
+    pub fn to_bindings(&self) -> KeyBindings {
+        let mut key_bindings = KeyBindings::default();
+        for (action, keys) in &self.bindings {
+            *key_bindings.get_mut(*action) = keys.iter().map(|key| key_name(*key).to_string()).collect();
+        }
+        key_bindings
+    }
+
+    /// The names of the keys bound to `action`, e.g. `"W / Up"`.
+    pub fn describe(&self, action: Action) -> String {
+        let keys = self.keys(action);
+        if keys.is_empty() {
+            return "-".to_string();
+        }
+        keys.iter().map(|key| key_name(*key)).collect::<Vec<_>>().join(" / ")
+    }
+
+    /// Whether a key of `action` was pressed during the last frame.
+    pub fn pressed(&self, app_state: &State, action: Action) -> bool {
+        app_state.keyboard().last_key_events().iter()
+            .any(|key_event| matches!(key_event, KeyEvent::Pressed(key) if self.keys(action).contains(key)))
+    }
+
+    /// Whether a key of `action` is currently held down.
+    pub fn held(&self, app_state: &State, action: Action) -> bool {
+        app_state.keyboard().get_keys_down().iter().any(|key| self.keys(action).contains(key))
+    }
+
+    /// Collects the player's input of this frame, held actions count while their key is down,
+    /// all others once per key press. Fire is held unless the `fire_mode` toggles auto-shoot.
+    pub fn frame_input(&self, app_state: &State, fire_mode: FireMode) -> FrameInput {
+        let actions = Action::ALL.into_iter().filter(|action| {
+            let held = action.is_held() || (*action == Action::Fire && fire_mode != FireMode::Toggle);
+            if held { self.held(app_state, *action) } else { self.pressed(app_state, *action) }
+        });
+        FrameInput::from_actions(actions)
+    }
+}
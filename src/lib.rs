@@ -0,0 +1,10 @@
+//! The terminal frontend of thrust: the screens, drawing, key bindings and high-scores around the simulation of `thrust-core`.
+
+pub mod cli;
+pub mod draw;
+pub mod game_over;
+pub mod highscore;
+pub mod input;
+pub mod keymap;
+pub mod lobby;
+pub mod screen;
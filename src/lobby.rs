@@ -0,0 +1,93 @@
+use ruscii::app::State;
+use ruscii::drawing::Pencil;
+use ruscii::keyboard::Key;
+use ruscii::spatial::Vec2;
+use ruscii::terminal::Color;
+use thrust_core::config::GameConfig;
+use thrust_core::game_state::GameMode;
+use thrust_core::net::{self, Host, Lockstep};
+use crate::input::key_pressed;
+
+enum Role {
+    /// Waits for a player on the port to play in `mode`, `None` if the port couldn't be opened.
+    Hosting { port: u16, mode: GameMode, host: Option<Host> },
+    /// Connects to the host once the screen was `drawn`, as connecting blocks for a moment.
+    Joining { addr: String, drawn: bool },
+}
+
+/// The screen before a networked game: the host waits for a player to join, the joining player connects to the host.
+pub struct LobbyScreen {
+    role: Role,
+    error: Option<String>,
+}
+
+pub enum LobbyChoice {
+    Start(Box<Lockstep>),
+    Cancel,
+}
+
+impl LobbyScreen {
+    pub fn host(port: u16, mode: GameMode) -> Self {
+        match Host::bind(port) {
+            Ok(host) => Self { role: Role::Hosting { port, mode, host: Some(host) }, error: None },
+            Err(err) => Self { role: Role::Hosting { port, mode, host: None }, error: Some(err.to_string()) },
+        }
+    }
+
+    pub fn join(addr: &str) -> Self {
+        Self { role: Role::Joining { addr: addr.to_string(), drawn: false }, error: None }
+    }
+
+    /// Waits for the other player, the host starts the game with `seed` and `config`.
+    /// A failed attempt is shown, the host keeps waiting and the joining player can retry with Enter.
+    pub fn handle_input(&mut self, app_state: &State, seed: u64, config: &GameConfig) -> Option<LobbyChoice> {
+        if key_pressed(app_state, Key::Esc) {
+            return Some(LobbyChoice::Cancel);
+        }
+        let result = match &mut self.role {
+            Role::Hosting { host: Some(host), .. } => host.accept(seed, config),
+            Role::Hosting { host: None, .. } => Ok(None),
+            Role::Joining { drawn, .. } if !*drawn => {
+                *drawn = true;
+                return None;
+            }
+            Role::Joining { drawn, .. } if self.error.is_some() => {
+                if key_pressed(app_state, Key::Enter) {
+                    self.error = None;
+                    *drawn = false;
+                }
+                return None;
+            }
+            Role::Joining { addr, .. } => net::join(addr).map(Some),
+        };
+        match result {
+            Ok(lockstep) => lockstep.map(|lockstep| LobbyChoice::Start(Box::new(lockstep))),
+            Err(err) => {
+                self.error = Some(err.to_string());
+                None
+            }
+        }
+    }
+
+    pub fn draw(&self, win_size: Vec2, pencil: &mut Pencil) {
+        pencil.set_origin(win_size / 2 - Vec2::y(3));
+        pencil.set_foreground(Color::Cyan);
+        pencil.draw_center_text("Network game", Vec2::zero());
+        pencil.set_foreground(Color::White);
+        let status = match &self.role {
+            Role::Hosting { port, mode, .. } => format!("Waiting for a player to join the {} game on port {}...", mode.name(), port),
+            Role::Joining { addr, .. } => format!("Joining the game at {}...", addr),
+        };
+        pencil.draw_center_text(&status, Vec2::y(2));
+        if let Some(error) = &self.error {
+            pencil.set_foreground(Color::Red);
+            pencil.draw_center_text(error, Vec2::y(4));
+        }
+        pencil.set_foreground(Color::Grey);
+        let hint = match self.role {
+            Role::Joining { .. } => "Enter: retry   Esc: back",
+            Role::Hosting { .. } => "Esc: back",
+        };
+        pencil.draw_center_text(hint, Vec2::y(6));
+    }
+}
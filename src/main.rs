@@ -1,119 +1,508 @@
-mod game_state;
-mod input;
-mod constant;
-mod enemy;
-mod ship;
-mod shot;
-mod goodie;
-
-use std::ops::Add;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::exit;
+use std::vec;
 use ruscii::app::{App, Config, State};
-use ruscii::terminal::{Window, Color};
-use ruscii::drawing::{Pencil, RectCharset};
-use ruscii::spatial::{Vec2};
-use ruscii::gui::{FPSCounter};
-use crate::constant::{BORDER_SIZE, CHAR_HEALTH, CHAR_HEALTH_BAR, CHAR_SHIELD, CHAR_SHIELD_BAR, FPS_LIMIT, GAME_SIZE, HUD_HEALTH_GRANULARITY, HUD_SHIELD_GRANULARITY};
+use ruscii::terminal::Window;
+use ruscii::drawing::Pencil;
+use ruscii::gui::FPSCounter;
+use ruscii::keyboard::Key;
+use thrust_core::action::Action;
+use thrust_core::agent::{self, Env};
+use thrust_core::autopilot;
+use thrust_core::config::{GameConfig, KeyBindings};
+use thrust_core::game_state::{GameMode, GameState};
+use thrust_core::input::FrameInput;
+use thrust_core::level::Level;
+use thrust_core::net::Lockstep;
+use thrust_core::replay::Replay;
+use thrust_core::sim;
+use thrust_core::spectate::{Spectator, SpectatorServer};
 
-use crate::game_state::GameState;
-use crate::input::handle_user_input;
+use thrust::draw::{cell, draw_border, draw_fps, draw_game, draw_hud};
+use thrust::cli::{CliArgs, SimArgs, SimFormat};
+use thrust::game_over::GameOverScreen;
+use thrust::highscore::HighScores;
+use thrust::input::{first_pressed_key, key_pressed};
+use thrust::keymap::KeyMap;
+use thrust::lobby::{LobbyChoice, LobbyScreen};
+use thrust::screen::{draw_demo_hint, draw_end_message, draw_high_scores, draw_tutorial, draw_waiting, ControlsChoice, ControlsScreen,
+                    Menu, Screen, AUTOPILOT_RESTART_SECONDS, DEMO_DELAY_SECONDS, END_HINT, NETWORK_END_HINT, PAUSE_OPTIONS,
+                    TITLE_OPTIONS};
 
-fn main() {
-    let mut app = App::config(Config::new().fps(FPS_LIMIT));
-    let mut game_state = GameState::new(Vec2::xy(GAME_SIZE.0, GAME_SIZE.1));
-    let mut fps_counter = FPSCounter::new();
+/// Everything that outlives a single game: restarting rebuilds the game state from here.
+struct Session {
+    args: CliArgs,
+    config: GameConfig,
+    replay: Option<Replay>,
+    levels: Option<Vec<Level>>,
+    keymap: KeyMap,
+    /// The keys of the second ship in co-op.
+    keymap_player2: KeyMap,
+    game_state: GameState,
+    /// The seed of the next game if `--seed` was given. `--autopilot` moves on to the following seed after every game.
+    seed: Option<u64>,
+    recording: Option<Replay>,
+    playback: Option<vec::IntoIter<Vec<FrameInput>>>,
+    /// The connection of a networked game, kept until its end screen is left.
+    network: Option<Lockstep>,
+    /// The frames the networked game has been waiting for the other player's input.
+    waiting_frames: usize,
+    /// The frames the end screen of an `--autopilot` game has been shown.
+    finished_frames: usize,
+    /// Streams every frame to spectators if `--serve` was given.
+    spectators: Option<SpectatorServer>,
+    /// The game streamed by another session if `--watch` was given.
+    watching: Option<Spectator>,
+    screen: Screen,
+    fps_counter: FPSCounter,
+}
 
-    app.run(|app_state: &mut State, window: &mut Window| {
-        handle_user_input(&mut game_state, app_state);
+impl Session {
+    fn new(args: CliArgs) -> Self {
+        let replay = args.replay.as_deref().map(load_replay);
+        let watching = args.watch.as_deref().map(watch);
+        // the keys always come from the local config, a replay only brings the config of the simulation
+        let mut local_config = load_config(&args);
+        if args.versus {
+            local_config.mode = GameMode::Versus;
+        }
+        let keymap = load_keymap(&local_config.keys);
+        let keymap_player2 = load_keymap(&local_config.keys_player2);
+        let config = match (&replay, &watching) {
+            (Some(replay), _) => replay.config.clone(),
+            (None, Some(spectator)) => spectator.config.clone(),
+            (None, None) => local_config,
+        };
+        let levels = args.level.as_deref().map(|path| load_levels(path, config.dimension()));
+        let screen = match (&replay, args.host, &args.join) {
+            _ if watching.is_some() => Screen::Watching,
+            (Some(_), _, _) => Screen::Playing,
+            (None, Some(port), _) => Screen::Lobby(LobbyScreen::host(port, config.mode)),
+            (None, None, Some(addr)) => Screen::Lobby(LobbyScreen::join(addr)),
+            (None, None, None) if args.autopilot => Screen::Playing,
+            (None, None, None) => Screen::Title(Menu::new(&TITLE_OPTIONS)),
+        };
+        let spectators = args.serve.map(serve);
+        let mut session = Self {
+            game_state: GameState::new(config.clone()),
+            seed: args.seed,
+            args,
+            config,
+            replay,
+            levels,
+            keymap,
+            keymap_player2,
+            recording: None,
+            playback: None,
+            network: None,
+            waiting_frames: 0,
+            finished_frames: 0,
+            spectators,
+            watching,
+            screen,
+            fps_counter: FPSCounter::new(),
+        };
+        session.restart();
+        session
+    }
 
+    /// Starts a new game, replays and fixed seeds start over with the same seed.
+    fn restart(&mut self) {
+        let seed = self.replay.as_ref().map(|replay| replay.seed).or(self.seed);
+        self.game_state = match seed {
+            Some(seed) => GameState::with_seed(self.config.clone(), seed),
+            None => GameState::new(self.config.clone()),
+        };
+        if let Some(levels) = &self.levels {
+            self.game_state.start_levels(levels.clone());
+        }
+        self.recording = self.args.record.as_ref().map(|_| Replay::new(self.game_state.seed, self.config.clone()));
+        self.playback = self.replay.as_ref().map(|replay| replay.inputs.clone().into_iter());
+    }
+
+    /// Starts a new game with `players` ships. A replay is always played with the ships it was recorded with.
+    fn play(&mut self, players: usize) {
+        self.config.players = self.replay.as_ref().map_or(players, |replay| replay.config.players);
+        self.restart();
+        self.screen = Screen::Playing;
+    }
+
+    /// Starts the game agreed on in the lobby, this side flies the ship of `lockstep.local_player`.
+    fn play_network(&mut self, lockstep: Lockstep) {
+        self.game_state = lockstep.new_game();
+        self.recording = self.args.record.as_ref().map(|_| Replay::new(lockstep.seed, lockstep.config.clone()));
+        self.playback = None;
+        self.network = Some(lockstep);
+        self.waiting_frames = 0;
+        self.screen = Screen::Playing;
+    }
+
+    /// Ends a networked game, telling the other player.
+    fn leave_network(&mut self) {
+        if let Some(lockstep) = self.network.take() {
+            lockstep.leave();
+        }
+    }
+
+    fn frame(&mut self, app_state: &mut State, window: &mut Window) {
         let win_size = window.size();
         let mut pencil = Pencil::new(window.canvas_mut());
 
-        if is_game_over(&game_state) {
-            render_game_over_screen(&game_state, win_size, &mut pencil);
-            return ();
+        match &mut self.screen {
+            Screen::Title(menu) => {
+                let choice = menu.handle_input(app_state, &self.keymap);
+                if menu.idle_frames > DEMO_DELAY_SECONDS * self.config.fps_limit as usize {
+                    let config = GameConfig { players: 1, ..self.config.clone() };
+                    self.screen = Screen::Demo(Box::new(GameState::new(config)));
+                    return;
+                }
+                match choice {
+                    Some("Play") => self.play(1),
+                    Some("Co-op") => self.play(2),
+                    Some("Tutorial") => self.screen = Screen::Tutorial,
+                    Some("Controls") => {
+                        self.screen = Screen::Controls(ControlsScreen::new(&self.keymap, &self.keymap_player2));
+                    }
+                    Some("High-scores") => self.screen = Screen::HighScores(HighScores::open_default().ok()),
+                    Some("Quit") => app_state.stop(),
+                    _ => menu.draw("THRUST", win_size, &mut pencil),
+                }
+            }
+            Screen::Tutorial => {
+                if key_pressed(app_state, Key::Enter) || key_pressed(app_state, Key::Esc) {
+                    self.screen = Screen::Title(Menu::new(&TITLE_OPTIONS));
+                } else {
+                    draw_tutorial(&self.config, &self.keymap, &self.keymap_player2, win_size, &mut pencil);
+                }
+            }
+            Screen::HighScores(high_scores) => {
+                if key_pressed(app_state, Key::Enter) || key_pressed(app_state, Key::Esc) {
+                    self.screen = Screen::Title(Menu::new(&TITLE_OPTIONS));
+                } else {
+                    draw_high_scores(high_scores, win_size, &mut pencil);
+                }
+            }
+            Screen::Controls(controls_screen) => {
+                match controls_screen.handle_input(app_state, &self.keymap) {
+                    Some(ControlsChoice::Save) => {
+                        let [keymap, keymap_player2] = &controls_screen.keymaps;
+                        let path = user_config_path(&self.args);
+                        match GameConfig::store_keys(&path, &keymap.to_bindings(), &keymap_player2.to_bindings()) {
+                            Ok(()) => {
+                                self.keymap = keymap.clone();
+                                self.keymap_player2 = keymap_player2.clone();
+                                self.screen = Screen::Title(Menu::new(&TITLE_OPTIONS));
+                            }
+                            Err(err) => controls_screen.message = Some(err.to_string()),
+                        }
+                    }
+                    Some(ControlsChoice::Cancel) => self.screen = Screen::Title(Menu::new(&TITLE_OPTIONS)),
+                    None => controls_screen.draw(win_size, &mut pencil),
+                }
+            }
+            Screen::Lobby(lobby) => {
+                // the host picks the seed, a networked game can't be paused or restarted
+                let seed = self.args.seed.unwrap_or_else(rand::random);
+                match lobby.handle_input(app_state, seed, &self.config) {
+                    Some(LobbyChoice::Start(lockstep)) => self.play_network(*lockstep),
+                    Some(LobbyChoice::Cancel) => self.screen = Screen::Title(Menu::new(&TITLE_OPTIONS)),
+                    None => lobby.draw(win_size, &mut pencil),
+                }
+            }
+            Screen::Playing => {
+                if self.keymap.pressed(app_state, Action::Pause) {
+                    self.screen = if self.network.is_some() {
+                        Screen::ConnectionClosed("You left the game".to_string())
+                    } else {
+                        Screen::Paused(Menu::new(&PAUSE_OPTIONS))
+                    };
+                    self.leave_network();
+                    return;
+                }
+                self.update_game(app_state);
+                pencil.set_origin((win_size - cell(self.game_state.dimension)) / 2);
+                draw_border(&self.game_state, &mut pencil);
+                draw_hud(&self.game_state, &mut pencil);
+                draw_game(&self.game_state, &mut pencil);
+                draw_fps(&mut self.fps_counter, &mut pencil);
+                if self.waiting_frames > self.config.fps_limit as usize {
+                    draw_waiting(&self.game_state, &mut pencil);
+                }
+            }
+            Screen::Watching => {
+                if key_pressed(app_state, Key::Esc) || key_pressed(app_state, Key::Q) {
+                    app_state.stop();
+                    return;
+                }
+                if let Some(spectator) = self.watching.as_mut() {
+                    match spectator.poll() {
+                        Ok(Some(snapshot)) => snapshot.restore(&mut self.game_state),
+                        Ok(None) => (),
+                        Err(err) => {
+                            self.watching = None;
+                            self.screen = Screen::ConnectionClosed(format!("Stream ended, {}", err));
+                            return;
+                        }
+                    }
+                }
+                pencil.set_origin((win_size - cell(self.game_state.dimension)) / 2);
+                draw_border(&self.game_state, &mut pencil);
+                draw_hud(&self.game_state, &mut pencil);
+                draw_game(&self.game_state, &mut pencil);
+                draw_fps(&mut self.fps_counter, &mut pencil);
+            }
+            Screen::Demo(demo) => {
+                if first_pressed_key(app_state).is_some() || demo.is_game_over() {
+                    self.screen = Screen::Title(Menu::new(&TITLE_OPTIONS));
+                    return;
+                }
+                demo.update(&[FrameInput::from_actions(autopilot::actions(demo, 0))]);
+                pencil.set_origin((win_size - cell(demo.dimension)) / 2);
+                draw_border(demo, &mut pencil);
+                draw_hud(demo, &mut pencil);
+                draw_game(demo, &mut pencil);
+                draw_demo_hint(demo, &mut pencil);
+            }
+            Screen::Paused(menu) => {
+                if self.keymap.pressed(app_state, Action::Pause) {
+                    self.screen = Screen::Playing;
+                    return;
+                }
+                match menu.handle_input(app_state, &self.keymap) {
+                    Some("Resume") => self.screen = Screen::Playing,
+                    Some("Restart") => self.play(self.config.players),
+                    Some("Main menu") => self.screen = Screen::Title(Menu::new(&TITLE_OPTIONS)),
+                    Some("Quit") => app_state.stop(),
+                    _ => menu.draw("Paused", win_size, &mut pencil),
+                }
+            }
+            Screen::GameOver(_) if self.args.autopilot && self.network.is_none() => {
+                self.finished_frames += 1;
+                if self.finished_frames > AUTOPILOT_RESTART_SECONDS * self.config.fps_limit as usize {
+                    // a soak test shouldn't play the same game over and over
+                    self.seed = self.seed.map(|seed| seed.wrapping_add(1));
+                    self.play(self.config.players);
+                } else if !self.handle_end_input(app_state) {
+                    if let Screen::GameOver(game_over_screen) = &self.screen {
+                        game_over_screen.draw(&self.game_state, win_size, &mut pencil);
+                    }
+                }
+            }
+            Screen::GameOver(game_over_screen) => {
+                let entering_name = game_over_screen.handle_input(app_state.keyboard().last_key_events(), &self.game_state);
+                if !entering_name && self.handle_end_input(app_state) {
+                    return;
+                }
+                if let Screen::GameOver(game_over_screen) = &self.screen {
+                    game_over_screen.draw(&self.game_state, win_size, &mut pencil);
+                }
+            }
+            Screen::ReplayFinished => {
+                if !self.handle_end_input(app_state) {
+                    draw_end_message("Replay finished", self.game_state.score(), self.game_state.seed, END_HINT, win_size, &mut pencil);
+                }
+            }
+            Screen::ConnectionClosed(reason) => {
+                let reason = reason.clone();
+                if !self.handle_end_input(app_state) {
+                    draw_end_message(&reason, self.game_state.score(), self.game_state.seed, NETWORK_END_HINT, win_size, &mut pencil);
+                }
+            }
+        }
+    }
+
+    /// Advances the game by one frame with the players' or the replay's inputs and switches to the end screens.
+    /// A networked game only advances once the other player's input arrived.
+    fn update_game(&mut self, app_state: &State) {
+        if let Some(lockstep) = self.network.as_mut() {
+            let input = if self.args.autopilot {
+                FrameInput::from_actions(autopilot::actions(&self.game_state, lockstep.local_player))
+            } else {
+                self.keymap.frame_input(app_state, lockstep.config.fire_mode)
+            };
+            match lockstep.step(&mut self.game_state, input) {
+                Ok(Some(inputs)) => {
+                    self.waiting_frames = 0;
+                    self.finish_frame(&inputs);
+                }
+                Ok(None) => self.waiting_frames += 1,
+                Err(err) => {
+                    self.network = None;
+                    self.screen = Screen::ConnectionClosed(format!("Game ended, {}", err));
+                }
+            }
+            return;
+        }
+        let inputs = match self.playback.as_mut() {
+            Some(inputs) => inputs.next(),
+            None => Some([&self.keymap, &self.keymap_player2][..self.config.players].iter().enumerate()
+                .map(|(player, keymap)| if self.args.autopilot && player == 0 {
+                    FrameInput::from_actions(autopilot::actions(&self.game_state, player))
+                } else {
+                    keymap.frame_input(app_state, self.config.fire_mode)
+                })
+                .collect()),
+        };
+        let Some(inputs) = inputs else {
+            self.screen = Screen::ReplayFinished;
+            return;
+        };
+        self.game_state.update(&inputs);
+        self.finish_frame(&inputs);
+    }
+
+    /// Records the `inputs` of the simulated frame and switches to the end screen once the game is over.
+    fn finish_frame(&mut self, inputs: &[FrameInput]) {
+        if let Some(recording) = self.recording.as_mut() {
+            recording.record(inputs);
+        }
+        if self.game_state.is_game_over() || self.game_state.is_level_complete() {
+            let title = if self.game_state.is_game_over() { "Game Over" } else { "Level complete" };
+            let hint = if self.network.is_some() { NETWORK_END_HINT } else { END_HINT };
+            // the autopilot's scores don't belong in the high-score table
+            let allow_name_entry = self.playback.is_none() && !self.args.autopilot;
+            self.screen = Screen::GameOver(GameOverScreen::new(title, &self.game_state, allow_name_entry, hint));
+            self.finished_frames = 0;
+        }
+    }
+
+    /// Handles the keys shown below a finished game, returns whether the screen changed.
+    /// Networked games can't be restarted, leaving their end screen closes the connection.
+    fn handle_end_input(&mut self, app_state: &mut State) -> bool {
+        let networked = self.network.is_some() || matches!(self.screen, Screen::ConnectionClosed(_));
+        if key_pressed(app_state, Key::R) && !networked {
+            self.play(self.config.players);
+        } else if key_pressed(app_state, Key::Enter) {
+            self.leave_network();
+            self.screen = Screen::Title(Menu::new(&TITLE_OPTIONS));
+        } else if key_pressed(app_state, Key::Q) || key_pressed(app_state, Key::Esc) {
+            self.leave_network();
+            app_state.stop();
+        } else {
+            return false;
         }
+        true
+    }
 
-        game_state.update(app_state.step());
+    /// Sends the current game to the spectators, on every frame so that newly connected ones are served in menus too.
+    fn stream(&mut self) {
+        if let Some(spectators) = self.spectators.as_mut() {
+            spectators.broadcast(&self.game_state);
+        }
+    }
 
-        pencil.set_origin((win_size - game_state.dimension) / 2);
+    /// Leaves a networked game and saves the last game if `--record` was given.
+    fn close(mut self) {
+        self.leave_network();
+        if let (Some(recording), Some(path)) = (self.recording, self.args.record) {
+            if let Err(err) = recording.save(&path) {
+                eprintln!("error: {}", err);
+                exit(1);
+            }
+        }
+    }
+}
 
-        draw_border(&game_state, &mut pencil);
-        draw_hud(&game_state, &mut pencil);
-        draw_game(&game_state, &mut pencil);
-        draw_fps(&mut fps_counter, &mut pencil);
+fn main() {
+    let args = CliArgs::parse();
+    if args.bot {
+        play_bot(&args);
+        return;
+    }
+    if let Some(sim_args) = &args.sim {
+        simulate(&args, sim_args);
+        return;
+    }
+    let mut session = Session::new(args);
+    let mut app = App::config(Config::new().fps(session.config.fps_limit));
+    app.run(|app_state: &mut State, window: &mut Window| {
+        session.frame(app_state, window);
+        session.stream();
     });
+    session.close();
 }
 
-fn draw_fps(fps_counter: &mut FPSCounter, pencil: &mut Pencil) {
-    fps_counter.update();
-    pencil.set_foreground(Color::White);
-    pencil.draw_text(&format!("FPS: {}", fps_counter.count()), Vec2::xy(1, 0));
+/// Runs games for a bot connected to stdin and stdout, without touching the terminal.
+fn play_bot(args: &CliArgs) {
+    let mut env = Env::new(load_config(args));
+    if let Err(err) = agent::serve(&mut env, io::stdin().lock(), io::stdout().lock()) {
+        eprintln!("error: {}", err);
+        exit(1);
+    }
 }
 
-fn is_game_over(game_state: &GameState) -> bool {
-    game_state.health == 0
+/// Plays the games asked for by `thrust sim` and prints how they went.
+fn simulate(args: &CliArgs, sim_args: &SimArgs) {
+    let config = load_config(args);
+    let results = sim::run_all(&config, sim_args.seeds(), sim_args.pilot, sim_args.max_frames(config.fps_limit));
+    match sim_args.format {
+        SimFormat::Json => println!("{:#}", sim::summary(&results)),
+        SimFormat::Csv => print!("{}", sim::csv(&results)),
+    }
 }
 
-fn render_game_over_screen(game_state: &GameState, win_size: Vec2, pencil: &mut Pencil) {
-    let msg = &format!("Game Over  -  score: {}", game_state.score);
-    pencil.set_origin(win_size / 2 - Vec2::x(msg.len() / 2));
-    pencil.draw_text(msg, Vec2::zero());
+fn load_keymap(key_bindings: &KeyBindings) -> KeyMap {
+    KeyMap::new(key_bindings).unwrap_or_else(|err| {
+        eprintln!("error: invalid config: {}", err);
+        exit(1);
+    })
 }
 
-fn draw_game(game_state: &GameState, pencil: &mut Pencil) {
-    game_state.ship.draw(pencil);
-    for shot in &game_state.enemy_shots {
-        shot.draw(pencil);
-    }
-    for enemy in &game_state.enemies {
-        enemy.draw(pencil);
-    }
-    for goodie in &game_state.goodies {
-        goodie.draw(pencil);
+fn serve(port: u16) -> SpectatorServer {
+    SpectatorServer::bind(port).unwrap_or_else(|err| {
+        eprintln!("error: can't stream on port {}: {}", port, err);
+        exit(1);
+    })
+}
+
+fn watch(addr: &str) -> Spectator {
+    Spectator::connect(addr).unwrap_or_else(|err| {
+        eprintln!("error: can't watch {}: {}", addr, err);
+        exit(1);
+    })
+}
+
+fn load_replay(path: &Path) -> Replay {
+    let replay = Replay::load(path).unwrap_or_else(|err| {
+        eprintln!("error: {}", err);
+        exit(1);
+    });
+    if !replay.is_current_version() {
+        eprintln!("error: replay was recorded with simulation version {}, but this is version {}",
+                  replay.simulation_version, thrust_core::SIMULATION_VERSION);
+        exit(1);
     }
+    replay
 }
 
-fn draw_border(game_state: &GameState, pencil: &mut Pencil) {
-    pencil.set_foreground(Color::Grey);
-    let border_rect = game_state.dimension.add(Vec2::xy(BORDER_SIZE, BORDER_SIZE));
-    pencil.draw_rect(&RectCharset::simple_round_lines(), Vec2::zero(), border_rect);
+/// The config given by `--config`, otherwise `config.toml` in the user's config directory.
+fn user_config_path(args: &CliArgs) -> PathBuf {
+    args.config.clone().unwrap_or_else(|| {
+        dirs::config_dir().unwrap_or_default().join("thrust").join("config.toml")
+    })
 }
 
-fn draw_hud(game_state: &GameState, pencil: &mut Pencil) {
-    let mut pos = Vec2::xy(-2, 0);
-    let digits = &format!("{}", game_state.health);
-    let chars = digits.chars();
-    pos.y -= chars.count() as i32;
-    pencil.set_foreground(Color::White);
-    for ch in digits.chars() {
-        pencil.draw_char(ch, pos);
-        pos.y += 1;
-    }
-    pencil.set_foreground(Color::Red);
-    pencil.draw_char(CHAR_HEALTH, pos);
-    let curr_health_hud = game_state.health / HUD_HEALTH_GRANULARITY;
-    for _ in 0..curr_health_hud {
-        pos.y += 1;
-        pencil.draw_char(CHAR_HEALTH_BAR, pos);
-    }
-
-    let mut pos = Vec2::xy(-4, 0);
-    let digits = &format!("{}", game_state.shield);
-    let chars = digits.chars();
-    pos.y -= chars.count() as i32;
-    pencil.set_foreground(Color::White);
-    for ch in digits.chars() {
-        pencil.draw_char(ch, pos);
-        pos.y += 1;
-    }
-    pencil.set_foreground(Color::Yellow);
-    pencil.draw_char(CHAR_SHIELD, pos);
-    let curr_shield_hud = game_state.shield / HUD_SHIELD_GRANULARITY;
-    for _ in 0..curr_shield_hud {
-        pos.y += 1;
-        pencil.draw_char(CHAR_SHIELD_BAR, pos);
-    }
-    pencil.set_foreground(Color::White);
-    let status_msg = &format!("Score: {}", game_state.score);
-    pencil.draw_text(status_msg, Vec2::xy(25, -1));
+/// Loads the config given by `--config`, or the one in the user's config directory if there is any.
+fn load_config(args: &CliArgs) -> GameConfig {
+    let path = Some(user_config_path(args)).filter(|path| args.config.is_some() || path.exists());
+    GameConfig::load(path.as_deref(), &args.overrides).unwrap_or_else(|err| {
+        eprintln!("error: {}", err);
+        exit(1);
+    })
 }
+
+fn load_levels(path: &Path, dimension: thrust_core::spatial::Vec2) -> Vec<Level> {
+    let levels = Level::load_all(path).unwrap_or_else(|err| {
+        eprintln!("error: {}", err);
+        exit(1);
+    });
+    for level in &levels {
+        if let Err(err) = level.validate(dimension) {
+            eprintln!("error: {}", err);
+            exit(1);
+        }
+    }
+    levels
+}
+
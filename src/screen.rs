@@ -0,0 +1,294 @@
+use ruscii::app::State;
+use ruscii::drawing::Pencil;
+use ruscii::keyboard::Key;
+use ruscii::spatial::Vec2;
+use ruscii::terminal::Color;
+use thrust_core::action::Action;
+use thrust_core::config::{GameConfig, KeyBindings};
+use thrust_core::game_state::GameState;
+use thrust_core::ship::FireMode;
+use crate::draw::cell;
+use crate::game_over::{draw_table, GameOverScreen};
+use crate::highscore::HighScores;
+use crate::input::{first_pressed_key, key_pressed};
+use crate::keymap::{key_name, KeyMap};
+use crate::lobby::LobbyScreen;
+
+pub const TITLE_OPTIONS: [&str; 6] = ["Play", "Co-op", "Tutorial", "Controls", "High-scores", "Quit"];
+pub const PAUSE_OPTIONS: [&str; 4] = ["Resume", "Restart", "Main menu", "Quit"];
+/// How long the title screen waits for a key before showing a demo game.
+pub const DEMO_DELAY_SECONDS: usize = 10;
+/// How long the end screen of an `--autopilot` game is shown before the next game starts.
+pub const AUTOPILOT_RESTART_SECONDS: usize = 3;
+
+/// The screens of the game, one of which is shown at a time.
+pub enum Screen {
+    Title(Menu),
+    Tutorial,
+    Playing,
+    Paused(Menu),
+    GameOver(GameOverScreen),
+    ReplayFinished,
+    /// Waits for the other player of a networked game.
+    Lobby(LobbyScreen),
+    /// A networked game ended early, with the reason why.
+    ConnectionClosed(String),
+    /// Draws the game streamed by another session.
+    Watching,
+    /// The autopilot plays a game while the title screen is left alone.
+    Demo(Box<GameState>),
+    HighScores(Option<HighScores>),
+    Controls(ControlsScreen),
+}
+
+/// Moves a menu selection with the arrow keys or the keys bound to moving up and down.
+fn menu_step(app_state: &State, keymap: &KeyMap) -> i32 {
+    if key_pressed(app_state, Key::Up) || keymap.pressed(app_state, Action::MoveUp) {
+        -1
+    } else if key_pressed(app_state, Key::Down) || keymap.pressed(app_state, Action::MoveDown) {
+        1
+    } else {
+        0
+    }
+}
+
+fn step_selection(selected: usize, step: i32, len: usize) -> usize {
+    (selected as i32 + step).rem_euclid(len as i32) as usize
+}
+
+/// A vertical list of options, navigated with the arrow keys or the movement keys and confirmed with Enter.
+pub struct Menu {
+    pub options: &'static [&'static str],
+    pub selected: usize,
+    /// The frames since the menu was shown or a key was last pressed.
+    pub idle_frames: usize,
+}
+
+impl Menu {
+    pub fn new(options: &'static [&'static str]) -> Self {
+        Self { options, selected: 0, idle_frames: 0 }
+    }
+
+    /// Moves the selection and returns the selected option once it is confirmed.
+    pub fn handle_input(&mut self, app_state: &State, keymap: &KeyMap) -> Option<&'static str> {
+        self.idle_frames = if first_pressed_key(app_state).is_some() { 0 } else { self.idle_frames + 1 };
+        if key_pressed(app_state, Key::Enter) {
+            return Some(self.options[self.selected]);
+        }
+        self.selected = step_selection(self.selected, menu_step(app_state, keymap), self.options.len());
+        None
+    }
+
+    pub fn draw(&self, title: &str, win_size: Vec2, pencil: &mut Pencil) {
+        pencil.set_origin(Vec2::xy(win_size.x / 2, win_size.y / 2 - self.options.len() as i32));
+        pencil.set_foreground(Color::Cyan);
+        pencil.draw_center_text(title, Vec2::zero());
+        for (i, option) in self.options.iter().enumerate() {
+            let (color, text) = if i == self.selected {
+                (Color::Yellow, format!("> {} <", option))
+            } else {
+                (Color::White, option.to_string())
+            };
+            pencil.set_foreground(color);
+            pencil.draw_center_text(&text, Vec2::y(2 + i as i32));
+        }
+    }
+}
+
+pub fn draw_tutorial(config: &GameConfig, keymap: &KeyMap, keymap_player2: &KeyMap, win_size: Vec2, pencil: &mut Pencil) {
+    let glyphs = &config.glyphs;
+    let key = |action| format!("{:<16}", keymap.describe(action));
+    let fire_line = match config.fire_mode {
+        FireMode::Toggle => format!("{} toggle auto-shoot, {} shoots once", key(Action::ToggleAutoShoot), keymap.describe(Action::Fire)),
+        FireMode::Hold => format!("{} hold to shoot", key(Action::Fire)),
+        FireMode::Charge => format!("{} hold to charge, release to shoot", key(Action::Fire)),
+    };
+    let lines = [
+        "Destroy the enemies before they reach the ground.".to_string(),
+        "Every escaped enemy damages your health, bypassing the shield.".to_string(),
+        String::new(),
+        format!("{}{}{}{} move the ship", key(Action::MoveUp), key(Action::MoveLeft), key(Action::MoveDown), key(Action::MoveRight)),
+        fire_line,
+        format!("{} pause the game", key(Action::Pause)),
+        format!("In co-op, the second ship moves with {} {} {} {}, fires with {} and bombs with {}.",
+                keymap_player2.describe(Action::MoveUp), keymap_player2.describe(Action::MoveLeft),
+                keymap_player2.describe(Action::MoveDown), keymap_player2.describe(Action::MoveRight),
+                keymap_player2.describe(Action::Fire), keymap_player2.describe(Action::Bomb)),
+        String::new(),
+        "Destroyed enemies drop goodies, touch them to pick them up:".to_string(),
+        format!("  {}        repair kit, heals the ship", glyphs.health),
+        format!("  {}        shield boost, charges the shield", glyphs.shield),
+        format!("  {} {} {}    ship upgrade, changes the main weapon",
+                glyphs.upgrade_ship_basic, glyphs.upgrade_ship_diagonal, glyphs.upgrade_ship_strong),
+        format!("  {} {} {} {}  rapid fire, spread, multiplier, invincibility",
+                glyphs.rapid_fire, glyphs.spread, glyphs.score_multiplier, glyphs.invincibility),
+        format!("  {}        bomb, stored until {} clears the field", glyphs.bomb, keymap.describe(Action::Bomb)),
+        String::new(),
+        format!("{} {} {} enemies need more hits the bigger they are,", glyphs.enemy, glyphs.enemy_fighter, glyphs.enemy_tank),
+        "and change their color when damaged.".to_string(),
+        "Bosses appear at score milestones, watch their health bar.".to_string(),
+    ];
+    pencil.set_origin(Vec2::xy(win_size.x / 2 - 32, win_size.y / 2 - lines.len() as i32 / 2 - 2));
+    pencil.set_foreground(Color::Cyan);
+    pencil.draw_text("How to play", Vec2::zero());
+    pencil.set_foreground(Color::White);
+    for (i, line) in lines.iter().enumerate() {
+        pencil.draw_text(line, Vec2::y(2 + i as i32));
+    }
+    pencil.set_foreground(Color::Grey);
+    pencil.draw_text("Enter / Esc: back", Vec2::y(lines.len() as i32 + 3));
+}
+
+pub fn draw_high_scores(high_scores: &Option<HighScores>, win_size: Vec2, pencil: &mut Pencil) {
+    pencil.set_origin(Vec2::xy(win_size.x / 2, win_size.y / 2 - 8));
+    pencil.set_foreground(Color::Cyan);
+    pencil.draw_center_text("High-scores", Vec2::zero());
+    pencil.set_foreground(Color::Grey);
+    pencil.draw_center_text("Enter / Esc: back", Vec2::y(17));
+    match high_scores {
+        Some(high_scores) => draw_table(high_scores, None, pencil),
+        None => {
+            pencil.set_foreground(Color::Red);
+            pencil.draw_center_text("High-scores unavailable", Vec2::y(2));
+        }
+    }
+}
+
+/// Shows a one line summary of the finished game together with the keys to continue.
+pub fn draw_end_message(title: &str, score: usize, seed: u64, hint: &str, win_size: Vec2, pencil: &mut Pencil) {
+    pencil.set_origin(win_size / 2);
+    pencil.set_foreground(Color::White);
+    pencil.draw_center_text(&format!("{}  -  score: {}  -  seed: {}", title, score, seed), Vec2::zero());
+    pencil.set_foreground(Color::Grey);
+    pencil.draw_center_text(hint, Vec2::y(2));
+}
+
+/// Tells the player that a networked game is stalled because the other player's input didn't arrive yet.
+pub fn draw_waiting(game_state: &GameState, pencil: &mut Pencil) {
+    pencil.set_foreground(Color::Yellow);
+    pencil.draw_center_text(" Waiting for the other player... ", cell(game_state.dimension) / 2);
+}
+
+/// Tells the viewers of a demo game how to get back to the title screen.
+pub fn draw_demo_hint(game_state: &GameState, pencil: &mut Pencil) {
+    // blinks once a second
+    if (game_state.frame / game_state.config.fps_limit as usize).is_multiple_of(2) {
+        pencil.set_foreground(Color::Yellow);
+        pencil.draw_center_text(" DEMO - press any key ", cell(game_state.dimension) / 2);
+    }
+}
+
+const CONTROLS_OPTIONS: [&str; 4] = ["Switch player", "Reset to defaults", "Save", "Cancel"];
+
+pub enum ControlsChoice {
+    Save,
+    Cancel,
+}
+
+/// Lists the keys of every action of one player and lets the player add and remove bindings.
+pub struct ControlsScreen {
+    /// The edited keys of both ships.
+    pub keymaps: [KeyMap; 2],
+    /// The index of the player whose keys are shown.
+    player: usize,
+    selected: usize,
+    waiting_for_key: bool,
+    pub message: Option<String>,
+}
+
+impl ControlsScreen {
+    pub fn new(keymap: &KeyMap, keymap_player2: &KeyMap) -> Self {
+        let keymaps = [keymap.clone(), keymap_player2.clone()];
+        Self { keymaps, player: 0, selected: 0, waiting_for_key: false, message: None }
+    }
+
+    fn rows(&self) -> usize {
+        Action::ALL.len() + CONTROLS_OPTIONS.len()
+    }
+
+    /// Binds `key` to `action` of the shown player, unless either player already uses it for something else.
+    fn bind(&mut self, action: Action, key: Key) -> Result<(), String> {
+        let other_player = 1 - self.player;
+        if let Some(other) = self.keymaps[other_player].action(key) {
+            return Err(format!("{} is already bound to {} of player {}", key_name(key), other.description(), other_player + 1));
+        }
+        self.keymaps[self.player].bind(action, key)
+            .map_err(|other| format!("{} is already bound to {}", key_name(key), other.description()))
+    }
+
+    /// An action of either player that needs a key but has none, with the number of the player.
+    fn unbound_required_action(&self) -> Option<(Action, usize)> {
+        let player1 = self.keymaps[0].unbound_required_action().map(|action| (action, 1));
+        // the second ship is paused with the keys of the first one
+        let player2 = self.keymaps[1].unbound_required_action()
+            .filter(|action| *action != Action::Pause)
+            .map(|action| (action, 2));
+        player1.or(player2)
+    }
+
+    /// Navigates with the `active` keymap, the edited ones only take effect once saved.
+    pub fn handle_input(&mut self, app_state: &State, active: &KeyMap) -> Option<ControlsChoice> {
+        if self.waiting_for_key {
+            let key = first_pressed_key(app_state)?;
+            self.waiting_for_key = false;
+            if key == Key::Esc {
+                return None;
+            }
+            self.message = self.bind(Action::ALL[self.selected], key).err();
+            return None;
+        }
+        let action = Action::ALL.get(self.selected).copied();
+        if key_pressed(app_state, Key::Enter) {
+            self.message = None;
+            match self.selected.checked_sub(Action::ALL.len()) {
+                None => self.waiting_for_key = true,
+                Some(0) => self.player = 1 - self.player,
+                Some(1) => {
+                    let defaults = if self.player == 0 { KeyBindings::default() } else { KeyBindings::player2() };
+                    self.keymaps[self.player] = KeyMap::new(&defaults).expect("default keys are valid");
+                }
+                Some(2) => match self.unbound_required_action() {
+                    Some((action, player)) => self.message = Some(format!("{} of player {} needs a key", action.description(), player)),
+                    None => return Some(ControlsChoice::Save),
+                },
+                Some(_) => return Some(ControlsChoice::Cancel),
+            }
+        } else if key_pressed(app_state, Key::Backspace) || key_pressed(app_state, Key::Delete) {
+            if let Some(action) = action {
+                self.keymaps[self.player].clear(action);
+            }
+        } else if key_pressed(app_state, Key::Esc) {
+            return Some(ControlsChoice::Cancel);
+        } else {
+            self.selected = step_selection(self.selected, menu_step(app_state, active), self.rows());
+        }
+        None
+    }
+
+    pub fn draw(&self, win_size: Vec2, pencil: &mut Pencil) {
+        pencil.set_origin(Vec2::xy(win_size.x / 2 - 24, win_size.y / 2 - self.rows() as i32 / 2 - 3));
+        pencil.set_foreground(Color::Cyan);
+        pencil.draw_text(&format!("Controls - player {}", self.player + 1), Vec2::zero());
+        let keymap = &self.keymaps[self.player];
+        for (i, action) in Action::ALL.iter().enumerate() {
+            let keys = if self.waiting_for_key && i == self.selected { "press a key...".to_string() } else { keymap.describe(*action) };
+            pencil.set_foreground(if i == self.selected { Color::Yellow } else { Color::White });
+            pencil.draw_text(&format!("{:<20}{}", action.description(), keys), Vec2::y(2 + i as i32));
+        }
+        for (i, option) in CONTROLS_OPTIONS.iter().enumerate() {
+            let row = Action::ALL.len() + i;
+            pencil.set_foreground(if row == self.selected { Color::Yellow } else { Color::White });
+            pencil.draw_text(option, Vec2::y(3 + row as i32));
+        }
+        let bottom = 4 + self.rows() as i32;
+        if let Some(message) = &self.message {
+            pencil.set_foreground(Color::Red);
+            pencil.draw_text(message, Vec2::y(bottom));
+        }
+        pencil.set_foreground(Color::Grey);
+        pencil.draw_text("Enter: add key   Backspace: clear keys   Esc: cancel", Vec2::y(bottom + 2));
+    }
+}
+
+pub const END_HINT: &str = "R: restart   Enter: main menu   Q: quit";
+pub const NETWORK_END_HINT: &str = "Enter: main menu   Q: quit";
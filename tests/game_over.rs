@@ -0,0 +1,50 @@
+use std::fs;
+use ruscii::keyboard::{Key, KeyEvent};
+use thrust::game_over::GameOverScreen;
+use thrust::highscore::HighScores;
+use thrust::screen::END_HINT;
+use thrust_core::config::GameConfig;
+use thrust_core::game_state::GameState;
+
+/// The end screen of a game scoring 120 points, with an empty high-score table in a directory named after `test`.
+fn game_over_screen(test: &str) -> (GameState, GameOverScreen) {
+    let dir = std::env::temp_dir().join(format!("thrust-game-over-{}-{}", test, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    let mut game = GameState::with_seed(GameConfig::default(), 1);
+    game.players[0].score = 120;
+    let screen = GameOverScreen::with_high_scores("Game Over", &game, HighScores::open(dir), true, END_HINT);
+    (game, screen)
+}
+
+fn pressed(keys: &[Key]) -> Vec<KeyEvent> {
+    keys.iter().map(|key| KeyEvent::Pressed(*key)).collect()
+}
+
+#[test]
+fn enter_saving_the_name_is_not_handled_again() {
+    let (game, mut screen) = game_over_screen("save");
+    assert!(screen.is_entering_name());
+    assert!(screen.handle_input(&pressed(&[Key::A, Key::C, Key::E]), &game));
+    // the Enter that saves the name must not also leave the screen and hide the new rank
+    assert!(screen.handle_input(&pressed(&[Key::Enter]), &game));
+    assert!(!screen.is_entering_name());
+    assert_eq!(screen.rank(), Some(0));
+    // the keys of the following frames are left to the caller
+    assert!(!screen.handle_input(&pressed(&[Key::Enter]), &game));
+}
+
+#[test]
+fn esc_skipping_the_name_entry_is_not_handled_again() {
+    let (game, mut screen) = game_over_screen("skip");
+    assert!(screen.handle_input(&pressed(&[Key::Esc]), &game));
+    assert!(!screen.is_entering_name());
+    assert_eq!(screen.rank(), None);
+    assert!(!screen.handle_input(&pressed(&[Key::Esc]), &game));
+}
+
+#[test]
+fn enter_without_a_name_keeps_asking() {
+    let (game, mut screen) = game_over_screen("empty");
+    assert!(screen.handle_input(&pressed(&[Key::Space, Key::Enter]), &game));
+    assert!(screen.is_entering_name());
+}
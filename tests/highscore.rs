@@ -0,0 +1,65 @@
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+use thrust::highscore::{HighScoreEntry, HighScores, MAX_ENTRIES};
+use thrust_core::ship::ShipType;
+
+/// An empty directory for the table of test `name`.
+fn table_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("thrust-highscores-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+fn entry(name: &str, score: usize) -> HighScoreEntry {
+    HighScoreEntry { name: name.to_string(), score, date: "2024-01-01".to_string(), ship_type: ShipType::Basic, frames: score * 10 }
+}
+
+#[test]
+fn entries_are_ranked_by_score_and_kept_on_disk() {
+    let dir = table_dir("ranked");
+    let mut high_scores = HighScores::open(dir.clone()).unwrap();
+    assert_eq!(high_scores.insert(entry("LOW", 10)).unwrap(), Some(0));
+    assert_eq!(high_scores.insert(entry("HIGH", 30)).unwrap(), Some(0));
+    assert_eq!(high_scores.insert(entry("MID", 20)).unwrap(), Some(1));
+    let names: Vec<String> = HighScores::open(dir.clone()).unwrap().entries().iter().map(|entry| entry.name.clone()).collect();
+    assert_eq!(names, ["HIGH", "MID", "LOW"]);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn table_keeps_only_the_best_entries() {
+    let dir = table_dir("full");
+    let mut high_scores = HighScores::open(dir.clone()).unwrap();
+    for score in 1..=MAX_ENTRIES {
+        high_scores.insert(entry("PLAYER", score * 10)).unwrap();
+    }
+    assert!(!high_scores.qualifies(10));
+    assert!(high_scores.qualifies(11));
+    assert_eq!(high_scores.insert(entry("LATE", 5)).unwrap(), None);
+    assert_eq!(high_scores.entries().len(), MAX_ENTRIES);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn concurrent_saves_keep_every_entry() {
+    let dir = table_dir("concurrent");
+    let writers: Vec<_> = (0..MAX_ENTRIES).map(|writer| {
+        let dir = dir.clone();
+        thread::spawn(move || {
+            // every writer saves from its own, by now outdated view of the table like a second running game would
+            let mut high_scores = HighScores::open(dir).unwrap();
+            high_scores.insert(entry(&format!("P{}", writer), 100 + writer)).unwrap();
+        })
+    }).collect();
+    for writer in writers {
+        writer.join().unwrap();
+    }
+    let high_scores = HighScores::open(dir.clone()).unwrap();
+    let mut scores: Vec<usize> = high_scores.entries().iter().map(|entry| entry.score).collect();
+    scores.sort_unstable();
+    assert_eq!(scores, (100..100 + MAX_ENTRIES).collect::<Vec<_>>());
+    let leftovers = fs::read_dir(&dir).unwrap().filter(|file| file.as_ref().unwrap().path().extension().is_some_and(|ext| ext == "tmp"));
+    assert_eq!(leftovers.count(), 0);
+    fs::remove_dir_all(dir).unwrap();
+}
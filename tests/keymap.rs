@@ -0,0 +1,55 @@
+use ruscii::keyboard::Key;
+use thrust::keymap::{parse_key, KeyMap};
+use thrust_core::action::Action;
+use thrust_core::config::KeyBindings;
+
+fn bindings(action: Action, keys: &[&str]) -> KeyBindings {
+    let mut key_bindings = KeyBindings::default();
+    *key_bindings.get_mut(action) = keys.iter().map(|key| key.to_string()).collect();
+    key_bindings
+}
+
+#[test]
+fn default_keys_build_a_keymap_and_round_trip() {
+    let keymap = KeyMap::new(&KeyBindings::default()).unwrap();
+    assert_eq!(keymap.keys(Action::MoveUp), [Key::W, Key::Up]);
+    assert_eq!(keymap.to_bindings(), KeyBindings::default());
+    assert_eq!(keymap.unbound_required_action(), None);
+}
+
+#[test]
+fn key_names_are_case_insensitive() {
+    assert_eq!(parse_key("space"), Some(Key::Space));
+    assert_eq!(parse_key(" PageUp "), Some(Key::PageUp));
+    assert_eq!(parse_key("Hyper"), None);
+}
+
+#[test]
+fn conflicting_and_unknown_keys_are_rejected_when_loading() {
+    let err = KeyMap::new(&bindings(Action::Fire, &["W"])).err().unwrap();
+    assert_eq!(err, "key 'W' is bound to both keys.move_up and keys.fire");
+    let err = KeyMap::new(&bindings(Action::Bomb, &["Hyper"])).err().unwrap();
+    assert_eq!(err, "unknown key 'Hyper' in keys.bomb");
+}
+
+#[test]
+fn rebinding_a_key_of_another_action_fails() {
+    let mut keymap = KeyMap::new(&KeyBindings::default()).unwrap();
+    assert_eq!(keymap.bind(Action::Fire, Key::Space), Err(Action::ToggleAutoShoot));
+    assert_eq!(keymap.bind(Action::MoveUp, Key::W), Ok(()));
+    assert_eq!(keymap.bind(Action::Fire, Key::G), Ok(()));
+    assert_eq!(keymap.keys(Action::Fire), [Key::F, Key::G]);
+    assert_eq!(keymap.keys(Action::MoveUp), [Key::W, Key::Up]);
+}
+
+#[test]
+fn movement_and_pause_need_a_key() {
+    let mut keymap = KeyMap::new(&KeyBindings::default()).unwrap();
+    keymap.clear(Action::Bomb);
+    assert_eq!(keymap.unbound_required_action(), None);
+    keymap.clear(Action::Pause);
+    assert_eq!(keymap.unbound_required_action(), Some(Action::Pause));
+    keymap.bind(Action::Pause, Key::Tab).unwrap();
+    keymap.clear(Action::MoveLeft);
+    assert_eq!(keymap.unbound_required_action(), Some(Action::MoveLeft));
+}
@@ -0,0 +1,96 @@
+//! Compares checking every ship shot against every enemy with querying the collision grid,
+//! and measures whole frames of games crowded with enemies and shots.
+//!
+//! Run with `cargo bench -p thrust-core`.
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use thrust_core::config::GameConfig;
+use thrust_core::constant::GRID_BUCKET_SIZE;
+use thrust_core::enemy::{Enemy, EnemyKind};
+use thrust_core::fixed::FixedVec2;
+use thrust_core::game_state::GameState;
+use thrust_core::hitbox::Sweep;
+use thrust_core::input::FrameInput;
+use thrust_core::movement::{Movement, MovementPattern};
+use thrust_core::shot::ShotType;
+use thrust_core::spatial::{SpatialGrid, Vec2};
+use thrust_core::world::Entity;
+
+const FIELD: Vec2 = Vec2 { x: 400, y: 200 };
+const COUNTS: [usize; 3] = [250, 1000, 4000];
+
+fn random_sweeps(count: usize, movement: Vec2, rng: &mut ChaCha8Rng) -> Vec<Sweep> {
+    (0..count).map(|_| {
+        let pos = Vec2::xy(rng.gen_range(1..FIELD.x - 1), rng.gen_range(1..FIELD.y - 1));
+        Sweep::new(FixedVec2::from_cell(pos), FixedVec2::from_cell(pos + movement), Vec2::xy(1, 1))
+    }).collect()
+}
+
+fn shots_vs_enemies(c: &mut Criterion) {
+    let mut group = c.benchmark_group("shots_vs_enemies");
+    for count in COUNTS {
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        let shots = random_sweeps(count, Vec2::y(-1), &mut rng);
+        let enemies = random_sweeps(count, Vec2::y(1), &mut rng);
+        group.bench_with_input(BenchmarkId::new("all_pairs", count), &count, |b, _| {
+            b.iter(|| {
+                shots.iter()
+                    .map(|shot| enemies.iter().filter(|enemy| shot.hits(enemy)).count())
+                    .sum::<usize>()
+            })
+        });
+        let mut grid = SpatialGrid::new(FIELD, GRID_BUCKET_SIZE);
+        let mut candidates = Vec::new();
+        group.bench_with_input(BenchmarkId::new("grid", count), &count, |b, _| {
+            b.iter(|| {
+                grid.rebuild(enemies.iter().map(Sweep::bounds));
+                let mut hits = 0;
+                for shot in &shots {
+                    grid.query(&shot.bounds(), &mut candidates);
+                    hits += candidates.iter().filter(|index| shot.hits(&enemies[**index])).count();
+                }
+                hits
+            })
+        });
+    }
+    group.finish();
+}
+
+/// A game on a large field with `count` enemies, enemy shots and ship shots spread over it.
+fn crowded_game(count: usize) -> GameState {
+    let config = GameConfig::from_toml(&format!(
+        "width = {}\nheight = {}\nenemy_shot_probability = 0.0\n", FIELD.x, FIELD.y
+    )).expect("benchmark config is valid");
+    let mut game = GameState::with_seed(config, 1);
+    let mut rng = ChaCha8Rng::seed_from_u64(2);
+    let mut random_pos = || Vec2::xy(rng.gen_range(1..FIELD.x - 1), rng.gen_range(1..FIELD.y - 10));
+    for _ in 0..count {
+        let movement = Movement::single(MovementPattern::default());
+        game.world.spawn(Entity::enemy(Enemy::new(EnemyKind::Tank, random_pos(), movement, 1000)));
+        game.world.spawn(Entity::shot(random_pos(), Vec2::y(1), ShotType::Enemy));
+        game.world.spawn(Entity::shot(random_pos(), Vec2::y(-1), ShotType::Basic));
+    }
+    game
+}
+
+fn game_update(c: &mut Criterion) {
+    let mut group = c.benchmark_group("game_update");
+    for count in COUNTS {
+        group.bench_with_input(BenchmarkId::from_parameter(count), &count, |b, &count| {
+            b.iter_batched(
+                || crowded_game(count),
+                |mut game| {
+                    game.update(&[FrameInput::default()]);
+                    black_box(game.score())
+                },
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, shots_vs_enemies, game_update);
+criterion_main!(benches);
@@ -0,0 +1,58 @@
+//! Plays a networked game with scripted inputs and prints the final checksum, to check the lockstep
+//! between two processes without a terminal:
+//!
+//! ```shell
+//! cargo run -p thrust-core --example lockstep -- host 7777
+//! cargo run -p thrust-core --example lockstep -- join 127.0.0.1:7777
+//! ```
+//!
+//! Both processes print the same checksum if their games stayed in sync.
+
+use std::process::exit;
+use std::thread;
+use std::time::Duration;
+use thrust_core::config::GameConfig;
+use thrust_core::input::FrameInput;
+use thrust_core::net::{self, Host, Lockstep};
+
+const FRAMES: usize = 2000;
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match args.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        ["host", port] => port.parse().map_err(|_| "invalid port".to_string()).and_then(host),
+        ["join", addr] => net::join(addr).map_err(|err| err.to_string()),
+        _ => Err("usage: lockstep host <PORT> | join <ADDR>".to_string()),
+    };
+    let mut lockstep = result.unwrap_or_else(|err| {
+        eprintln!("error: {}", err);
+        exit(1);
+    });
+    let mut game = lockstep.new_game();
+    while game.frame < FRAMES && !game.is_game_over() {
+        // a different input every few frames, so both ships move and shoot
+        let bits = (game.frame / 7 + lockstep.local_player * 3) % 128;
+        match lockstep.step(&mut game, FrameInput::from_bits(bits as u8)) {
+            Ok(Some(_)) => (),
+            Ok(None) => thread::sleep(Duration::from_millis(1)),
+            Err(err) => {
+                eprintln!("error: {}", err);
+                exit(1);
+            }
+        }
+    }
+    println!("player {}: frame {} score {} checksum {:016x}", lockstep.local_player + 1, game.frame, game.score(), game.checksum());
+    lockstep.leave();
+}
+
+fn host(port: u16) -> Result<Lockstep, String> {
+    let mut host = Host::bind(port).map_err(|err| err.to_string())?;
+    eprintln!("waiting for a player on port {}", port);
+    loop {
+        match host.accept(7, &GameConfig::default()) {
+            Ok(Some(lockstep)) => return Ok(lockstep),
+            Ok(None) => thread::sleep(Duration::from_millis(10)),
+            Err(err) => eprintln!("error: {}", err),
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+use crate::input::FrameInput;
+
+/// A thing the player can do, independent of the key (or bot) triggering it.
+///
+/// Frontends map their keys to actions and turn the actions of a frame into a [`FrameInput`].
+/// Actions like [`Action::Pause`] only concern the frontend and have no effect on the simulation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    ToggleAutoShoot,
+    Fire,
+    Bomb,
+    Pause,
+}
+
+impl Action {
+    pub const ALL: [Action; 8] = [
+        Action::MoveUp,
+        Action::MoveDown,
+        Action::MoveLeft,
+        Action::MoveRight,
+        Action::ToggleAutoShoot,
+        Action::Fire,
+        Action::Bomb,
+        Action::Pause,
+    ];
+
+    /// The key of the action in the `[keys]` table of the config.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::MoveUp => "move_up",
+            Action::MoveDown => "move_down",
+            Action::MoveLeft => "move_left",
+            Action::MoveRight => "move_right",
+            Action::ToggleAutoShoot => "toggle_auto_shoot",
+            Action::Fire => "fire",
+            Action::Bomb => "bomb",
+            Action::Pause => "pause",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Action::MoveUp => "Move up",
+            Action::MoveDown => "Move down",
+            Action::MoveLeft => "Move left",
+            Action::MoveRight => "Move right",
+            Action::ToggleAutoShoot => "Toggle auto-shoot",
+            Action::Fire => "Fire",
+            Action::Bomb => "Trigger bomb",
+            Action::Pause => "Pause",
+        }
+    }
+
+    /// Whether the action lasts as long as its key is held down, instead of triggering once per key press.
+    pub fn is_held(&self) -> bool {
+        matches!(self, Action::MoveUp | Action::MoveDown | Action::MoveLeft | Action::MoveRight)
+    }
+}
+
+impl FrameInput {
+    pub fn from_actions(actions: impl IntoIterator<Item = Action>) -> Self {
+        let mut input = FrameInput::default();
+        for action in actions {
+            match action {
+                Action::MoveUp => input.up = true,
+                Action::MoveDown => input.down = true,
+                Action::MoveLeft => input.left = true,
+                Action::MoveRight => input.right = true,
+                Action::ToggleAutoShoot => input.toggle_auto_shoot = true,
+                Action::Fire => input.fire = true,
+                Action::Bomb => input.bomb = true,
+                Action::Pause => (),
+            }
+        }
+        input
+    }
+}
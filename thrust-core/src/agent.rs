@@ -0,0 +1,279 @@
+//! A gym-style interface for bots: [`Env::reset`] starts a game, [`Env::step`] plays one frame with the bot's
+//! [`Action`]s and returns what the bot sees of the game, its reward and whether the game is over.
+//!
+//! Nothing is rendered and nothing waits for the frame rate, so games run as fast as the simulation allows.
+//! [`serve`] offers the same over JSON lines, for bots written in other languages.
+
+use std::error;
+use std::fmt::{self, Display, Formatter};
+use std::io::{self, BufRead, Write};
+use serde::{Deserialize, Serialize};
+use crate::action::Action;
+use crate::boss::Boss;
+use crate::config::GameConfig;
+use crate::enemy::EnemyKind;
+use crate::game_state::GameState;
+use crate::goodie::GoodieType;
+use crate::input::FrameInput;
+use crate::shot::ShotType;
+use crate::world::{Faction, Sprite};
+
+/// What a bot gets to know about its ship.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ShipObservation {
+    /// The cell of the ship's left end, the ship is 3 cells wide.
+    pub x: i32,
+    pub y: i32,
+    pub ship_type: &'static str,
+    pub health: usize,
+    pub shield: usize,
+    pub score: usize,
+    pub bombs: usize,
+    pub auto_shoot: bool,
+    pub charge: usize,
+    /// The names of the active power-ups.
+    pub effects: Vec<&'static str>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntityKind {
+    Enemy,
+    /// Anything the enemies or the boss fired, which damages the ship.
+    EnemyShot,
+    /// Shots of the ship, which can't hurt it.
+    ShipShot,
+    Goodie,
+}
+
+/// An enemy, shot or goodie on the field, with its movement per frame in cells.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EntityObservation {
+    pub kind: EntityKind,
+    /// What exactly it is, e.g. `fighter`, `missile` or `repair_kit`.
+    pub name: &'static str,
+    pub x: i32,
+    pub y: i32,
+    pub vx: f64,
+    pub vy: f64,
+    pub health: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BossObservation {
+    /// The top left cell of the boss.
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+    pub health: usize,
+    /// The column of the laser beam reaching down to the ground, while it is fired.
+    pub laser_x: Option<i32>,
+}
+
+/// The game as a list of everything on the field, seen from the bot's ship.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Observation {
+    /// The seed the game was started with, resetting with it plays the same game again.
+    pub seed: u64,
+    pub frame: usize,
+    /// The size of the field including its border, cells with `0 < x < width` and `0 < y < height - 1` are inside.
+    pub width: i32,
+    pub height: i32,
+    pub ship: ShipObservation,
+    pub entities: Vec<EntityObservation>,
+    pub boss: Option<BossObservation>,
+}
+
+impl Observation {
+    pub fn new(game: &GameState) -> Self {
+        let player = &game.players[0];
+        let ship = ShipObservation {
+            x: player.ship.pos.x,
+            y: player.ship.pos.y,
+            ship_type: player.ship.ship_type.name(),
+            health: player.health,
+            shield: player.shield,
+            score: player.score,
+            bombs: player.bombs,
+            auto_shoot: player.ship.auto_shoot,
+            charge: player.ship.charge,
+            effects: player.effects.active().iter().map(|effect| effect.kind.name()).collect(),
+        };
+        let entities = game.world.iter().map(|entity| EntityObservation {
+            kind: match entity.sprite {
+                Sprite::Enemy(_) => EntityKind::Enemy,
+                Sprite::Shot(_) if entity.faction == Faction::Player => EntityKind::ShipShot,
+                Sprite::Shot(_) => EntityKind::EnemyShot,
+                Sprite::Goodie(_) => EntityKind::Goodie,
+            },
+            name: sprite_name(entity.sprite),
+            x: entity.position.cell.x,
+            y: entity.position.cell.y,
+            vx: entity.velocity.x.to_f64(),
+            vy: entity.velocity.y.to_f64(),
+            health: entity.health.map(|health| health.current),
+        }).collect();
+        let boss = game.boss.as_ref().map(|boss| BossObservation {
+            x: boss.pos.x,
+            y: boss.pos.y,
+            width: Boss::size().x,
+            height: Boss::size().y,
+            health: boss.health,
+            laser_x: boss.laser.map(|laser| laser.x),
+        });
+        Self { seed: game.seed, frame: game.frame, width: game.dimension.x, height: game.dimension.y, ship, entities, boss }
+    }
+}
+
+fn sprite_name(sprite: Sprite) -> &'static str {
+    match sprite {
+        Sprite::Enemy(EnemyKind::Drone) => "drone",
+        Sprite::Enemy(EnemyKind::Fighter) => "fighter",
+        Sprite::Enemy(EnemyKind::Tank) => "tank",
+        Sprite::Shot(ShotType::Enemy) => "enemy",
+        Sprite::Shot(ShotType::EnemyAimed) => "enemy_aimed",
+        Sprite::Shot(ShotType::EnemySpread) => "enemy_spread",
+        Sprite::Shot(ShotType::EnemyBurst) => "enemy_burst",
+        Sprite::Shot(ShotType::Missile) => "missile",
+        Sprite::Shot(ShotType::Boss) => "boss",
+        Sprite::Shot(ShotType::Basic) => "basic",
+        Sprite::Shot(ShotType::Strong) => "strong",
+        Sprite::Shot(ShotType::DiagonalLeft) => "diagonal_left",
+        Sprite::Shot(ShotType::DiagonalRight) => "diagonal_right",
+        Sprite::Goodie(GoodieType::RepairKit(_)) => "repair_kit",
+        Sprite::Goodie(GoodieType::ShieldBoost(_)) => "shield_boost",
+        Sprite::Goodie(GoodieType::ShipUpgrade(ship_type)) => ship_type.name(),
+        Sprite::Goodie(GoodieType::PowerUp(kind)) => kind.name(),
+        Sprite::Goodie(GoodieType::Bomb) => "bomb",
+    }
+}
+
+/// The outcome of [`Env::step`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Step {
+    pub observation: Observation,
+    /// The points scored during the step, minus the health and shield lost.
+    pub reward: i64,
+    /// Whether the ship is destroyed or the levels are complete, the game needs a [`Env::reset`] then.
+    pub done: bool,
+}
+
+/// A single player game driven by a bot instead of a keyboard.
+pub struct Env {
+    config: GameConfig,
+    pub game: GameState,
+}
+
+impl Env {
+    /// An environment playing games with `config`, always with a single ship. It starts with a game seeded with 0.
+    pub fn new(mut config: GameConfig) -> Self {
+        config.players = 1;
+        Self { game: GameState::with_seed(config.clone(), 0), config }
+    }
+
+    /// Starts a new game with `seed`, the same seed and actions always play out the same way.
+    pub fn reset(&mut self, seed: u64) -> Observation {
+        self.game = GameState::with_seed(self.config.clone(), seed);
+        Observation::new(&self.game)
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.game.is_game_over() || self.game.is_level_complete()
+    }
+
+    /// Plays one frame with `actions`, which have the same effect as the keys bound to them.
+    /// Once the game is over it stays as it is, further steps only repeat the last observation.
+    pub fn step(&mut self, actions: &[Action]) -> Step {
+        if self.is_done() {
+            return Step { observation: Observation::new(&self.game), reward: 0, done: true };
+        }
+        let player = &self.game.players[0];
+        let before = (player.score, player.health + player.shield);
+        self.game.update(&[FrameInput::from_actions(actions.iter().copied())]);
+        let player = &self.game.players[0];
+        let reward = player.score.saturating_sub(before.0) as i64 - before.1.saturating_sub(player.health + player.shield) as i64;
+        Step { observation: Observation::new(&self.game), reward, done: self.is_done() }
+    }
+}
+
+/// A line of the JSON lines protocol, tagged by its `cmd`.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "cmd", rename_all = "snake_case", deny_unknown_fields)]
+enum Request {
+    /// Starts a new game, with a random seed if none is given. The observation tells the seed.
+    Reset { seed: Option<u64> },
+    /// Plays `repeat` frames (1 by default) with the same actions, answering with the summed up reward.
+    /// Stops early once the game is over.
+    Step {
+        #[serde(default)]
+        actions: Vec<Action>,
+        repeat: Option<usize>,
+    },
+}
+
+#[derive(Debug)]
+pub enum AgentError {
+    /// A line that isn't a valid request.
+    InvalidRequest(String),
+    /// A step after the game ended, without a reset in between.
+    GameOver,
+}
+
+impl Display for AgentError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            AgentError::InvalidRequest(msg) => write!(f, "invalid request: {}", msg),
+            AgentError::GameOver => write!(f, "the game is over, send a reset"),
+        }
+    }
+}
+
+impl error::Error for AgentError {}
+
+impl Env {
+    /// Answers a single line of the JSON lines protocol, see [`serve`].
+    pub fn handle_request(&mut self, line: &str) -> Result<Step, AgentError> {
+        let request = serde_json::from_str(line).map_err(|err| AgentError::InvalidRequest(err.to_string()))?;
+        match request {
+            Request::Reset { seed } => {
+                let observation = self.reset(seed.unwrap_or_else(rand::random));
+                Ok(Step { observation, reward: 0, done: false })
+            }
+            Request::Step { .. } if self.is_done() => Err(AgentError::GameOver),
+            Request::Step { actions, repeat } => {
+                let mut step = self.step(&actions);
+                for _ in 1..repeat.unwrap_or(1) {
+                    if step.done {
+                        break;
+                    }
+                    let reward = step.reward;
+                    step = self.step(&actions);
+                    step.reward += reward;
+                }
+                Ok(step)
+            }
+        }
+    }
+}
+
+/// Lets a bot play over JSON lines until `input` ends: every request line gets exactly one answer line.
+///
+/// Requests are `{"cmd": "reset", "seed": 42}` and `{"cmd": "step", "actions": ["move_left", "fire"], "repeat": 4}`,
+/// with the actions named like in the `[keys]` config table. Both are answered with a [`Step`],
+/// `{"observation": {...}, "reward": 0, "done": false}`, or with `{"error": "..."}`.
+pub fn serve(env: &mut Env, input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+    for line in input.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let answer = match env.handle_request(&line) {
+            Ok(step) => serde_json::to_string(&step),
+            Err(err) => serde_json::to_string(&serde_json::json!({ "error": err.to_string() })),
+        };
+        writeln!(output, "{}", answer.map_err(io::Error::from)?)?;
+        output.flush()?;
+    }
+    Ok(())
+}
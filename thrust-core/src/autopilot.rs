@@ -0,0 +1,178 @@
+//! A scripted pilot flying a ship by itself, for demos and soak tests.
+//!
+//! It decides on the [`Action`]s of every frame just like a player pressing keys, so its games run through
+//! the same [`FrameInput`](crate::input::FrameInput)s and can be recorded, replayed and played over the network.
+
+use crate::action::Action;
+use crate::boss::Boss;
+use crate::constant::{AUTOPILOT_HORIZON, AUTOPILOT_LOW_HEALTH_PERCENT};
+use crate::effect::EffectKind;
+use crate::fixed::{Fixed, FixedVec2};
+use crate::game_state::GameState;
+use crate::goodie::GoodieType;
+use crate::hitbox::Sweep;
+use crate::player::Player;
+use crate::ship::{FireMode, ShipType};
+use crate::spatial::Vec2;
+use crate::world::{Faction, Role};
+
+const MOVES: [(i32, i32); 9] = [(0, 0), (-1, 0), (1, 0), (0, -1), (0, 1), (-1, -1), (1, -1), (-1, 1), (1, 1)];
+
+/// Something that damages the ship on contact, moving in a straight line.
+struct Threat {
+    exact: FixedVec2,
+    velocity: FixedVec2,
+    size: Vec2,
+}
+
+impl Threat {
+    /// The way the threat moves during each of the next frames, assuming it keeps its velocity.
+    fn path(&self) -> Vec<Sweep> {
+        let mut exact = self.exact;
+        (0..AUTOPILOT_HORIZON).map(|_| {
+            let from = exact;
+            exact += self.velocity;
+            Sweep::new(from, exact, self.size)
+        }).collect()
+    }
+}
+
+/// The actions of `player` for the next frame.
+///
+/// Every possible move is tried for the next few frames against where the enemies and their shots are heading.
+/// Among the moves that keep the ship unharmed the longest, the pilot takes the one bringing it closest to
+/// a goodie it can still catch, or else below the lowest enemy. With low health it only goes for repairs and shields.
+pub fn actions(game: &GameState, player: usize) -> Vec<Action> {
+    let state = &game.players[player];
+    if state.is_destroyed() {
+        return Vec::new();
+    }
+    let paths: Vec<Vec<Sweep>> = if state.effects.is_active(EffectKind::Invincibility) {
+        Vec::new()
+    } else {
+        threats(game).iter().map(Threat::path).collect()
+    };
+    let target = Vec2::xy(target_x(game, state), game.dimension.y - 2);
+    // ties go to the last of the maximal moves, i.e. staying put wins over moving
+    let (safety, _, (dx, dy)) = MOVES.iter().rev()
+        .map(|&(dx, dy)| {
+            let (safety, first_cell) = rate_move(game, state, &paths, dx, dy);
+            let distance = 2 * (first_cell.x + 1 - target.x).abs() + (first_cell.y - target.y).abs();
+            (safety, distance, (dx, dy))
+        })
+        .max_by_key(|&(safety, distance, _)| (safety, -distance))
+        .expect("there are moves");
+    let mut actions = Vec::new();
+    match dx {
+        -1 => actions.push(Action::MoveLeft),
+        1 => actions.push(Action::MoveRight),
+        _ => (),
+    }
+    match dy {
+        -1 => actions.push(Action::MoveUp),
+        1 => actions.push(Action::MoveDown),
+        _ => (),
+    }
+    // releasing a fully charged weapon for one frame shoots its strong volley
+    let charged = game.config.fire_mode == FireMode::Charge && state.ship.charge >= game.config.charge_frames;
+    if !charged {
+        actions.push(Action::Fire);
+    }
+    if safety <= 2 && state.bombs > 0 {
+        actions.push(Action::Bomb);
+    }
+    actions
+}
+
+/// Everything that could hit the ships: enemies, their shots, the boss and its laser.
+fn threats(game: &GameState) -> Vec<Threat> {
+    let mut threats: Vec<Threat> = game.world.iter()
+        .filter(|entity| entity.faction == Faction::Enemy)
+        .map(|entity| Threat { exact: entity.position.exact, velocity: entity.velocity, size: entity.size })
+        .collect();
+    if let Some(boss) = &game.boss {
+        let still = FixedVec2::default();
+        threats.push(Threat { exact: FixedVec2::from_cell(boss.pos), velocity: still, size: Boss::size() });
+        if let Some(laser) = boss.laser_hitbox(game.dimension) {
+            threats.push(Threat { exact: FixedVec2::from_cell(laser.pos), velocity: still, size: laser.size });
+        }
+    }
+    threats
+}
+
+/// Keeps moving in the direction `dx`, `dy` for the next frames, checked against the [`Threat::path`]s.
+/// Returns the first frame the ship would be hit in (past the horizon if it stays unharmed) and the ship's cell after the first frame.
+fn rate_move(game: &GameState, state: &Player, paths: &[Vec<Sweep>], dx: i32, dy: i32) -> (usize, Vec2) {
+    let speed_x = Fixed::from_f64(game.config.move_speed_x) * dx;
+    let speed_y = Fixed::from_f64(game.config.move_speed_y) * dy;
+    let mut ship = state.ship.exact;
+    let mut first_cell = None;
+    for frame in 1..=AUTOPILOT_HORIZON {
+        let previous = ship;
+        // the same bounds the ship itself keeps to
+        let x = ship.x + speed_x;
+        if 0 < x.round() && x.round() < game.dimension.x - 2 {
+            ship.x = x;
+        }
+        let y = ship.y + speed_y;
+        if 1 < y.round() && y.round() < game.dimension.y {
+            ship.y = y;
+        }
+        first_cell.get_or_insert(ship.cell());
+        let ship_sweep = Sweep::new(previous, ship, Vec2::xy(3, 1));
+        let hit = paths.iter().any(|path| ship_sweep.hits(&path[frame - 1]));
+        if hit {
+            return (frame, first_cell.expect("set above"));
+        }
+    }
+    (AUTOPILOT_HORIZON + 1, first_cell.unwrap_or(state.ship.pos))
+}
+
+/// The column the middle of the ship should be in: below a wanted goodie, the lowest enemy or the boss.
+fn target_x(game: &GameState, state: &Player) -> i32 {
+    let ship = state.ship.pos;
+    let low_health = state.health * 100 <= game.config.max_health * AUTOPILOT_LOW_HEALTH_PERCENT;
+    let speed_x = game.config.move_speed_x.max(0.1);
+    let goodie = game.world.iter()
+        .filter_map(|entity| entity.as_goodie().map(|goodie| (entity, goodie)))
+        .filter(|(_, goodie)| wants(game, state, goodie, low_health))
+        .filter_map(|(entity, _)| {
+            let pos = entity.position.cell;
+            let fall_speed = entity.velocity.y.to_f64();
+            if pos.y > ship.y || fall_speed <= 0.0 {
+                return None;
+            }
+            let frames_left = (ship.y - pos.y) as f64 / fall_speed;
+            let frames_needed = (pos.x - ship.x - 1).abs() as f64 / speed_x;
+            (frames_needed <= frames_left).then_some((frames_left, pos.x))
+        })
+        .min_by(|a, b| a.0.total_cmp(&b.0));
+    if let Some((_, x)) = goodie {
+        return x;
+    }
+    let lowest_enemy = game.world.iter()
+        .filter(|entity| matches!(entity.role, Role::Enemy(_)))
+        .max_by_key(|entity| entity.position.cell.y);
+    if let Some(enemy) = lowest_enemy {
+        // aim where the enemy will be once a shot rising a cell per frame reached it
+        let frames = (ship.y - enemy.position.cell.y).max(0);
+        return (enemy.position.exact + FixedVec2::new(enemy.velocity.x * frames, Fixed::ZERO)).cell().x;
+    }
+    match &game.boss {
+        Some(boss) => boss.pos.x + Boss::size().x / 2,
+        None => game.dimension.x / 2,
+    }
+}
+
+/// Whether the goodie is worth a detour. With low health, only what protects the ship is.
+fn wants(game: &GameState, state: &Player, goodie: &GoodieType, low_health: bool) -> bool {
+    match goodie {
+        GoodieType::RepairKit(_) => state.health < game.config.max_health,
+        GoodieType::ShieldBoost(_) => state.shield < game.config.max_shield,
+        GoodieType::PowerUp(EffectKind::Invincibility) => true,
+        _ if low_health => false,
+        GoodieType::ShipUpgrade(ship_type) => *ship_type != ShipType::Basic && *ship_type != state.ship.ship_type,
+        GoodieType::PowerUp(_) => true,
+        GoodieType::Bomb => state.bombs < game.config.goodies.max_bombs,
+    }
+}
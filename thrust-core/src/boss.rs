@@ -0,0 +1,163 @@
+use rand::Rng;
+use crate::config::BossConfig;
+use crate::constant::BORDER_SIZE;
+use crate::hitbox::Hitbox;
+use crate::shot::ShotType;
+use crate::spatial::Vec2;
+use crate::world::{Entity, World};
+
+pub const BOSS_SPRITE: [&str; 3] = [
+    "/-MMM-\\",
+    "|(o o)|",
+    " \\VVV/ ",
+];
+
+/// The steps a boss repeats for its whole life: a direction and how many moves to go in it.
+const MOVEMENT_SCRIPT: [(Vec2, usize); 6] = [
+    (Vec2 { x: 0, y: 1 }, 2),
+    (Vec2 { x: 1, y: 0 }, 12),
+    (Vec2 { x: 0, y: 1 }, 1),
+    (Vec2 { x: -1, y: 0 }, 24),
+    (Vec2 { x: 0, y: -1 }, 1),
+    (Vec2 { x: 1, y: 0 }, 12),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BossAttack {
+    /// A fan of five shots.
+    Spread,
+    /// One shot out of each cannon, heading towards the ship.
+    Aimed,
+    /// A beam down to the ground, sweeping sideways across the field.
+    SweepingLaser,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Laser {
+    pub x: i32,
+    pub direction: i32,
+    pub remaining_frames: usize,
+}
+
+#[derive(Hash)]
+pub struct Boss {
+    pub pos: Vec2,
+    pub health: usize,
+    pub max_health: usize,
+    pub laser: Option<Laser>,
+    script_step: usize,
+    script_moves: usize,
+    last_movement: usize,
+    last_attack_frame: usize,
+}
+
+impl Boss {
+    /// Creates the boss of the `encounter`-th (starting at 0) boss fight, entering at the top center.
+    pub fn new(encounter: usize, config: &BossConfig, dimension: Vec2, frame: usize) -> Self {
+        let max_health = config.base_health + encounter * config.health_increase;
+        Self {
+            pos: Vec2::xy(dimension.x / 2 - Boss::size().x / 2, BORDER_SIZE),
+            health: max_health,
+            max_health,
+            laser: None,
+            script_step: 0,
+            script_moves: 0,
+            last_movement: frame,
+            last_attack_frame: frame,
+        }
+    }
+
+    pub fn size() -> Vec2 {
+        Vec2::xy(BOSS_SPRITE[0].chars().count() as i32, BOSS_SPRITE.len() as i32)
+    }
+
+    pub fn hitbox(&self) -> Hitbox {
+        Hitbox::new(self.pos, Boss::size())
+    }
+
+    /// The cells covered by the laser beam, reaching from below the boss down to the ground.
+    pub fn laser_hitbox(&self, dimension: Vec2) -> Option<Hitbox> {
+        self.laser.as_ref().map(|laser| {
+            let top = self.pos.y + Boss::size().y;
+            Hitbox::new(Vec2::xy(laser.x, top), Vec2::xy(1, dimension.y - BORDER_SIZE - top + 1))
+        })
+    }
+
+    /// Applies `damage` to the boss and returns whether it got destroyed.
+    pub fn hit(&mut self, damage: usize) -> bool {
+        self.health = self.health.saturating_sub(damage);
+        self.health == 0
+    }
+
+    pub fn update<R: Rng>(&mut self, frame: usize, config: &BossConfig, dimension: Vec2, ship_pos: Vec2, world: &mut World, rng: &mut R) {
+        self.move_self(frame, config.move_interval, dimension);
+        self.update_laser(dimension);
+        if self.laser.is_none() && self.last_attack_frame + config.attack_interval < frame {
+            let attack = match rng.gen_range(0..3) {
+                0 => BossAttack::Spread,
+                1 => BossAttack::Aimed,
+                _ => BossAttack::SweepingLaser,
+            };
+            self.attack(attack, config.laser_duration, ship_pos, world, rng);
+            self.last_attack_frame = frame;
+        }
+    }
+
+    fn move_self(&mut self, frame: usize, move_interval: usize, dimension: Vec2) {
+        if self.last_movement + move_interval >= frame {
+            return;
+        }
+        self.last_movement = frame;
+        let (direction, moves) = MOVEMENT_SCRIPT[self.script_step];
+        let new_pos = self.pos + direction;
+        if new_pos.x > BORDER_SIZE && new_pos.x + Boss::size().x < dimension.x && new_pos.y >= BORDER_SIZE {
+            self.pos = new_pos;
+        }
+        self.script_moves += 1;
+        if self.script_moves == moves {
+            self.script_moves = 0;
+            self.script_step = (self.script_step + 1) % MOVEMENT_SCRIPT.len();
+        }
+    }
+
+    fn update_laser(&mut self, dimension: Vec2) {
+        let Some(laser) = self.laser.as_mut() else { return; };
+        laser.x += laser.direction;
+        if laser.x <= BORDER_SIZE || laser.x >= dimension.x - BORDER_SIZE {
+            laser.direction = -laser.direction;
+        }
+        laser.remaining_frames -= 1;
+        if laser.remaining_frames == 0 {
+            self.laser = None;
+        }
+    }
+
+    fn attack<R: Rng>(&mut self, attack: BossAttack, laser_duration: usize, ship_pos: Vec2, world: &mut World, rng: &mut R) {
+        let size = Boss::size();
+        let bottom = self.pos.y + size.y;
+        let center = Vec2::xy(self.pos.x + size.x / 2, bottom);
+        match attack {
+            BossAttack::Spread => {
+                for dx in -2..=2 {
+                    world.spawn(Entity::shot(center, Vec2::xy(dx, 1), ShotType::Boss));
+                }
+            }
+            BossAttack::Aimed => {
+                for cannon in [Vec2::xy(self.pos.x, bottom), Vec2::xy(self.pos.x + size.x - 1, bottom)] {
+                    world.spawn(Entity::shot(cannon, aim(cannon, ship_pos), ShotType::Boss));
+                }
+            }
+            BossAttack::SweepingLaser => {
+                let direction = if rng.gen_bool(0.5) { 1 } else { -1 };
+                self.laser = Some(Laser { x: center.x, direction, remaining_frames: laser_duration });
+            }
+        }
+    }
+}
+
+/// The whole cell direction from `from` that comes closest to heading towards `target`.
+fn aim(from: Vec2, target: Vec2) -> Vec2 {
+    let delta = target - from;
+    let dx = if delta.x.abs() * 2 < delta.y.abs() { 0 } else { delta.x.signum() };
+    Vec2::xy(dx, 1)
+}
@@ -0,0 +1,699 @@
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::{error, fs, io};
+use serde::{Deserialize, Serialize};
+use toml::{Table, Value};
+use toml_edit::{Array, DocumentMut, Item};
+use crate::action::Action;
+use crate::constant::*;
+use crate::game_state::GameMode;
+use crate::ship::FireMode;
+use crate::spatial::Vec2;
+
+/// All tunable values of a game. Every field defaults to the matching constant of [`crate::constant`].
+///
+/// It is loaded from a TOML file, where the nested structs are tables (e.g. `[damage]`),
+/// and `key=value` overrides, where nested keys are separated by dots (e.g. `damage.collision=40`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GameConfig {
+    pub fps_limit: u32,
+    pub width: i32,
+    pub height: i32,
+    pub move_speed_x: f64,
+    pub move_speed_y: f64,
+    pub enemy_shot_probability: f64,
+    pub fire_mode: FireMode,
+    pub charge_frames: usize,
+    /// The frames between two usual volleys of the ship, lowered by the rapid fire power-up.
+    pub shot_interval: usize,
+    /// The number of ships, set by the title menu: 1, or 2 for local co-op with each player flying their own ship.
+    pub players: usize,
+    /// Whether two ships play together or against each other, the host's mode counts for a network game.
+    pub mode: GameMode,
+    pub initial_health: usize,
+    pub initial_shield: usize,
+    pub max_health: usize,
+    pub max_shield: usize,
+    pub damage: DamageConfig,
+    pub spawn: SpawnConfig,
+    pub boss: BossConfig,
+    pub hud: HudConfig,
+    pub goodies: GoodieConfig,
+    pub effects: EffectConfig,
+    pub scoring: ScoringConfig,
+    pub weapons: WeaponConfig,
+    pub glyphs: Glyphs,
+    pub keys: KeyBindings,
+    /// The keys of the second ship in co-op. Its `pause` keys are ignored.
+    pub keys_player2: KeyBindings,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DamageConfig {
+    pub enemy_reached_ground: usize,
+    pub collision: usize,
+    pub shot_hit: usize,
+    pub shot_ship_basic: usize,
+    pub shot_ship_strong: usize,
+    pub laser: usize,
+    pub boss_contact: usize,
+    pub missile: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SpawnConfig {
+    pub initial_interval: usize,
+    pub speedup_after_frames: usize,
+    pub interval_decrease: usize,
+    pub min_interval: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BossConfig {
+    pub score_interval: usize,
+    pub frame_interval: usize,
+    pub base_health: usize,
+    pub health_increase: usize,
+    pub score: usize,
+    pub move_interval: usize,
+    pub attack_interval: usize,
+    pub laser_duration: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HudConfig {
+    pub health_granularity: usize,
+    pub shield_granularity: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GoodieConfig {
+    pub repair_kit_health: usize,
+    pub shield_boost_shield: usize,
+    pub max_bombs: usize,
+    pub bomb_boss_damage: usize,
+    pub fall_speed: f64,
+    pub weights: GoodieWeights,
+}
+
+/// How often each type of goodie drops, relative to the sum of all weights.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GoodieWeights {
+    pub repair_kit: u32,
+    pub shield_boost: u32,
+    pub ship_upgrade: u32,
+    pub rapid_fire: u32,
+    pub spread: u32,
+    pub score_multiplier: u32,
+    pub invincibility: u32,
+    pub bomb: u32,
+}
+
+impl GoodieWeights {
+    pub fn total(&self) -> u32 {
+        self.repair_kit + self.shield_boost + self.ship_upgrade + self.rapid_fire
+            + self.spread + self.score_multiplier + self.invincibility + self.bomb
+    }
+}
+
+/// The durations (in frames) and strengths of the timed power-ups.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EffectConfig {
+    pub rapid_fire_duration: usize,
+    pub rapid_fire_shot_interval: usize,
+    pub spread_duration: usize,
+    pub score_multiplier_duration: usize,
+    pub score_multiplier: usize,
+    pub invincibility_duration: usize,
+}
+
+/// The points of each enemy kind and the bonuses of [`crate::score::ScoreKeeper`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScoringConfig {
+    pub drone: usize,
+    pub fighter: usize,
+    pub tank: usize,
+    pub combo_window: usize,
+    pub combo_bonus: usize,
+    pub multiplier_kills: usize,
+    pub max_multiplier: usize,
+    pub goodie_bonus: usize,
+    pub graze_bonus: usize,
+    pub no_damage_wave_bonus: usize,
+    pub missile: usize,
+}
+
+/// The speeds (in cells per frame) and timings of the enemy weapons, see [`crate::enemy::EnemyWeapon`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WeaponConfig {
+    pub aimed_speed: f64,
+    pub spread_speed: f64,
+    pub burst_speed: f64,
+    pub burst_count: usize,
+    pub burst_delay: usize,
+    pub burst_interval: usize,
+    pub missile_speed: f64,
+    pub missile_homing_frames: usize,
+    /// The probability of a randomly spawned enemy getting a random weapon instead of the one of its kind.
+    pub random_weapon_probability: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Glyphs {
+    pub health: char,
+    pub shield: char,
+    pub upgrade_ship_basic: char,
+    pub upgrade_ship_diagonal: char,
+    pub upgrade_ship_strong: char,
+    pub enemy: char,
+    pub enemy_fighter: char,
+    pub enemy_tank: char,
+    pub health_bar: char,
+    pub shield_bar: char,
+    pub shot_enemy: char,
+    pub shot_enemy_aimed: char,
+    pub shot_enemy_spread: char,
+    pub shot_enemy_burst: char,
+    pub missile: char,
+    pub shot_boss: char,
+    pub laser: char,
+    pub boss_health_bar: char,
+    pub shot_ship_basic: char,
+    pub shot_ship_strong: char,
+    pub shot_ship_diagonal_l: char,
+    pub shot_ship_diagonal_r: char,
+    pub rapid_fire: char,
+    pub spread: char,
+    pub score_multiplier: char,
+    pub invincibility: char,
+    pub bomb: char,
+}
+
+/// The names of the keys bound to each [`Action`]. They are only interpreted by the frontend,
+/// e.g. `"W"`, `"Up"` or `"Space"`, and compared case-insensitively.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct KeyBindings {
+    pub move_up: Vec<String>,
+    pub move_down: Vec<String>,
+    pub move_left: Vec<String>,
+    pub move_right: Vec<String>,
+    pub toggle_auto_shoot: Vec<String>,
+    /// Called `fire_once` before the fire modes, configs saved back then still load.
+    #[serde(alias = "fire_once")]
+    pub fire: Vec<String>,
+    pub bomb: Vec<String>,
+    pub pause: Vec<String>,
+}
+
+impl KeyBindings {
+    pub fn get(&self, action: Action) -> &Vec<String> {
+        match action {
+            Action::MoveUp => &self.move_up,
+            Action::MoveDown => &self.move_down,
+            Action::MoveLeft => &self.move_left,
+            Action::MoveRight => &self.move_right,
+            Action::ToggleAutoShoot => &self.toggle_auto_shoot,
+            Action::Fire => &self.fire,
+            Action::Bomb => &self.bomb,
+            Action::Pause => &self.pause,
+        }
+    }
+
+    pub fn get_mut(&mut self, action: Action) -> &mut Vec<String> {
+        match action {
+            Action::MoveUp => &mut self.move_up,
+            Action::MoveDown => &mut self.move_down,
+            Action::MoveLeft => &mut self.move_left,
+            Action::MoveRight => &mut self.move_right,
+            Action::ToggleAutoShoot => &mut self.toggle_auto_shoot,
+            Action::Fire => &mut self.fire,
+            Action::Bomb => &mut self.bomb,
+            Action::Pause => &mut self.pause,
+        }
+    }
+
+    /// The keys of the second ship in co-op, on the right hand side of the keyboard.
+    pub fn player2() -> Self {
+        let keys = |names: &[&str]| names.iter().map(|name| name.to_string()).collect();
+        Self {
+            move_up: keys(&["I"]),
+            move_down: keys(&["K"]),
+            move_left: keys(&["J"]),
+            move_right: keys(&["L"]),
+            toggle_auto_shoot: keys(&["U"]),
+            fire: keys(&["O"]),
+            bomb: keys(&["N"]),
+            pause: Vec::new(),
+        }
+    }
+
+    /// Returns the first key bound to two different actions, together with both actions.
+    pub fn find_conflict(&self) -> Option<(&str, Action, Action)> {
+        for (i, action) in Action::ALL.iter().enumerate() {
+            for other in &Action::ALL[i + 1..] {
+                for key in self.get(*action) {
+                    if self.get(*other).iter().any(|other_key| other_key.eq_ignore_ascii_case(key)) {
+                        return Some((key, *action, *other));
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns the first key bound both here and in `other`, together with its action in each.
+    pub fn find_conflict_with<'a>(&'a self, other: &KeyBindings) -> Option<(&'a str, Action, Action)> {
+        for action in Action::ALL {
+            for other_action in Action::ALL {
+                for key in self.get(action) {
+                    if other.get(other_action).iter().any(|other_key| other_key.eq_ignore_ascii_case(key)) {
+                        return Some((key, action, other_action));
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            fps_limit: FPS_LIMIT,
+            width: GAME_SIZE.0,
+            height: GAME_SIZE.1,
+            move_speed_x: MOVE_SPEED_X,
+            move_speed_y: MOVE_SPEED_Y,
+            enemy_shot_probability: ENEMY_SHOT_PROBABILITY,
+            fire_mode: FireMode::default(),
+            charge_frames: CHARGE_FRAMES,
+            shot_interval: SHIP_SHOT_INTERVAL,
+            players: PLAYERS,
+            mode: GameMode::default(),
+            initial_health: INITIAL_HEALTH,
+            initial_shield: INITIAL_SHIELD,
+            max_health: MAX_HEALTH,
+            max_shield: MAX_SHIELD,
+            damage: DamageConfig::default(),
+            spawn: SpawnConfig::default(),
+            boss: BossConfig::default(),
+            hud: HudConfig::default(),
+            goodies: GoodieConfig::default(),
+            effects: EffectConfig::default(),
+            scoring: ScoringConfig::default(),
+            weapons: WeaponConfig::default(),
+            glyphs: Glyphs::default(),
+            keys: KeyBindings::default(),
+            keys_player2: KeyBindings::player2(),
+        }
+    }
+}
+
+impl Default for DamageConfig {
+    fn default() -> Self {
+        Self {
+            enemy_reached_ground: DMG_ENEMY_REACHED_GROUND,
+            collision: DMG_COLLISION,
+            shot_hit: DMG_SHOT_HIT,
+            shot_ship_basic: DMG_SHOT_SHIP_BASIC,
+            shot_ship_strong: DMG_SHOT_SHIP_STRONG,
+            laser: DMG_LASER,
+            boss_contact: DMG_BOSS_CONTACT,
+            missile: DMG_MISSILE,
+        }
+    }
+}
+
+impl Default for SpawnConfig {
+    fn default() -> Self {
+        Self {
+            initial_interval: INITIAL_SPAWN_INTERVAL,
+            speedup_after_frames: SPEEDUP_AFTER_X_FRAMES,
+            interval_decrease: SPAWN_INTERVAL_DECREASE,
+            min_interval: MIN_SPAWN_INTERVAL,
+        }
+    }
+}
+
+impl Default for BossConfig {
+    fn default() -> Self {
+        Self {
+            score_interval: BOSS_SCORE_INTERVAL,
+            frame_interval: BOSS_FRAME_INTERVAL,
+            base_health: BOSS_BASE_HEALTH,
+            health_increase: BOSS_HEALTH_INCREASE,
+            score: BOSS_SCORE,
+            move_interval: BOSS_MOVE_INTERVAL,
+            attack_interval: BOSS_ATTACK_INTERVAL,
+            laser_duration: BOSS_LASER_DURATION,
+        }
+    }
+}
+
+impl Default for HudConfig {
+    fn default() -> Self {
+        Self {
+            health_granularity: HUD_HEALTH_GRANULARITY,
+            shield_granularity: HUD_SHIELD_GRANULARITY,
+        }
+    }
+}
+
+impl Default for GoodieConfig {
+    fn default() -> Self {
+        Self {
+            repair_kit_health: REPAIR_KIT_HEALTH,
+            shield_boost_shield: SHIELD_BOOST_SHIELD,
+            max_bombs: MAX_BOMBS,
+            bomb_boss_damage: DMG_BOMB_BOSS,
+            fall_speed: GOODIE_FALL_SPEED,
+            weights: GoodieWeights::default(),
+        }
+    }
+}
+
+impl Default for GoodieWeights {
+    fn default() -> Self {
+        Self {
+            repair_kit: WEIGHT_REPAIR_KIT,
+            shield_boost: WEIGHT_SHIELD_BOOST,
+            ship_upgrade: WEIGHT_SHIP_UPGRADE,
+            rapid_fire: WEIGHT_RAPID_FIRE,
+            spread: WEIGHT_SPREAD,
+            score_multiplier: WEIGHT_SCORE_MULTIPLIER,
+            invincibility: WEIGHT_INVINCIBILITY,
+            bomb: WEIGHT_BOMB,
+        }
+    }
+}
+
+impl Default for EffectConfig {
+    fn default() -> Self {
+        Self {
+            rapid_fire_duration: RAPID_FIRE_DURATION,
+            rapid_fire_shot_interval: RAPID_FIRE_SHOT_INTERVAL,
+            spread_duration: SPREAD_DURATION,
+            score_multiplier_duration: SCORE_MULTIPLIER_DURATION,
+            score_multiplier: SCORE_MULTIPLIER,
+            invincibility_duration: INVINCIBILITY_DURATION,
+        }
+    }
+}
+
+impl Default for ScoringConfig {
+    fn default() -> Self {
+        Self {
+            drone: SCORE_DRONE,
+            fighter: SCORE_FIGHTER,
+            tank: SCORE_TANK,
+            combo_window: COMBO_WINDOW,
+            combo_bonus: COMBO_BONUS,
+            multiplier_kills: MULTIPLIER_KILLS,
+            max_multiplier: MAX_MULTIPLIER,
+            goodie_bonus: GOODIE_BONUS,
+            graze_bonus: GRAZE_BONUS,
+            no_damage_wave_bonus: NO_DAMAGE_WAVE_BONUS,
+            missile: SCORE_MISSILE,
+        }
+    }
+}
+
+impl Default for WeaponConfig {
+    fn default() -> Self {
+        Self {
+            aimed_speed: AIMED_SHOT_SPEED,
+            spread_speed: SPREAD_SHOT_SPEED,
+            burst_speed: BURST_SHOT_SPEED,
+            burst_count: BURST_COUNT,
+            burst_delay: BURST_DELAY,
+            burst_interval: BURST_INTERVAL,
+            missile_speed: MISSILE_SPEED,
+            missile_homing_frames: MISSILE_HOMING_FRAMES,
+            random_weapon_probability: RANDOM_WEAPON_PROBABILITY,
+        }
+    }
+}
+
+impl Default for Glyphs {
+    fn default() -> Self {
+        Self {
+            health: CHAR_HEALTH,
+            shield: CHAR_SHIELD,
+            upgrade_ship_basic: CHAR_UPGRADE_SHIP_BASIC,
+            upgrade_ship_diagonal: CHAR_UPGRADE_SHIP_DIAGONAL,
+            upgrade_ship_strong: CHAR_UPGRADE_SHIP_STRONG,
+            enemy: CHAR_ENEMY,
+            enemy_fighter: CHAR_ENEMY_FIGHTER,
+            enemy_tank: CHAR_ENEMY_TANK,
+            health_bar: CHAR_HEALTH_BAR,
+            shield_bar: CHAR_SHIELD_BAR,
+            shot_enemy: CHAR_SHOT_ENEMY,
+            shot_enemy_aimed: CHAR_SHOT_ENEMY_AIMED,
+            shot_enemy_spread: CHAR_SHOT_ENEMY_SPREAD,
+            shot_enemy_burst: CHAR_SHOT_ENEMY_BURST,
+            missile: CHAR_MISSILE,
+            shot_boss: CHAR_SHOT_BOSS,
+            laser: CHAR_LASER,
+            boss_health_bar: CHAR_BOSS_HEALTH_BAR,
+            shot_ship_basic: CHAR_SHOT_SHIP_BASIC,
+            shot_ship_strong: CHAR_SHOT_SHIP_STRONG,
+            shot_ship_diagonal_l: CHAR_SHOT_SHIP_DIAGONAL_L,
+            shot_ship_diagonal_r: CHAR_SHOT_SHIP_DIAGONAL_R,
+            rapid_fire: CHAR_RAPID_FIRE,
+            spread: CHAR_SPREAD,
+            score_multiplier: CHAR_SCORE_MULTIPLIER,
+            invincibility: CHAR_INVINCIBILITY,
+            bomb: CHAR_BOMB,
+        }
+    }
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        let keys = |names: &[&str]| names.iter().map(|name| name.to_string()).collect();
+        Self {
+            move_up: keys(&["W", "Up"]),
+            move_down: keys(&["S", "Down"]),
+            move_left: keys(&["A", "Left"]),
+            move_right: keys(&["D", "Right"]),
+            toggle_auto_shoot: keys(&["Space"]),
+            fire: keys(&["F"]),
+            bomb: keys(&["B"]),
+            pause: keys(&["P", "Esc"]),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(PathBuf, io::Error),
+    Write(PathBuf, io::Error),
+    Parse(String, toml::de::Error),
+    InvalidOverride(String),
+    Invalid(String),
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::Io(path, err) => write!(f, "could not read config {}: {}", path.display(), err),
+            ConfigError::Write(path, err) => write!(f, "could not write config {}: {}", path.display(), err),
+            ConfigError::Parse(source, err) => write!(f, "could not parse config {}: {}", source, err),
+            ConfigError::InvalidOverride(arg) => write!(f, "invalid config override '{}', expected KEY=VALUE", arg),
+            ConfigError::Invalid(reason) => write!(f, "invalid config: {}", reason),
+        }
+    }
+}
+
+impl error::Error for ConfigError {}
+
+impl GameConfig {
+    /// Loads the config from an optional TOML `file`, then applies the `KEY=VALUE` `overrides` and validates the result.
+    pub fn load(file: Option<&Path>, overrides: &[String]) -> Result<GameConfig, ConfigError> {
+        let mut table = match file {
+            Some(path) => {
+                let content = fs::read_to_string(path).map_err(|err| ConfigError::Io(path.to_path_buf(), err))?;
+                content.parse::<Table>().map_err(|err| ConfigError::Parse(path.display().to_string(), err))?
+            }
+            None => Table::new(),
+        };
+        for arg in overrides {
+            apply_override(&mut table, arg)?;
+        }
+        let source = file.map_or("arguments".to_string(), |path| path.display().to_string());
+        GameConfig::from_table(table, &source)
+    }
+
+    pub fn from_toml(content: &str) -> Result<GameConfig, ConfigError> {
+        let table = content.parse::<Table>().map_err(|err| ConfigError::Parse("content".to_string(), err))?;
+        GameConfig::from_table(table, "content")
+    }
+
+    pub fn to_toml(&self) -> String {
+        toml::to_string(self).expect("config is always serializable")
+    }
+
+    fn from_table(mut table: Table, source: &str) -> Result<GameConfig, ConfigError> {
+        fill_player2_keys(&mut table);
+        let config: GameConfig = table.try_into().map_err(|err| ConfigError::Parse(source.to_string(), err))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Replaces the `[keys]` and `[keys_player2]` of the config file at `path` with `keys` and `keys_player2`.
+    /// Everything else in the file stays as it is, including the comments. The file is created if it does not exist yet.
+    /// Nothing is written unless the config with the new keys is valid, so the game always starts with a saved config.
+    pub fn store_keys(path: &Path, keys: &KeyBindings, keys_player2: &KeyBindings) -> Result<(), ConfigError> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(ConfigError::Io(path.to_path_buf(), err)),
+        };
+        let mut table = content.parse::<Table>().map_err(|err| ConfigError::Parse(path.display().to_string(), err))?;
+        for (name, bindings) in [("keys", keys), ("keys_player2", keys_player2)] {
+            table.insert(name.to_string(), Value::try_from(bindings).expect("key bindings are always serializable"));
+        }
+        GameConfig::from_table(table, &path.display().to_string())?;
+        let mut document = content.parse::<DocumentMut>().expect("valid TOML is a valid document");
+        store_key_table(&mut document, "keys", keys);
+        store_key_table(&mut document, "keys_player2", keys_player2);
+        let write = || {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            fs::write(path, document.to_string())
+        };
+        write().map_err(|err| ConfigError::Write(path.to_path_buf(), err))
+    }
+
+    pub fn dimension(&self) -> Vec2 {
+        Vec2::xy(self.width, self.height)
+    }
+
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let checks = [
+            (1 <= self.fps_limit && self.fps_limit <= 240, "fps_limit must be between 1 and 240"),
+            (self.width >= 20, "width must be at least 20"),
+            (self.height >= 10, "height must be at least 10"),
+            (0.0 < self.move_speed_x && self.move_speed_x <= 5.0, "move_speed_x must be above 0 and at most 5"),
+            (0.0 < self.move_speed_y && self.move_speed_y <= 5.0, "move_speed_y must be above 0 and at most 5"),
+            ((0.0..=1.0).contains(&self.enemy_shot_probability), "enemy_shot_probability must be between 0 and 1"),
+            (self.charge_frames >= 1, "charge_frames must be at least 1"),
+            (self.shot_interval >= 1, "shot_interval must be at least 1"),
+            (1 <= self.players && self.players <= MAX_PLAYERS, "players must be 1 or 2"),
+            (self.max_health >= 1, "max_health must be at least 1"),
+            (1 <= self.initial_health && self.initial_health <= self.max_health, "initial_health must be between 1 and max_health"),
+            (self.initial_shield <= self.max_shield, "initial_shield must not exceed max_shield"),
+            (self.spawn.min_interval >= 1, "spawn.min_interval must be at least 1"),
+            (self.spawn.initial_interval >= self.spawn.min_interval, "spawn.initial_interval must not be below spawn.min_interval"),
+            (self.damage.shot_ship_basic >= 1, "damage.shot_ship_basic must be at least 1"),
+            (self.damage.shot_ship_strong >= 1, "damage.shot_ship_strong must be at least 1"),
+            (self.boss.base_health >= 1, "boss.base_health must be at least 1"),
+            (self.boss.laser_duration >= 1, "boss.laser_duration must be at least 1"),
+            (self.boss.move_interval >= 1, "boss.move_interval must be at least 1"),
+            (self.boss.attack_interval >= 1, "boss.attack_interval must be at least 1"),
+            (self.hud.health_granularity >= 1, "hud.health_granularity must be at least 1"),
+            (self.hud.shield_granularity >= 1, "hud.shield_granularity must be at least 1"),
+            (self.goodies.weights.total() >= 1, "goodies.weights must not all be 0"),
+            (0.0 < self.goodies.fall_speed && self.goodies.fall_speed <= 1.0, "goodies.fall_speed must be above 0 and at most 1"),
+            (self.effects.rapid_fire_duration >= 1, "effects.rapid_fire_duration must be at least 1"),
+            (self.effects.rapid_fire_shot_interval >= 1, "effects.rapid_fire_shot_interval must be at least 1"),
+            (self.effects.spread_duration >= 1, "effects.spread_duration must be at least 1"),
+            (self.effects.score_multiplier_duration >= 1, "effects.score_multiplier_duration must be at least 1"),
+            (self.effects.invincibility_duration >= 1, "effects.invincibility_duration must be at least 1"),
+            (self.scoring.multiplier_kills >= 1, "scoring.multiplier_kills must be at least 1"),
+            (self.scoring.max_multiplier >= 1, "scoring.max_multiplier must be at least 1"),
+            (self.scoring.combo_window >= 1, "scoring.combo_window must be at least 1"),
+            (0.0 < self.weapons.aimed_speed && self.weapons.aimed_speed <= 1.0, "weapons.aimed_speed must be above 0 and at most 1"),
+            (0.0 < self.weapons.spread_speed && self.weapons.spread_speed <= 1.0, "weapons.spread_speed must be above 0 and at most 1"),
+            (0.0 < self.weapons.burst_speed && self.weapons.burst_speed <= 1.0, "weapons.burst_speed must be above 0 and at most 1"),
+            (0.0 < self.weapons.missile_speed && self.weapons.missile_speed <= 1.0, "weapons.missile_speed must be above 0 and at most 1"),
+            ((0.0..=1.0).contains(&self.weapons.random_weapon_probability), "weapons.random_weapon_probability must be between 0 and 1"),
+            (self.weapons.burst_count >= 1, "weapons.burst_count must be at least 1"),
+            (self.weapons.burst_interval >= 1, "weapons.burst_interval must be at least 1"),
+            (self.weapons.burst_delay >= 1, "weapons.burst_delay must be at least 1"),
+        ];
+        if let Some((_, reason)) = checks.iter().find(|(valid, _)| !valid) {
+            return Err(ConfigError::Invalid(reason.to_string()));
+        }
+        if let Some((key, action, other)) = self.keys.find_conflict() {
+            return Err(ConfigError::Invalid(
+                format!("key '{}' is bound to both keys.{} and keys.{}", key, action.name(), other.name())
+            ));
+        }
+        if let Some((key, action, other)) = self.keys_player2.find_conflict() {
+            return Err(ConfigError::Invalid(
+                format!("key '{}' is bound to both keys_player2.{} and keys_player2.{}", key, action.name(), other.name())
+            ));
+        }
+        match self.keys.find_conflict_with(&self.keys_player2) {
+            Some((key, action, other)) => Err(ConfigError::Invalid(
+                format!("key '{}' is bound to both keys.{} and keys_player2.{}", key, action.name(), other.name())
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Sets every action of the `name` table in `document` to its keys in `keys`, adding the table if it is missing.
+fn store_key_table(document: &mut DocumentMut, name: &str, keys: &KeyBindings) {
+    if !document.get(name).is_some_and(Item::is_table_like) {
+        document.insert(name, toml_edit::table());
+    }
+    let table = document[name].as_table_like_mut().expect("the keys are a table");
+    for action in Action::ALL {
+        let mut names = toml_edit::Value::Array(keys.get(action).iter().collect::<Array>());
+        // updated in place, so the comments around the line stay
+        match table.get_mut(action.name()) {
+            Some(Item::Value(old)) => {
+                *names.decor_mut() = old.decor().clone();
+                *old = names;
+            }
+            _ => { table.insert(action.name(), Item::Value(names)); }
+        }
+    }
+}
+
+/// Fills the actions missing from a `[keys_player2]` table with the second ship's default keys,
+/// as the usual defaults would be the keys of the first ship.
+fn fill_player2_keys(table: &mut Table) {
+    let Some(Value::Table(keys)) = table.get_mut("keys_player2") else { return; };
+    let Ok(Value::Table(defaults)) = Value::try_from(KeyBindings::player2()) else { return; };
+    for (action, default_keys) in defaults {
+        keys.entry(action).or_insert(default_keys);
+    }
+}
+
+/// Inserts a `some.nested.key=value` override into `table`. Values are parsed as TOML,
+/// falling back to a plain string so glyphs can be given without quotes.
+fn apply_override(table: &mut Table, arg: &str) -> Result<(), ConfigError> {
+    let invalid = || ConfigError::InvalidOverride(arg.to_string());
+    let (key, value) = arg.split_once('=').ok_or_else(invalid)?;
+    let value = match format!("value = {}", value.trim()).parse::<Table>() {
+        Ok(mut parsed) => parsed.remove("value").ok_or_else(invalid)?,
+        Err(_) => Value::String(value.trim().to_string()),
+    };
+    let mut keys: Vec<&str> = key.trim().split('.').collect();
+    let last = keys.pop().filter(|last| !last.is_empty()).ok_or_else(invalid)?;
+    let mut current = table;
+    for key in keys {
+        current = current.entry(key)
+            .or_insert_with(|| Value::Table(Table::new()))
+            .as_table_mut()
+            .ok_or_else(invalid)?;
+    }
+    current.insert(last.to_string(), value);
+    Ok(())
+}
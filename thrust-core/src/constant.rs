@@ -0,0 +1,123 @@
+pub const FPS_LIMIT: u32 = 20;
+pub const MOVE_SPEED_X: f64 = 2.0;
+pub const MOVE_SPEED_Y: f64 = 1.0;
+pub const GAME_SIZE: (i32, i32) = (60, 32);
+pub const BORDER_SIZE: i32 = 1;
+pub const GRID_BUCKET_SIZE: i32 = 4;
+
+pub const ENEMY_SHOT_PROBABILITY: f64 = 0.2;
+pub const CHARGE_FRAMES: usize = FPS_LIMIT as usize;
+pub const PLAYERS: usize = 1;
+pub const MAX_PLAYERS: usize = 2;
+pub const NET_HANDSHAKE_TIMEOUT_MILLIS: u64 = 5000;
+pub const NET_TIMEOUT_MILLIS: u64 = 10000;
+pub const NET_LEAVE_TIMEOUT_MILLIS: u64 = 500;
+pub const SPECTATOR_MAX_BACKLOG: usize = 1 << 20;
+pub const SPECTATOR_MAX_MESSAGE_LEN: usize = 4 << 20;
+pub const REPLAY_MAX_CONFIG_LEN: usize = 1 << 20;
+pub const REPLAY_MAX_FRAMES: usize = 1 << 22;
+pub const AUTOPILOT_HORIZON: usize = 8;
+pub const AUTOPILOT_LOW_HEALTH_PERCENT: usize = 40;
+
+pub const INITIAL_HEALTH: usize = 100;
+pub const INITIAL_SHIELD: usize = 0;
+pub const MAX_HEALTH: usize = 100;
+pub const MAX_SHIELD: usize = 100;
+
+pub const DMG_ENEMY_REACHED_GROUND: usize = 15;
+pub const DMG_COLLISION: usize = 50;
+pub const DMG_SHOT_HIT: usize = 5;
+pub const DMG_SHOT_SHIP_BASIC: usize = 1;
+pub const DMG_SHOT_SHIP_STRONG: usize = 2;
+
+pub const INITIAL_SPAWN_INTERVAL: usize = (2 * FPS_LIMIT) as usize;
+pub const SPEEDUP_AFTER_X_FRAMES: usize = (FPS_LIMIT * 10) as usize;
+pub const SPAWN_INTERVAL_DECREASE: usize = 10;
+pub const MIN_SPAWN_INTERVAL: usize = (FPS_LIMIT / 2) as usize;
+
+pub const BOSS_SCORE_INTERVAL: usize = 250;
+pub const BOSS_FRAME_INTERVAL: usize = (FPS_LIMIT * 120) as usize;
+pub const BOSS_BASE_HEALTH: usize = 40;
+pub const BOSS_HEALTH_INCREASE: usize = 20;
+pub const BOSS_SCORE: usize = 100;
+pub const BOSS_MOVE_INTERVAL: usize = 3;
+pub const BOSS_ATTACK_INTERVAL: usize = (FPS_LIMIT * 2) as usize;
+pub const BOSS_LASER_DURATION: usize = (FPS_LIMIT * 3) as usize;
+pub const DMG_LASER: usize = 2;
+pub const DMG_BOSS_CONTACT: usize = 5;
+pub const DMG_MISSILE: usize = 10;
+
+pub const AIMED_SHOT_SPEED: f64 = 0.8;
+pub const SPREAD_SHOT_SPEED: f64 = 0.6;
+pub const BURST_SHOT_SPEED: f64 = 1.0;
+pub const BURST_COUNT: usize = 4;
+pub const BURST_DELAY: usize = (FPS_LIMIT / 2) as usize;
+pub const BURST_INTERVAL: usize = 2;
+pub const MISSILE_SPEED: f64 = 0.3;
+pub const MISSILE_HOMING_FRAMES: usize = (FPS_LIMIT * 3) as usize;
+pub const RANDOM_WEAPON_PROBABILITY: f64 = 0.25;
+
+pub const SHIP_SHOT_INTERVAL: usize = 15;
+pub const REPAIR_KIT_HEALTH: usize = 5;
+pub const SHIELD_BOOST_SHIELD: usize = 10;
+pub const MAX_BOMBS: usize = 3;
+pub const DMG_BOMB_BOSS: usize = 10;
+pub const GOODIE_FALL_SPEED: f64 = 1.0;
+pub const RAPID_FIRE_DURATION: usize = (FPS_LIMIT * 10) as usize;
+pub const RAPID_FIRE_SHOT_INTERVAL: usize = 5;
+pub const SPREAD_DURATION: usize = (FPS_LIMIT * 10) as usize;
+pub const SCORE_MULTIPLIER_DURATION: usize = (FPS_LIMIT * 10) as usize;
+pub const SCORE_MULTIPLIER: usize = 2;
+pub const INVINCIBILITY_DURATION: usize = (FPS_LIMIT * 5) as usize;
+
+pub const SCORE_DRONE: usize = 5;
+pub const SCORE_FIGHTER: usize = 10;
+pub const SCORE_TANK: usize = 20;
+pub const COMBO_WINDOW: usize = FPS_LIMIT as usize;
+pub const COMBO_BONUS: usize = 2;
+pub const MULTIPLIER_KILLS: usize = 10;
+pub const MAX_MULTIPLIER: usize = 4;
+pub const GOODIE_BONUS: usize = 2;
+pub const GRAZE_BONUS: usize = 3;
+pub const NO_DAMAGE_WAVE_BONUS: usize = 50;
+pub const SCORE_MISSILE: usize = 3;
+
+pub const WEIGHT_REPAIR_KIT: u32 = 20;
+pub const WEIGHT_SHIELD_BOOST: u32 = 30;
+pub const WEIGHT_SHIP_UPGRADE: u32 = 10;
+pub const WEIGHT_RAPID_FIRE: u32 = 4;
+pub const WEIGHT_SPREAD: u32 = 4;
+pub const WEIGHT_SCORE_MULTIPLIER: u32 = 4;
+pub const WEIGHT_INVINCIBILITY: u32 = 2;
+pub const WEIGHT_BOMB: u32 = 2;
+
+pub const HUD_HEALTH_GRANULARITY: usize = 5;
+pub const HUD_SHIELD_GRANULARITY: usize = 5;
+
+pub const CHAR_HEALTH: char = '♥';
+pub const CHAR_SHIELD: char = 'O';
+pub const CHAR_UPGRADE_SHIP_BASIC: char = '^';
+pub const CHAR_UPGRADE_SHIP_DIAGONAL: char = 'Y';
+pub const CHAR_UPGRADE_SHIP_STRONG: char = 'T';
+pub const CHAR_ENEMY: char = 'M';
+pub const CHAR_ENEMY_FIGHTER: char = 'W';
+pub const CHAR_ENEMY_TANK: char = 'H';
+pub const CHAR_HEALTH_BAR: char = '|';
+pub const CHAR_SHIELD_BAR: char = '|';
+pub const CHAR_SHOT_ENEMY: char = '|';
+pub const CHAR_SHOT_ENEMY_AIMED: char = 'o';
+pub const CHAR_SHOT_ENEMY_SPREAD: char = '.';
+pub const CHAR_SHOT_ENEMY_BURST: char = ':';
+pub const CHAR_MISSILE: char = 'V';
+pub const CHAR_SHOT_BOSS: char = '*';
+pub const CHAR_LASER: char = '¦';
+pub const CHAR_BOSS_HEALTH_BAR: char = '█';
+pub const CHAR_SHOT_SHIP_BASIC: char = '|';
+pub const CHAR_SHOT_SHIP_STRONG: char = '█';
+pub const CHAR_SHOT_SHIP_DIAGONAL_L: char = '\\';
+pub const CHAR_SHOT_SHIP_DIAGONAL_R: char = '/';
+pub const CHAR_RAPID_FIRE: char = '»';
+pub const CHAR_SPREAD: char = '%';
+pub const CHAR_SCORE_MULTIPLIER: char = '$';
+pub const CHAR_INVINCIBILITY: char = '#';
+pub const CHAR_BOMB: char = '@';
@@ -0,0 +1,77 @@
+use crate::config::{EffectConfig, Glyphs};
+
+/// A power-up that lasts for a limited number of frames after its goodie was picked up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EffectKind {
+    RapidFire,
+    Spread,
+    ScoreMultiplier,
+    Invincibility,
+}
+
+impl EffectKind {
+    pub const ALL: [EffectKind; 4] = [EffectKind::RapidFire, EffectKind::Spread, EffectKind::ScoreMultiplier, EffectKind::Invincibility];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            EffectKind::RapidFire => "Rapid fire",
+            EffectKind::Spread => "Spread",
+            EffectKind::ScoreMultiplier => "Multiplier",
+            EffectKind::Invincibility => "Invincible",
+        }
+    }
+
+    pub fn duration(&self, config: &EffectConfig) -> usize {
+        match self {
+            EffectKind::RapidFire => config.rapid_fire_duration,
+            EffectKind::Spread => config.spread_duration,
+            EffectKind::ScoreMultiplier => config.score_multiplier_duration,
+            EffectKind::Invincibility => config.invincibility_duration,
+        }
+    }
+
+    pub fn character(&self, glyphs: &Glyphs) -> char {
+        match self {
+            EffectKind::RapidFire => glyphs.rapid_fire,
+            EffectKind::Spread => glyphs.spread,
+            EffectKind::ScoreMultiplier => glyphs.score_multiplier,
+            EffectKind::Invincibility => glyphs.invincibility,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ActiveEffect {
+    pub kind: EffectKind,
+    pub remaining_frames: usize,
+}
+
+/// The effects currently active on the ship, in the order they were picked up.
+#[derive(Debug, Clone, Default, Hash)]
+pub struct Effects {
+    active: Vec<ActiveEffect>,
+}
+
+impl Effects {
+    /// Starts the effect, or restarts its duration if it is already active.
+    pub fn activate(&mut self, kind: EffectKind, duration: usize) {
+        match self.active.iter_mut().find(|effect| effect.kind == kind) {
+            Some(effect) => effect.remaining_frames = duration,
+            None => self.active.push(ActiveEffect { kind, remaining_frames: duration }),
+        }
+    }
+
+    pub fn is_active(&self, kind: EffectKind) -> bool {
+        self.active.iter().any(|effect| effect.kind == kind)
+    }
+
+    pub fn active(&self) -> &[ActiveEffect] {
+        &self.active
+    }
+
+    /// Counts down all effects by one frame and removes the expired ones.
+    pub fn update(&mut self) {
+        self.active.iter_mut().for_each(|effect| effect.remaining_frames -= 1);
+        self.active.retain(|effect| effect.remaining_frames > 0);
+    }
+}
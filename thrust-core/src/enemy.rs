@@ -0,0 +1,194 @@
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+use serde::Deserialize;
+use crate::config::{GameConfig, Glyphs, ScoringConfig, WeaponConfig};
+use crate::fixed::{Fixed, FixedVec2};
+use crate::movement::{Movement, MovementContext};
+use crate::shot::ShotType;
+use crate::spatial::Vec2;
+use crate::world::Entity;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EnemyKind {
+    Drone,
+    Fighter,
+    Tank,
+}
+
+impl EnemyKind {
+    pub const ALL: [EnemyKind; 3] = [EnemyKind::Drone, EnemyKind::Fighter, EnemyKind::Tank];
+
+    pub fn max_health(&self) -> usize {
+        match self {
+            EnemyKind::Drone => 1,
+            EnemyKind::Fighter => 2,
+            EnemyKind::Tank => 4,
+        }
+    }
+
+    pub fn score(&self, scoring: &ScoringConfig) -> usize {
+        match self {
+            EnemyKind::Drone => scoring.drone,
+            EnemyKind::Fighter => scoring.fighter,
+            EnemyKind::Tank => scoring.tank,
+        }
+    }
+
+    pub fn character(&self, glyphs: &Glyphs) -> char {
+        match self {
+            EnemyKind::Drone => glyphs.enemy,
+            EnemyKind::Fighter => glyphs.enemy_fighter,
+            EnemyKind::Tank => glyphs.enemy_tank,
+        }
+    }
+}
+
+impl Distribution<EnemyKind> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> EnemyKind {
+        match rng.gen_range(0..=5) {
+            0..=3 => EnemyKind::Drone,
+            4 => EnemyKind::Fighter,
+            _ => EnemyKind::Tank,
+        }
+    }
+}
+
+/// What an enemy fires on each of its shots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EnemyWeapon {
+    /// A single shot straight down.
+    Straight,
+    /// A single shot towards the ship.
+    Aimed,
+    /// Three shots fanning out downwards.
+    Spread,
+    /// Several shots towards the ship in quick succession, after a short delay to warn the player.
+    Burst,
+    /// A slow missile steering towards the ship for a while, which can be shot down.
+    Missile,
+}
+
+impl EnemyWeapon {
+    /// The weapon enemies of `kind` use unless a level says otherwise.
+    pub fn of(kind: EnemyKind) -> EnemyWeapon {
+        match kind {
+            EnemyKind::Drone => EnemyWeapon::Straight,
+            EnemyKind::Fighter => EnemyWeapon::Aimed,
+            EnemyKind::Tank => EnemyWeapon::Spread,
+        }
+    }
+}
+
+impl Distribution<EnemyWeapon> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> EnemyWeapon {
+        match rng.gen_range(0..=4) {
+            0 => EnemyWeapon::Straight,
+            1 => EnemyWeapon::Aimed,
+            2 => EnemyWeapon::Spread,
+            3 => EnemyWeapon::Burst,
+            _ => EnemyWeapon::Missile,
+        }
+    }
+}
+
+/// A burst the enemy is about to fire or still firing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Burst {
+    pub remaining_shots: usize,
+    pub next_shot_frame: usize,
+}
+
+/// The part of an enemy entity driving its movement and weapon.
+#[derive(Hash)]
+pub struct Enemy {
+    pub kind: EnemyKind,
+    /// The cell the movement pattern moved the enemy to, which it glides towards until its next move.
+    pub target: Vec2,
+    pub movement: Movement,
+    pub last_movement: usize,
+    pub move_interval: usize,
+    pub last_shot_frame: usize,
+    pub shot_interval: usize,
+    pub weapon: EnemyWeapon,
+    pub burst: Option<Burst>,
+}
+
+impl Enemy {
+    pub fn new(kind: EnemyKind, initial_position: Vec2, movement: Movement, shot_interval: usize) -> Self {
+        Self {
+            kind,
+            target: initial_position,
+            movement,
+            move_interval: 10,
+            last_movement: 0,
+            shot_interval,
+            last_shot_frame: 0,
+            weapon: EnemyWeapon::of(kind),
+            burst: None,
+        }
+    }
+
+    /// Fires the enemy's weapon from `pos` every `shot_interval` frames with a chance of `shot_probability`,
+    /// adding the new shots to `shots`. `target` is where aimed weapons point to.
+    pub fn shoot<R: Rng>(&mut self, shot_frame: usize, pos: Vec2, shots: &mut Vec<Entity>, target: Vec2, config: &GameConfig, rng: &mut R) {
+        self.fire_burst(shot_frame, pos, shots, target, &config.weapons);
+        if self.last_shot_frame + self.shot_interval < shot_frame {
+            if self.burst.is_none() && rng.gen_bool(config.enemy_shot_probability) {
+                self.fire(shot_frame, pos, shots, target, &config.weapons);
+            }
+            self.last_shot_frame = shot_frame;
+        }
+    }
+
+    fn fire(&mut self, shot_frame: usize, pos: Vec2, shots: &mut Vec<Entity>, target: Vec2, weapons: &WeaponConfig) {
+        match self.weapon {
+            EnemyWeapon::Straight => shots.push(Entity::shot(pos, Vec2::y(1), ShotType::Enemy)),
+            EnemyWeapon::Aimed => {
+                shots.push(Entity::aimed_shot(pos, target, Fixed::from_f64(weapons.aimed_speed), ShotType::EnemyAimed));
+            }
+            EnemyWeapon::Spread => {
+                let speed = Fixed::from_f64(weapons.spread_speed);
+                for dx in -1..=1 {
+                    let velocity = FixedVec2::towards(FixedVec2::default(), FixedVec2::from_cell(Vec2::xy(dx, 2)), speed);
+                    shots.push(Entity::shot_with_velocity(pos, velocity, ShotType::EnemySpread));
+                }
+            }
+            EnemyWeapon::Burst => {
+                self.burst = Some(Burst { remaining_shots: weapons.burst_count, next_shot_frame: shot_frame + weapons.burst_delay });
+            }
+            EnemyWeapon::Missile => {
+                shots.push(Entity::aimed_shot(pos, target, Fixed::from_f64(weapons.missile_speed), ShotType::Missile));
+            }
+        }
+    }
+
+    fn fire_burst(&mut self, shot_frame: usize, pos: Vec2, shots: &mut Vec<Entity>, target: Vec2, weapons: &WeaponConfig) {
+        let Some(burst) = self.burst.as_mut().filter(|burst| burst.next_shot_frame <= shot_frame) else { return; };
+        shots.push(Entity::aimed_shot(pos, target, Fixed::from_f64(weapons.burst_speed), ShotType::EnemyBurst));
+        burst.remaining_shots -= 1;
+        burst.next_shot_frame = shot_frame + weapons.burst_interval;
+        if burst.remaining_shots == 0 {
+            self.burst = None;
+            self.last_shot_frame = shot_frame;
+        }
+    }
+
+    /// Whether the enemy is winding up or firing a burst, which frontends should make visible as a warning.
+    pub fn is_bursting(&self) -> bool {
+        self.burst.is_some()
+    }
+
+    /// Advances the enemy along its movement pattern every `move_interval` frames, keeping it within the field's columns.
+    /// In between, it glides from `exact` towards the cell the pattern moved it to, with the returned velocity.
+    pub fn move_self(&mut self, move_frame: usize, exact: FixedVec2, velocity: FixedVec2, context: &MovementContext) -> FixedVec2 {
+        if self.last_movement + self.move_interval >= move_frame {
+            return velocity;
+        }
+        let next = self.movement.step(self.target, context);
+        self.target = context.clamp(next);
+        self.last_movement = move_frame;
+        (FixedVec2::from_cell(self.target) - exact) / (self.move_interval as i32 + 1)
+    }
+}
@@ -0,0 +1,169 @@
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub};
+use crate::spatial::Vec2;
+
+const FRACTION_BITS: u32 = 8;
+
+/// A fixed-point number with 8 fractional bits, i.e. a precision of 1/256 of a cell.
+///
+/// Integer arithmetic keeps sub-cell movement deterministic on every platform, which replays rely on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Fixed(i32);
+
+impl Fixed {
+    pub const ZERO: Fixed = Fixed(0);
+    pub const ONE: Fixed = Fixed(1 << FRACTION_BITS);
+
+    pub const fn from_int(value: i32) -> Fixed {
+        Fixed(value << FRACTION_BITS)
+    }
+
+    /// `numerator / denominator`, e.g. `Fixed::ratio(1, 4)` for a quarter cell.
+    pub const fn ratio(numerator: i32, denominator: i32) -> Fixed {
+        Fixed((numerator << FRACTION_BITS) / denominator)
+    }
+
+    /// Converts a config value, rounding to the nearest representable number.
+    pub fn from_f64(value: f64) -> Fixed {
+        Fixed((value * (1 << FRACTION_BITS) as f64).round() as i32)
+    }
+
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 / (1 << FRACTION_BITS) as f64
+    }
+
+    pub const fn raw(self) -> i32 {
+        self.0
+    }
+
+    pub const fn from_raw(raw: i32) -> Fixed {
+        Fixed(raw)
+    }
+
+    /// The nearest whole number, halves are rounded up.
+    pub const fn round(self) -> i32 {
+        (self.0 + (1 << (FRACTION_BITS - 1))) >> FRACTION_BITS
+    }
+
+    pub fn abs(self) -> Fixed {
+        Fixed(self.0.abs())
+    }
+}
+
+impl Add for Fixed {
+    type Output = Fixed;
+
+    fn add(self, other: Fixed) -> Fixed {
+        Fixed(self.0 + other.0)
+    }
+}
+
+impl AddAssign for Fixed {
+    fn add_assign(&mut self, other: Fixed) {
+        self.0 += other.0;
+    }
+}
+
+impl Sub for Fixed {
+    type Output = Fixed;
+
+    fn sub(self, other: Fixed) -> Fixed {
+        Fixed(self.0 - other.0)
+    }
+}
+
+impl Neg for Fixed {
+    type Output = Fixed;
+
+    fn neg(self) -> Fixed {
+        Fixed(-self.0)
+    }
+}
+
+impl Mul<i32> for Fixed {
+    type Output = Fixed;
+
+    fn mul(self, factor: i32) -> Fixed {
+        Fixed(self.0 * factor)
+    }
+}
+
+impl Div<i32> for Fixed {
+    type Output = Fixed;
+
+    fn div(self, divisor: i32) -> Fixed {
+        Fixed(self.0 / divisor)
+    }
+}
+
+/// A position or velocity with sub-cell precision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct FixedVec2 {
+    pub x: Fixed,
+    pub y: Fixed,
+}
+
+impl FixedVec2 {
+    pub const fn new(x: Fixed, y: Fixed) -> FixedVec2 {
+        FixedVec2 { x, y }
+    }
+
+    /// The position of the center of `cell`.
+    pub const fn from_cell(cell: Vec2) -> FixedVec2 {
+        FixedVec2 { x: Fixed::from_int(cell.x), y: Fixed::from_int(cell.y) }
+    }
+
+    /// The cell the position lies in.
+    pub fn cell(self) -> Vec2 {
+        Vec2::xy(self.x.round(), self.y.round())
+    }
+
+    /// A velocity of `speed` cells per frame pointing from `from` to `to`. Points down if both are equal.
+    pub fn towards(from: FixedVec2, to: FixedVec2, speed: Fixed) -> FixedVec2 {
+        let (dx, dy) = ((to.x - from.x).raw() as i64, (to.y - from.y).raw() as i64);
+        let length = (dx * dx + dy * dy).isqrt();
+        if length == 0 {
+            return FixedVec2::new(Fixed::ZERO, speed);
+        }
+        let scale = |delta: i64| Fixed::from_raw((delta * speed.raw() as i64 / length) as i32);
+        FixedVec2::new(scale(dx), scale(dy))
+    }
+}
+
+impl Add for FixedVec2 {
+    type Output = FixedVec2;
+
+    fn add(self, other: FixedVec2) -> FixedVec2 {
+        FixedVec2::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for FixedVec2 {
+    fn add_assign(&mut self, other: FixedVec2) {
+        self.x += other.x;
+        self.y += other.y;
+    }
+}
+
+impl Sub for FixedVec2 {
+    type Output = FixedVec2;
+
+    fn sub(self, other: FixedVec2) -> FixedVec2 {
+        FixedVec2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Mul<i32> for FixedVec2 {
+    type Output = FixedVec2;
+
+    fn mul(self, factor: i32) -> FixedVec2 {
+        FixedVec2::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl Div<i32> for FixedVec2 {
+    type Output = FixedVec2;
+
+    fn div(self, divisor: i32) -> FixedVec2 {
+        FixedVec2::new(self.x / divisor, self.y / divisor)
+    }
+}
@@ -0,0 +1,608 @@
+use std::cmp::max;
+use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
+use rand::{Rng, SeedableRng, thread_rng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use crate::boss::Boss;
+use crate::config::GameConfig;
+use crate::constant::{BORDER_SIZE, GRID_BUCKET_SIZE};
+use crate::enemy::{Enemy, EnemyKind, EnemyWeapon};
+use crate::fixed::Fixed;
+use crate::goodie::GoodieType;
+use crate::input::FrameInput;
+use crate::level::{Level, LevelProgress};
+use crate::movement::{Movement, MovementContext, MovementPattern};
+use crate::player::Player;
+use crate::score::ScoreSource;
+use crate::ship::{FireMode, Ship};
+use crate::shot::ShotType;
+use crate::spatial::{SpatialGrid, Vec2};
+use crate::stats::DamageCause;
+use crate::world::{Entity, Faction, Role, World};
+
+/// How the ships of a game with two players play together.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GameMode {
+    /// The players fight the enemies together, the points of both count for the game.
+    #[default]
+    CoOp,
+    /// The players compete for the higher score: bosses come at the score of the leader
+    /// and the finished game is won by the player with the most points.
+    Versus,
+}
+
+impl GameMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameMode::CoOp => "co-op",
+            GameMode::Versus => "versus",
+        }
+    }
+}
+
+pub struct GameState {
+    pub config: GameConfig,
+    pub dimension: Vec2,
+    /// One player per ship, `config.players` of them.
+    pub players: Vec<Player>,
+    /// The enemies, shots and goodies on the field.
+    pub world: World,
+    pub boss: Option<Boss>,
+    pub boss_encounters: usize,
+    pub next_boss_score: usize,
+    pub next_boss_frame: usize,
+    pub level: Option<LevelProgress>,
+    pub frame: usize,
+    pub last_spawn: usize,
+    pub spawn_interval: usize,
+    pub last_spawn_speedup: usize,
+    pub seed: u64,
+    pub rng: ChaCha8Rng,
+    /// The goodies dropped so far by [`GoodieType::name`], to compare with the ones the players collected.
+    pub goodies_dropped: BTreeMap<&'static str, usize>,
+    /// The entities by where they moved during the frame, rebuilt for every collision pass.
+    grid: SpatialGrid,
+}
+
+impl GameState {
+    pub fn new(config: GameConfig) -> GameState {
+        GameState::with_seed(config, thread_rng().gen())
+    }
+
+    /// Creates a game whose every random decision is drawn from an RNG seeded with `seed`.
+    /// The same seed and the same sequence of inputs always produce the same run.
+    pub fn with_seed(config: GameConfig, seed: u64) -> GameState {
+        let dimension = config.dimension();
+        // the ships start evenly spread over the bottom row, a single one in the middle
+        let players = (0..config.players).map(|player| {
+            let x = dimension.x * (player as i32 + 1) / (config.players as i32 + 1);
+            Player::new(Ship::new(player, Vec2::xy(x, dimension.y - 2)), &config)
+        }).collect();
+        GameState {
+            dimension,
+            players,
+            world: World::default(),
+            boss: None,
+            boss_encounters: 0,
+            next_boss_score: config.boss.score_interval,
+            next_boss_frame: config.boss.frame_interval,
+            level: None,
+            frame: 0,
+            last_spawn: 0,
+            spawn_interval: config.spawn.initial_interval,
+            last_spawn_speedup: 0,
+            seed,
+            rng: ChaCha8Rng::seed_from_u64(seed),
+            goodies_dropped: BTreeMap::new(),
+            grid: SpatialGrid::new(dimension, GRID_BUCKET_SIZE),
+            config,
+        }
+    }
+
+    /// Switches from endless random spawning to playing the waves of `levels` one after the other.
+    pub fn start_levels(&mut self, levels: Vec<Level>) {
+        self.level = Some(LevelProgress::new(levels, self.frame));
+    }
+
+    /// The game ends once every ship is destroyed.
+    pub fn is_game_over(&self) -> bool {
+        self.players.iter().all(Player::is_destroyed)
+    }
+
+    pub fn is_level_complete(&self) -> bool {
+        self.level.as_ref().is_some_and(|level| level.is_complete())
+    }
+
+    /// The points of all players together.
+    pub fn score(&self) -> usize {
+        self.players.iter().map(|player| player.score).sum()
+    }
+
+    /// Whether the players compete against each other, which takes a [`GameMode::Versus`] game of more than one ship.
+    pub fn is_versus(&self) -> bool {
+        self.config.mode == GameMode::Versus && self.players.len() > 1
+    }
+
+    /// The player with the most points once a versus game is over or its level is complete.
+    /// `None` while the game runs, on a draw and in games that aren't versus.
+    pub fn winner(&self) -> Option<usize> {
+        if !self.is_versus() || !(self.is_game_over() || self.is_level_complete()) {
+            return None;
+        }
+        let best = self.players.iter().map(|player| player.score).max()?;
+        let mut leaders = (0..self.players.len()).filter(|&player| self.players[player].score == best);
+        let leader = leaders.next();
+        if leaders.next().is_some() { None } else { leader }
+    }
+
+    /// The score the boss milestones are counted with: the points of all players together, in versus the leader's.
+    fn milestone_score(&self) -> usize {
+        if self.is_versus() {
+            self.players.iter().map(|player| player.score).max().unwrap_or(0)
+        } else {
+            self.score()
+        }
+    }
+
+    /// The indices of the players whose ship is still on the field.
+    fn active_players(&self) -> Vec<usize> {
+        (0..self.players.len()).filter(|&player| !self.players[player].is_destroyed()).collect()
+    }
+
+    /// The position the enemies at `pos` aim and steer at: the closest ship still on the field.
+    fn target_ship(&self, pos: Vec2) -> Vec2 {
+        let distance = |ship: &Ship| {
+            let delta = ship.pos + Vec2::x(1) - pos;
+            delta.x * delta.x + delta.y * delta.y
+        };
+        self.players.iter()
+            .filter(|player| !player.is_destroyed())
+            .map(|player| &player.ship)
+            .min_by_key(|ship| distance(ship))
+            .unwrap_or(&self.players[0].ship)
+            .pos
+    }
+
+    /// A hash of the state that matters for the rest of the run. Two games fed the same seed, config and inputs
+    /// have the same checksum after every frame, networked games compare it to detect that they went apart.
+    /// It is the same on every platform, so games on 32 and 64 bit machines can be compared.
+    pub fn checksum(&self) -> u64 {
+        let mut hasher = Fnv1a::default();
+        (self.frame, self.rng.get_word_pos(), self.next_boss_score, self.next_boss_frame).hash(&mut hasher);
+        (self.boss_encounters, self.last_spawn, self.spawn_interval, self.last_spawn_speedup).hash(&mut hasher);
+        for player in &self.players {
+            (player.health, player.shield, player.score, player.bombs).hash(&mut hasher);
+            (&player.scoring, &player.effects).hash(&mut hasher);
+            (player.ship.exact, player.ship.auto_shoot, player.ship.charge).hash(&mut hasher);
+        }
+        for entity in self.world.iter() {
+            (entity.position.exact, entity.velocity, entity.health.map(|health| health.current)).hash(&mut hasher);
+            entity.role.hash(&mut hasher);
+        }
+        (&self.boss, &self.level).hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Fires, bombs and moves the ship of `player` as its `input` of `frame` says.
+    fn apply_input(&mut self, player: usize, input: &FrameInput, frame: usize) {
+        let ship = &mut self.players[player].ship;
+        match self.config.fire_mode {
+            FireMode::Toggle => {
+                if input.toggle_auto_shoot {
+                    ship.auto_shoot = !ship.auto_shoot;
+                }
+                if input.fire {
+                    ship.shoot(frame, &mut self.world);
+                }
+            }
+            FireMode::Hold => {
+                if input.fire {
+                    ship.shoot(frame, &mut self.world);
+                }
+            }
+            FireMode::Charge => ship.charge_or_release(input.fire, self.config.charge_frames, frame, &mut self.world),
+        }
+        if input.bomb {
+            self.trigger_bomb(player, frame);
+        }
+        let ship = &mut self.players[player].ship;
+        let (speed_x, speed_y) = (Fixed::from_f64(self.config.move_speed_x), Fixed::from_f64(self.config.move_speed_y));
+        if input.up {
+            ship.move_y(-speed_y, self.dimension.y);
+        }
+        if input.down {
+            ship.move_y(speed_y, self.dimension.y);
+        }
+        if input.left {
+            ship.move_x(-speed_x, self.dimension.x - 2);
+        }
+        if input.right {
+            ship.move_x(speed_x, self.dimension.x - 2);
+        }
+    }
+
+    /// Simulates one frame with the `inputs` of that frame, one per player. Missing inputs count as no input.
+    ///
+    /// Everything fires and steers first, then all entities move at once. Touching the ships is checked next,
+    /// then what left the field is despawned, new enemies arrive and finally the shots deal their damage.
+    pub fn update(&mut self, inputs: &[FrameInput]) {
+        let frame = self.frame;
+        self.frame += 1;
+        for player in &mut self.players {
+            player.update_effects(&self.config);
+            player.scoring.update(frame, &self.config.scoring);
+            player.ship.previous = player.ship.exact;
+        }
+        let active = self.active_players();
+        for &player in &active {
+            let ship_type = self.players[player].ship.ship_type;
+            self.players[player].stats.record_frame(ship_type);
+        }
+        for &player in &active {
+            self.apply_input(player, &inputs.get(player).copied().unwrap_or_default(), frame);
+        }
+        for &player in &active {
+            self.players[player].ship.update(frame, &mut self.world);
+        }
+        self.update_boss(frame, &active);
+        self.update_enemies(frame);
+        self.steer_missiles();
+        self.world.move_entities();
+        self.update_contacts(&active);
+        self.spawn_boss(frame);
+        self.spawn_enemy(frame);
+        self.update_hits(frame);
+        self.update_game_speed(frame);
+    }
+
+    fn award(&mut self, player: usize, source: ScoreSource, points: usize) {
+        self.players[player].award(source, points, &self.config);
+    }
+
+    /// Scores a destroyed enemy together with the bonus of the combo it extends.
+    fn score_kill(&mut self, player: usize, kind: EnemyKind, frame: usize) {
+        self.award(player, ScoreSource::Kills, kind.score(&self.config.scoring));
+        let combo = self.players[player].scoring.register_kill(frame, &self.config.scoring);
+        if combo > 1 {
+            self.award(player, ScoreSource::Combos, (combo - 1) * self.config.scoring.combo_bonus);
+        }
+    }
+
+    fn finish_wave(&mut self) {
+        for player in 0..self.players.len() {
+            if self.players[player].scoring.finish_wave() {
+                self.award(player, ScoreSource::NoDamageWaves, self.config.scoring.no_damage_wave_bonus);
+            }
+        }
+    }
+
+    /// Uses up a stored bomb of `player` to destroy all enemies and enemy shots on the field and damage the boss.
+    /// The enemies' points are awarded in one lump on purpose: clearing the field with a bomb neither extends the combo
+    /// nor counts towards the multiplier, and the destroyed enemies drop no goodies.
+    fn trigger_bomb(&mut self, player: usize, frame: usize) {
+        if self.players[player].bombs == 0 {
+            return;
+        }
+        self.players[player].bombs -= 1;
+        let partial_score: usize = self.world.enemies()
+            .filter_map(Entity::as_enemy)
+            .map(|enemy| enemy.kind.score(&self.config.scoring))
+            .sum();
+        self.award(player, ScoreSource::Kills, partial_score);
+        self.world.retain(|entity| entity.faction != Faction::Enemy);
+        if self.boss.as_mut().is_some_and(|boss| boss.hit(self.config.goodies.bomb_boss_damage)) {
+            self.defeat_boss(Some(player), frame);
+        }
+    }
+
+    fn update_boss(&mut self, frame: usize, active: &[usize]) {
+        let Some(boss_pos) = self.boss.as_ref().map(|boss| boss.pos) else { return; };
+        let target = self.target_ship(boss_pos + Vec2::xy(Boss::size().x / 2, Boss::size().y / 2));
+        let Some(boss) = self.boss.as_mut() else { return; };
+        boss.update(frame, &self.config.boss, self.dimension, target, &mut self.world, &mut self.rng);
+        for &player in active {
+            let player = &mut self.players[player];
+            let mut standard_damage = 0;
+            if player.ship.is_hit_by(&boss.hitbox()) {
+                standard_damage += self.config.damage.boss_contact;
+            }
+            if boss.laser_hitbox(self.dimension).is_some_and(|laser| player.ship.is_hit_by(&laser)) {
+                standard_damage += self.config.damage.laser;
+            }
+            player.damage(standard_damage, 0, DamageCause::Boss);
+        }
+    }
+
+    /// Starts a boss fight once the score or the survived time reaches the next milestone.
+    /// When playing levels, bosses only appear at the start of waves that ask for one.
+    fn spawn_boss(&mut self, frame: usize) {
+        if self.boss.is_some() {
+            return;
+        }
+        let due = match &self.level {
+            Some(level) => level.is_wave_start(frame) && level.current_wave().is_some_and(|wave| wave.boss),
+            None => self.milestone_score() >= self.next_boss_score || frame >= self.next_boss_frame,
+        };
+        if due {
+            self.boss = Some(Boss::new(self.boss_encounters, &self.config.boss, self.dimension, frame));
+            self.boss_encounters += 1;
+        }
+    }
+
+    /// Lets the enemies fire and pick the velocity they move with during the frame.
+    fn update_enemies(&mut self, frame: usize) {
+        let targets: Vec<Vec2> = self.world.enemies().map(|entity| self.target_ship(entity.position.cell)).collect();
+        let mut targets = targets.into_iter();
+        let mut shots = Vec::new();
+        for entity in self.world.iter_mut() {
+            let Role::Enemy(enemy) = &mut entity.role else { continue; };
+            let ship_pos = targets.next().expect("one target per enemy");
+            let context = MovementContext { ship_pos, dimension: self.dimension };
+            enemy.shoot(frame, entity.position.cell, &mut shots, ship_pos + Vec2::x(1), &self.config, &mut self.rng);
+            entity.velocity = enemy.move_self(frame, entity.position.exact, entity.velocity, &context);
+        }
+        self.world.extend(shots);
+    }
+
+    /// Turns the missiles towards the closest ship while they are homing.
+    fn steer_missiles(&mut self) {
+        let weapons = &self.config.weapons;
+        let homing = |entity: &Entity| {
+            entity.as_shot().is_some_and(|shot| shot.shot_type == ShotType::Missile)
+                && entity.lifetime.age < weapons.missile_homing_frames
+        };
+        let targets: Vec<Vec2> = self.world.iter()
+            .filter(|entity| homing(entity))
+            .map(|entity| self.target_ship(entity.position.cell) + Vec2::x(1))
+            .collect();
+        let mut targets = targets.into_iter();
+        for entity in self.world.iter_mut().filter(|entity| homing(entity)) {
+            entity.steer(targets.next().expect("one target per missile"), Fixed::from_f64(weapons.missile_speed));
+        }
+    }
+
+    /// Checks everything that moved against the `active` ships, then despawns what they destroyed or collected
+    /// and what left the field.
+    fn update_contacts(&mut self, active: &[usize]) {
+        let mut removed = vec![false; self.world.len()];
+        let mut candidates: Vec<Vec<usize>> = vec![Vec::new(); self.players.len()];
+        self.grid.rebuild(self.world.iter().map(|entity| entity.sweep().bounds()));
+        for &player in active {
+            self.grid.query(&self.players[player].ship.sweep().bounds(), &mut candidates[player]);
+        }
+        self.collide_enemies(active, &candidates, &mut removed);
+        for &player in active {
+            self.collide_enemy_shots(player, &candidates[player], &mut removed);
+        }
+        for &player in active {
+            self.collect_goodies(player, &candidates[player], &mut removed);
+        }
+        self.world.despawn(&removed, self.dimension);
+    }
+
+    /// Destroys the enemies a ship ran into and the ones that reached the ground. The ship running into an enemy
+    /// is damaged, an enemy reaching the ground damages every ship.
+    fn collide_enemies(&mut self, active: &[usize], candidates: &[Vec<usize>], removed: &mut [bool]) {
+        let mut standard_damage = vec![0; self.players.len()];
+        for &player in active {
+            let ship = &self.players[player].ship;
+            for &index in &candidates[player] {
+                let entity = &self.world[index];
+                if !removed[index] && entity.as_enemy().is_some() && ship.is_swept_by(&entity.sweep()) {
+                    standard_damage[player] += self.config.damage.collision;
+                    removed[index] = true;
+                }
+            }
+        }
+        let mut direct_damage = 0;
+        for (index, entity) in self.world.iter().enumerate() {
+            if !removed[index] && entity.as_enemy().is_some() && entity.position.cell.y > self.dimension.y - BORDER_SIZE {
+                direct_damage += self.config.damage.enemy_reached_ground;
+                removed[index] = true;
+            }
+        }
+        for &player in active {
+            self.players[player].damage(standard_damage[player], direct_damage, DamageCause::Collision);
+        }
+    }
+
+    /// Damages the ship of `player` for the enemy shots that hit it and counts grazes of shots that passed right next
+    /// to it without hitting it.
+    fn collide_enemy_shots(&mut self, player: usize, candidates: &[usize], removed: &mut [bool]) {
+        let ship = &self.players[player].ship;
+        let mut standard_damage = 0;
+        let mut grazes = 0;
+        for &index in candidates {
+            let entity = &self.world[index];
+            let Some(shot) = entity.as_shot().filter(|_| entity.faction == Faction::Enemy) else { continue; };
+            if !removed[index] && ship.is_swept_by(&entity.sweep()) {
+                standard_damage += shot.shot_type.damage(&self.config.damage);
+                removed[index] = true;
+            }
+        }
+        let graze_area = ship.hitbox().grown(1);
+        let mut grazing = Vec::new();
+        self.grid.query(&graze_area, &mut grazing);
+        for index in grazing {
+            let entity = &mut self.world[index];
+            let hitbox = entity.hitbox();
+            let Role::Shot(shot) = &mut entity.role else { continue; };
+            if entity.faction == Faction::Enemy && !removed[index] && !shot.grazed && graze_area.intersects(&hitbox) {
+                shot.grazing.get_or_insert(player);
+            }
+        }
+        for (index, entity) in self.world.iter_mut().enumerate() {
+            let passed = !graze_area.intersects(&entity.hitbox())
+                || !entity.lifetime.bounds.contains(entity.position.cell, self.dimension);
+            let Role::Shot(shot) = &mut entity.role else { continue; };
+            if shot.grazing == Some(player) && !removed[index] && passed {
+                shot.grazing = None;
+                shot.grazed = true;
+                grazes += 1;
+            }
+        }
+        self.players[player].damage(standard_damage, 0, DamageCause::Shot);
+        self.award(player, ScoreSource::Grazes, grazes * self.config.scoring.graze_bonus);
+    }
+
+    /// Gives the goodies the ship of `player` touched to it.
+    fn collect_goodies(&mut self, player: usize, candidates: &[usize], removed: &mut [bool]) {
+        let mut collected = 0;
+        for &index in candidates {
+            let entity = &self.world[index];
+            let Some(&goodie_type) = entity.as_goodie() else { continue; };
+            if removed[index] || !self.players[player].ship.is_swept_by(&entity.sweep()) {
+                continue;
+            }
+            collected += 1;
+            removed[index] = true;
+            self.players[player].collect(goodie_type, &self.config);
+        }
+        self.award(player, ScoreSource::Goodies, collected * self.config.scoring.goodie_bonus);
+    }
+
+    /// Spawns regular enemies, which is paused while a boss fight is going on.
+    /// When playing levels, the enemies of the current wave are spawned instead.
+    fn spawn_enemy(&mut self, frame: usize) {
+        if let Some(level) = self.level.as_mut() {
+            self.world.extend(level.spawn(frame, &self.config));
+            if level.check_cleared(frame, self.world.enemies().next().is_none() && self.boss.is_none()) {
+                self.finish_wave();
+            }
+            return;
+        }
+        if self.boss.is_some() {
+            self.last_spawn = frame;
+            return;
+        }
+        if self.last_spawn + self.spawn_interval < frame {
+            self.last_spawn = frame;
+            let x_pos = self.rng.gen_range(5..self.dimension.x - 5);
+            let kind: EnemyKind = self.rng.gen();
+            let pattern: MovementPattern = self.rng.gen();
+            let mut enemy = Enemy::new(
+                kind,
+                Vec2::xy(x_pos, BORDER_SIZE),
+                Movement::single(pattern),
+                self.config.fps_limit as usize,
+            );
+            if self.rng.gen_bool(self.config.weapons.random_weapon_probability) {
+                enemy.weapon = self.rng.gen::<EnemyWeapon>();
+            }
+            self.world.spawn(Entity::enemy(enemy));
+        }
+    }
+
+    /// Lets the shots damage what they hit. Destroyed enemies drop a goodie, destroyed missiles count as kills.
+    /// The points go to the player who fired the destroying shot.
+    fn update_hits(&mut self, frame: usize) {
+        let hits = self.world.resolve_hits(self.boss.as_mut(), &self.config.damage, &mut self.grid);
+        let goodie_speed = Fixed::from_f64(self.config.goodies.fall_speed);
+        let mut killed = Vec::new();
+        let mut missiles_destroyed = vec![0; self.players.len()];
+        for (entity, owner) in hits.destroyed {
+            match &entity.role {
+                Role::Enemy(enemy) => {
+                    killed.push((owner, enemy.kind));
+                    let goodie_type = self.rng.sample(&self.config.goodies);
+                    self.drop_goodie(entity.position.cell, goodie_type, goodie_speed);
+                }
+                Role::Shot(_) => {
+                    if let Some(owner) = owner {
+                        missiles_destroyed[owner] += 1;
+                    }
+                }
+                Role::Goodie(_) => {}
+            }
+        }
+        for (player, missiles) in missiles_destroyed.into_iter().enumerate() {
+            self.award(player, ScoreSource::Kills, missiles * self.config.scoring.missile);
+        }
+        for (owner, kind) in killed {
+            if let Some(player) = owner {
+                self.score_kill(player, kind, frame);
+            }
+        }
+        if hits.boss_destroyed {
+            self.defeat_boss(hits.boss_destroyed_by, frame);
+        }
+    }
+
+    /// Ends the boss fight, the boss points go to `player` who destroyed it.
+    fn defeat_boss(&mut self, player: Option<usize>, frame: usize) {
+        let Some(boss) = self.boss.take() else { return; };
+        if let Some(player) = player {
+            self.award(player, ScoreSource::Bosses, self.config.boss.score * self.boss_encounters);
+        }
+        let hitbox = boss.hitbox();
+        let speed = Fixed::from_f64(self.config.goodies.fall_speed);
+        for x in [hitbox.pos.x, hitbox.pos.x + hitbox.size.x / 2, hitbox.pos.x + hitbox.size.x - 1] {
+            let goodie_type = self.rng.sample(&self.config.goodies);
+            self.drop_goodie(Vec2::xy(x, hitbox.pos.y + hitbox.size.y - 1), goodie_type, speed);
+        }
+        self.next_boss_score = self.milestone_score() + self.config.boss.score_interval;
+        self.next_boss_frame = frame + self.config.boss.frame_interval;
+    }
+
+    fn drop_goodie(&mut self, pos: Vec2, goodie_type: GoodieType, speed: Fixed) {
+        *self.goodies_dropped.entry(goodie_type.name()).or_default() += 1;
+        self.world.spawn(Entity::goodie(pos, goodie_type, speed));
+    }
+
+    /// Spawns enemies faster after a while. Without levels, the time between two speed-ups counts as a wave.
+    fn update_game_speed(&mut self, frame: usize) {
+        let spawn = &self.config.spawn;
+        if self.last_spawn_speedup + spawn.speedup_after_frames < frame {
+            self.spawn_interval = max(spawn.min_interval, self.spawn_interval.saturating_sub(spawn.interval_decrease));
+            self.last_spawn_speedup = frame;
+            if self.level.is_none() {
+                self.finish_wave();
+            }
+        }
+    }
+}
+
+/// The 64 bit FNV-1a hash. Unlike the hasher of the standard library its output is fixed,
+/// so checksums stay comparable between builds. Integers are hashed little endian and `usize` as `u64`,
+/// so they are comparable between platforms as well.
+struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Fnv1a(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for Fnv1a {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ *byte as u64).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+}
@@ -0,0 +1,75 @@
+use rand::distributions::Distribution;
+use rand::Rng;
+use crate::config::{GoodieConfig, Glyphs};
+use crate::effect::EffectKind;
+use crate::ship::ShipType;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GoodieType {
+    RepairKit(usize),
+    ShieldBoost(usize),
+    ShipUpgrade(ShipType),
+    PowerUp(EffectKind),
+    Bomb,
+}
+
+/// Drops goodies according to the configured weights.
+impl Distribution<GoodieType> for GoodieConfig {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> GoodieType {
+        let weights = &self.weights;
+        let choices = [
+            (weights.repair_kit, GoodieType::RepairKit(self.repair_kit_health)),
+            (weights.shield_boost, GoodieType::ShieldBoost(self.shield_boost_shield)),
+            (weights.ship_upgrade, GoodieType::ShipUpgrade(ShipType::Basic)),
+            (weights.rapid_fire, GoodieType::PowerUp(EffectKind::RapidFire)),
+            (weights.spread, GoodieType::PowerUp(EffectKind::Spread)),
+            (weights.score_multiplier, GoodieType::PowerUp(EffectKind::ScoreMultiplier)),
+            (weights.invincibility, GoodieType::PowerUp(EffectKind::Invincibility)),
+            (weights.bomb, GoodieType::Bomb),
+        ];
+        let mut remaining = rng.gen_range(0..weights.total());
+        for (weight, goodie_type) in choices {
+            if remaining < weight {
+                return match goodie_type {
+                    GoodieType::ShipUpgrade(_) => GoodieType::ShipUpgrade(rng.gen()),
+                    goodie_type => goodie_type,
+                };
+            }
+            remaining -= weight;
+        }
+        unreachable!("the sampled value is below the total weight")
+    }
+}
+
+impl GoodieType {
+    /// The names of all goodies, like their weights in the config. Ship upgrades of any type share a name.
+    pub const NAMES: [&'static str; 8] =
+        ["repair_kit", "shield_boost", "ship_upgrade", "rapid_fire", "spread", "score_multiplier", "invincibility", "bomb"];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GoodieType::RepairKit(_) => "repair_kit",
+            GoodieType::ShieldBoost(_) => "shield_boost",
+            GoodieType::ShipUpgrade(_) => "ship_upgrade",
+            GoodieType::PowerUp(EffectKind::RapidFire) => "rapid_fire",
+            GoodieType::PowerUp(EffectKind::Spread) => "spread",
+            GoodieType::PowerUp(EffectKind::ScoreMultiplier) => "score_multiplier",
+            GoodieType::PowerUp(EffectKind::Invincibility) => "invincibility",
+            GoodieType::Bomb => "bomb",
+        }
+    }
+
+    pub fn character(&self, glyphs: &Glyphs) -> char {
+        match self {
+            GoodieType::RepairKit(_) => glyphs.health,
+            GoodieType::ShieldBoost(_) => glyphs.shield,
+            GoodieType::ShipUpgrade(ship_type) => match ship_type {
+                ShipType::Basic => glyphs.upgrade_ship_basic,
+                ShipType::DiagonalShot => glyphs.upgrade_ship_diagonal,
+                ShipType::StrongShot => glyphs.upgrade_ship_strong,
+            },
+            GoodieType::PowerUp(effect) => effect.character(glyphs),
+            GoodieType::Bomb => glyphs.bomb,
+        }
+    }
+}
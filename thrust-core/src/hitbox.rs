@@ -0,0 +1,119 @@
+use crate::fixed::{Fixed, FixedVec2};
+use crate::spatial::Vec2;
+
+/// An axis aligned rectangle of cells occupied by an object, `pos` being its top left cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hitbox {
+    pub pos: Vec2,
+    pub size: Vec2,
+}
+
+impl Hitbox {
+    pub fn new(pos: Vec2, size: Vec2) -> Self {
+        Self { pos, size }
+    }
+
+    /// A hitbox covering the single cell at `pos`.
+    pub fn cell(pos: Vec2) -> Self {
+        Self { pos, size: Vec2::xy(1, 1) }
+    }
+
+    /// The hitbox enlarged by `margin` cells in every direction.
+    pub fn grown(&self, margin: i32) -> Self {
+        Self { pos: self.pos - Vec2::xy(margin, margin), size: self.size + Vec2::xy(2 * margin, 2 * margin) }
+    }
+
+    pub fn contains(&self, point: Vec2) -> bool {
+        self.pos.x <= point.x && point.x < self.pos.x + self.size.x
+            && self.pos.y <= point.y && point.y < self.pos.y + self.size.y
+    }
+
+    pub fn intersects(&self, other: &Hitbox) -> bool {
+        self.pos.x < other.pos.x + other.size.x && other.pos.x < self.pos.x + self.size.x
+            && self.pos.y < other.pos.y + other.size.y && other.pos.y < self.pos.y + self.size.y
+    }
+}
+
+/// The way an object of `size` cells moved during the last frame, from `from` to `to`.
+///
+/// For collisions an object at an exact position `pos` covers the area from `pos - 0.5` to `pos - 0.5 + size`,
+/// which for whole cells is exactly its [`Hitbox`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sweep {
+    pub from: FixedVec2,
+    pub to: FixedVec2,
+    pub size: Vec2,
+}
+
+/// A fraction `numerator / denominator` with a positive denominator, the time within a frame.
+#[derive(Clone, Copy)]
+struct Time(i64, i64);
+
+impl Time {
+    fn less(self, other: Time) -> bool {
+        self.0 * other.1 < other.0 * self.1
+    }
+}
+
+impl Sweep {
+    pub fn new(from: FixedVec2, to: FixedVec2, size: Vec2) -> Self {
+        Self { from, to, size }
+    }
+
+    /// An object that did not move, e.g. the boss between two of its steps.
+    pub fn stationary(hitbox: &Hitbox) -> Self {
+        let pos = FixedVec2::from_cell(hitbox.pos);
+        Self { from: pos, to: pos, size: hitbox.size }
+    }
+
+    /// Whether both objects overlapped at any moment of the frame, assuming both moved in a straight line.
+    ///
+    /// The segment this object moved along relative to `other` is tested against the area
+    /// in which the two would overlap, so objects passing through or swapping places are found as well.
+    pub fn hits(&self, other: &Sweep) -> bool {
+        let start = self.from - other.from;
+        let delta = (self.to - self.from) - (other.to - other.from);
+        let axes = [
+            (start.x, delta.x, self.size.x, other.size.x),
+            (start.y, delta.y, self.size.y, other.size.y),
+        ];
+        // the open interval of times in which the objects overlap on every axis checked so far,
+        // starting with one that covers the whole frame
+        let (mut enter, mut exit) = (Time(-1, 1), Time(2, 1));
+        for (start, delta, size, other_size) in axes {
+            let (start, delta) = (start.raw() as i64, delta.raw() as i64);
+            let (low, high) = (-Fixed::from_int(size).raw() as i64, Fixed::from_int(other_size).raw() as i64);
+            if delta == 0 {
+                if start <= low || start >= high {
+                    return false;
+                }
+                continue;
+            }
+            let (axis_enter, axis_exit) = if delta > 0 {
+                (Time(low - start, delta), Time(high - start, delta))
+            } else {
+                (Time(start - high, -delta), Time(start - low, -delta))
+            };
+            if enter.less(axis_enter) {
+                enter = axis_enter;
+            }
+            if axis_exit.less(exit) {
+                exit = axis_exit;
+            }
+        }
+        enter.less(exit) && enter.less(Time(1, 1)) && Time(0, 1).less(exit)
+    }
+
+    /// The cells the object might have touched along its way, with a margin for the rounding of its positions.
+    pub fn bounds(&self) -> Hitbox {
+        let (from, to) = (self.from.cell(), self.to.cell());
+        let first = Vec2::xy(from.x.min(to.x), from.y.min(to.y));
+        let last = Vec2::xy(from.x.max(to.x), from.y.max(to.y));
+        Hitbox::new(first, last - first + self.size).grown(1)
+    }
+
+    /// Whether the object touched the non-moving `other` anywhere along its way.
+    pub fn intersects(&self, other: &Hitbox) -> bool {
+        self.hits(&Sweep::stationary(other))
+    }
+}
@@ -0,0 +1,50 @@
+/// Everything the player can do to the game within a single frame.
+///
+/// Frontends translate their key events into a `FrameInput` and pass it to
+/// [`GameState::update`](crate::game_state::GameState::update). Since it is the only way to influence a
+/// running game, recording one `FrameInput` per frame is enough to replay a whole run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FrameInput {
+    pub up: bool,
+    pub down: bool,
+    pub left: bool,
+    pub right: bool,
+    pub toggle_auto_shoot: bool,
+    /// Set once per key press in [`FireMode::Toggle`](crate::ship::FireMode::Toggle), otherwise while fire is held.
+    pub fire: bool,
+    pub bomb: bool,
+}
+
+const BIT_UP: u8 = 1;
+const BIT_DOWN: u8 = 1 << 1;
+const BIT_LEFT: u8 = 1 << 2;
+const BIT_RIGHT: u8 = 1 << 3;
+const BIT_TOGGLE_AUTO_SHOOT: u8 = 1 << 4;
+const BIT_FIRE: u8 = 1 << 5;
+const BIT_BOMB: u8 = 1 << 6;
+
+impl FrameInput {
+    pub fn to_bits(self) -> u8 {
+        let mut bits = 0;
+        if self.up { bits |= BIT_UP; }
+        if self.down { bits |= BIT_DOWN; }
+        if self.left { bits |= BIT_LEFT; }
+        if self.right { bits |= BIT_RIGHT; }
+        if self.toggle_auto_shoot { bits |= BIT_TOGGLE_AUTO_SHOOT; }
+        if self.fire { bits |= BIT_FIRE; }
+        if self.bomb { bits |= BIT_BOMB; }
+        bits
+    }
+
+    pub fn from_bits(bits: u8) -> Self {
+        Self {
+            up: bits & BIT_UP != 0,
+            down: bits & BIT_DOWN != 0,
+            left: bits & BIT_LEFT != 0,
+            right: bits & BIT_RIGHT != 0,
+            toggle_auto_shoot: bits & BIT_TOGGLE_AUTO_SHOOT != 0,
+            fire: bits & BIT_FIRE != 0,
+            bomb: bits & BIT_BOMB != 0,
+        }
+    }
+}
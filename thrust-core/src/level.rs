@@ -0,0 +1,291 @@
+use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::{error, fs, io};
+use serde::Deserialize;
+use crate::config::GameConfig;
+use crate::constant::BORDER_SIZE;
+use crate::enemy::{Enemy, EnemyKind, EnemyWeapon};
+use crate::movement::{Movement, MovementPattern, PatternPhase};
+use crate::spatial::Vec2;
+use crate::world::Entity;
+
+/// A stage made of waves that are played one after the other, loaded from a TOML file.
+///
+/// ```toml
+/// name = "First contact"
+///
+/// [[waves]]
+/// clear = "all_destroyed"
+/// spawns = [
+///     { kind = "drone", formation = { type = "line", x = 10, count = 5, spacing = 8 } },
+///     { kind = "tank", delay = 60, formation = { type = "single", x = 30 }, move_interval = 15, weapon = "burst" },
+///     { kind = "fighter", delay = 90, formation = { type = "single", x = 20 }, patterns = [
+///         { pattern = { type = "strafe", speed = 2 }, moves = 12 },
+///         { pattern = { type = "dive" } },
+///     ] },
+/// ]
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Level {
+    pub name: String,
+    pub waves: Vec<Wave>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Wave {
+    #[serde(default)]
+    pub spawns: Vec<SpawnGroup>,
+    /// Whether a boss joins the wave right at its start.
+    #[serde(default)]
+    pub boss: bool,
+    #[serde(default)]
+    pub clear: ClearCondition,
+}
+
+/// When a wave counts as cleared, so the next one starts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClearCondition {
+    /// All enemies of the wave are spawned and none (including a boss) is left on the field.
+    #[default]
+    AllDestroyed,
+    /// The given number of frames passed since the wave started.
+    AfterFrames(usize),
+}
+
+/// A group of enemies of the same kind, spawned together in a formation.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SpawnGroup {
+    pub kind: EnemyKind,
+    pub formation: Formation,
+    /// Frames after the start of the wave until the group is spawned.
+    #[serde(default)]
+    pub delay: usize,
+    /// The step of a straight movement, used if no `patterns` are given.
+    #[serde(default = "default_movement")]
+    pub movement: Vec2,
+    /// The movement patterns the enemies switch through, one after the other.
+    #[serde(default)]
+    pub patterns: Vec<PatternPhase>,
+    #[serde(default = "default_move_interval")]
+    pub move_interval: usize,
+    /// Frames between two shot attempts, defaults to one second.
+    #[serde(default)]
+    pub shot_interval: Option<usize>,
+    /// Defaults to the usual weapon of the enemy kind.
+    #[serde(default)]
+    pub weapon: Option<EnemyWeapon>,
+}
+
+fn default_movement() -> Vec2 {
+    Vec2::y(1)
+}
+
+fn default_move_interval() -> usize {
+    10
+}
+
+impl SpawnGroup {
+    pub fn movement(&self) -> Movement {
+        if self.patterns.is_empty() {
+            Movement::single(MovementPattern::Straight { step: self.movement })
+        } else {
+            Movement::new(self.patterns.clone())
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum Formation {
+    Single { x: i32 },
+    /// `count` enemies side by side, starting at `x`.
+    Line { x: i32, count: usize, spacing: i32 },
+    /// `count` enemies on top of each other at column `x`.
+    Column { x: i32, count: usize, spacing: i32 },
+    /// `count` enemies in a V shape starting at `x`, its tip pointing downwards.
+    Wedge { x: i32, count: usize, spacing: i32 },
+}
+
+impl Formation {
+    /// The spawn positions of the formation, with `top` being the topmost row.
+    pub fn positions(&self, top: i32) -> Vec<Vec2> {
+        match *self {
+            Formation::Single { x } => vec![Vec2::xy(x, top)],
+            Formation::Line { x, count, spacing } => {
+                (0..count as i32).map(|i| Vec2::xy(x + i * spacing, top)).collect()
+            }
+            Formation::Column { x, count, spacing } => {
+                (0..count as i32).map(|i| Vec2::xy(x, top + i * spacing)).collect()
+            }
+            Formation::Wedge { x, count, spacing } => {
+                let tip = (count as i32 - 1) / 2;
+                (0..count as i32).map(|i| Vec2::xy(x + i * spacing, top + tip - (i - tip).abs())).collect()
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum LevelError {
+    Io(PathBuf, io::Error),
+    Parse(PathBuf, toml::de::Error),
+    Invalid(String),
+}
+
+impl Display for LevelError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LevelError::Io(path, err) => write!(f, "could not read level {}: {}", path.display(), err),
+            LevelError::Parse(path, err) => write!(f, "could not parse level {}: {}", path.display(), err),
+            LevelError::Invalid(reason) => write!(f, "invalid level: {}", reason),
+        }
+    }
+}
+
+impl error::Error for LevelError {}
+
+impl Level {
+    pub fn load(path: &Path) -> Result<Level, LevelError> {
+        let content = fs::read_to_string(path).map_err(|err| LevelError::Io(path.to_path_buf(), err))?;
+        let mut level: Level = toml::from_str(&content).map_err(|err| LevelError::Parse(path.to_path_buf(), err))?;
+        for wave in &mut level.waves {
+            wave.spawns.sort_by_key(|group| group.delay);
+        }
+        Ok(level)
+    }
+
+    /// Loads a single level file, or all `.toml` files of a directory in the order of their file names.
+    pub fn load_all(path: &Path) -> Result<Vec<Level>, LevelError> {
+        if !path.is_dir() {
+            return Ok(vec![Level::load(path)?]);
+        }
+        let mut paths = fs::read_dir(path)
+            .and_then(|entries| entries.map(|entry| entry.map(|entry| entry.path())).collect::<Result<Vec<_>, _>>())
+            .map_err(|err| LevelError::Io(path.to_path_buf(), err))?;
+        paths.retain(|path| path.extension().is_some_and(|ext| ext == "toml"));
+        paths.sort();
+        if paths.is_empty() {
+            return Err(LevelError::Invalid(format!("no level files in {}", path.display())));
+        }
+        paths.iter().map(|path| Level::load(path)).collect()
+    }
+
+    /// Checks that the level can be played on a field of the given `dimension`.
+    pub fn validate(&self, dimension: Vec2) -> Result<(), LevelError> {
+        let invalid = |wave: usize, reason: String| Err(LevelError::Invalid(format!("{}, wave {}: {}", self.name, wave + 1, reason)));
+        if self.waves.is_empty() {
+            return Err(LevelError::Invalid(format!("{}: a level needs at least one wave", self.name)));
+        }
+        for (i, wave) in self.waves.iter().enumerate() {
+            if wave.spawns.is_empty() && !wave.boss {
+                return invalid(i, "a wave needs at least one spawn or a boss".to_string());
+            }
+            for group in &wave.spawns {
+                if group.move_interval == 0 {
+                    return invalid(i, "move_interval must be at least 1".to_string());
+                }
+                if group.shot_interval == Some(0) {
+                    return invalid(i, "shot_interval must be at least 1".to_string());
+                }
+                if let Some(Err(reason)) = group.patterns.iter().map(|phase| phase.pattern.validate()).find(Result::is_err) {
+                    return invalid(i, reason);
+                }
+                let positions = group.formation.positions(BORDER_SIZE);
+                if positions.is_empty() {
+                    return invalid(i, "a formation needs at least one enemy".to_string());
+                }
+                if let Some(pos) = positions.iter().find(|pos| pos.x <= 0 || pos.x >= dimension.x || pos.y >= dimension.y) {
+                    return invalid(i, format!("spawn position ({}, {}) is outside of the field", pos.x, pos.y));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Tracks the progress through a sequence of levels and spawns the enemies of the current wave.
+pub struct LevelProgress {
+    pub levels: Vec<Level>,
+    pub level_index: usize,
+    pub wave_index: usize,
+    wave_start_frame: usize,
+    spawned_groups: usize,
+}
+
+/// Only the progress is hashed, the levels themselves don't change during a game.
+impl Hash for LevelProgress {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (self.level_index, self.wave_index, self.wave_start_frame, self.spawned_groups).hash(state);
+    }
+}
+
+impl LevelProgress {
+    pub fn new(levels: Vec<Level>, frame: usize) -> Self {
+        Self { levels, level_index: 0, wave_index: 0, wave_start_frame: frame, spawned_groups: 0 }
+    }
+
+    pub fn current_level(&self) -> Option<&Level> {
+        self.levels.get(self.level_index)
+    }
+
+    pub fn current_wave(&self) -> Option<&Wave> {
+        self.current_level().and_then(|level| level.waves.get(self.wave_index))
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.level_index >= self.levels.len()
+    }
+
+    /// Whether the current wave just started in `frame` and thus wants its boss spawned.
+    pub fn is_wave_start(&self, frame: usize) -> bool {
+        self.wave_start_frame == frame
+    }
+
+    /// Returns the enemies of the current wave that are due in `frame`.
+    pub fn spawn(&mut self, frame: usize, config: &GameConfig) -> Vec<Entity> {
+        let mut enemies = Vec::new();
+        let Some(wave) = self.current_wave() else { return enemies; };
+        let due_groups = wave.spawns[self.spawned_groups..].iter()
+            .take_while(|group| self.wave_start_frame + group.delay <= frame)
+            .count();
+        for group in &wave.spawns[self.spawned_groups..self.spawned_groups + due_groups] {
+            for pos in group.formation.positions(BORDER_SIZE) {
+                let shot_interval = group.shot_interval.unwrap_or(config.fps_limit as usize);
+                let mut enemy = Enemy::new(group.kind, pos, group.movement(), shot_interval);
+                enemy.move_interval = group.move_interval;
+                enemy.weapon = group.weapon.unwrap_or(enemy.weapon);
+                enemy.last_movement = frame;
+                enemy.last_shot_frame = frame;
+                enemies.push(Entity::enemy(enemy));
+            }
+        }
+        self.spawned_groups += due_groups;
+        enemies
+    }
+
+    /// Advances to the next wave (and level) once the current one is cleared and tells whether it was.
+    /// `field_empty` tells whether there are no enemies (and no boss) left on the field.
+    pub fn check_cleared(&mut self, frame: usize, field_empty: bool) -> bool {
+        let Some(wave) = self.current_wave() else { return false; };
+        let cleared = match wave.clear {
+            ClearCondition::AllDestroyed => self.spawned_groups == wave.spawns.len() && field_empty,
+            ClearCondition::AfterFrames(frames) => self.wave_start_frame + frames <= frame,
+        };
+        if !cleared {
+            return false;
+        }
+        self.wave_index += 1;
+        if self.wave_index >= self.levels[self.level_index].waves.len() {
+            self.wave_index = 0;
+            self.level_index += 1;
+        }
+        self.wave_start_frame = frame + 1;
+        self.spawned_groups = 0;
+        true
+    }
+}
@@ -0,0 +1,40 @@
+//! Terminal independent simulation of the thrust game.
+//!
+//! Everything needed to run a game (the ship, the [`world::World`] of enemies, shots and goodies and the [`game_state::GameState`]
+//! tying them together) lives here. Rendering and input handling are left to a frontend.
+
+pub mod action;
+pub mod agent;
+pub mod autopilot;
+pub mod boss;
+pub mod config;
+pub mod constant;
+pub mod effect;
+pub mod enemy;
+pub mod fixed;
+pub mod game_state;
+pub mod goodie;
+pub mod hitbox;
+pub mod input;
+pub mod level;
+pub mod movement;
+pub mod net;
+pub mod player;
+pub mod replay;
+pub mod score;
+pub mod ship;
+pub mod snapshot;
+pub mod shot;
+pub mod sim;
+pub mod spatial;
+pub mod spectate;
+pub mod stats;
+pub mod world;
+
+/// The version of `thrust-core`.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The version of the simulation. It has to be bumped by every change that lets the same seed, config and inputs
+/// play a different game, e.g. by drawing a random number more or less. Replays only play back on the same simulation version,
+/// and networked games are only joined from the same one.
+pub const SIMULATION_VERSION: u32 = 4;
@@ -0,0 +1,192 @@
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+use serde::Deserialize;
+use crate::spatial::Vec2;
+
+/// `sin(2π * i / 16)` in thousandths, so patterns stay deterministic on every platform.
+const SINE_TABLE: [i32; 16] = [0, 383, 707, 924, 1000, 924, 707, 383, 0, -383, -707, -924, -1000, -924, -707, -383];
+
+/// `amplitude * sin(2π * t / period)`, interpolated between the entries of [`SINE_TABLE`].
+fn sine(amplitude: i32, t: usize, period: usize) -> i32 {
+    let position = (t % period) * SINE_TABLE.len() * 1000 / period;
+    let (index, fraction) = (position / 1000, (position % 1000) as i32);
+    let (from, to) = (SINE_TABLE[index], SINE_TABLE[(index + 1) % SINE_TABLE.len()]);
+    let value = from + (to - from) * fraction / 1000;
+    (amplitude * value + 500 * value.signum()) / 1000
+}
+
+/// How an enemy moves on each of its move ticks.
+///
+/// In level files a pattern is a table tagged with its `type`, e.g. `{ type = "sine_wave", amplitude = 4, period = 16 }`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum MovementPattern {
+    /// Moves by `step` on every tick.
+    Straight { step: Vec2 },
+    /// Falls one row per tick while swinging sideways around its starting column.
+    SineWave { amplitude: i32, period: usize },
+    /// Falls one row per tick while moving diagonally, turning around after `width` ticks.
+    ZigZag { width: usize },
+    /// Falls one row per tick while steering one column towards the ship.
+    Dive,
+    /// Flies sideways by `speed` columns per tick, dropping one row every `descent` ticks and whenever it turns at a border.
+    Strafe {
+        speed: i32,
+        #[serde(default = "default_strafe_descent")]
+        descent: usize,
+    },
+    /// Circles around a center that sinks one row every quarter turn.
+    Circle { radius: i32, period: usize },
+    /// Flies through `waypoints`, relative to where the pattern started, one cell per tick. Falls down afterwards.
+    /// Waypoints beyond the columns enemies can reach are moved to the nearest reachable one.
+    Path { waypoints: Vec<Vec2> },
+}
+
+impl MovementPattern {
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            MovementPattern::SineWave { period, .. } | MovementPattern::Circle { period, .. } if *period < 4 => {
+                Err("the period of a movement pattern must be at least 4".to_string())
+            }
+            MovementPattern::ZigZag { width: 0 } => Err("the width of a zig_zag pattern must be at least 1".to_string()),
+            MovementPattern::Strafe { speed: 0, .. } => Err("the speed of a strafe pattern must not be 0".to_string()),
+            MovementPattern::Strafe { descent: 0, .. } => Err("the descent of a strafe pattern must be at least 1".to_string()),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn default_strafe_descent() -> usize {
+    4
+}
+
+impl Default for MovementPattern {
+    fn default() -> Self {
+        MovementPattern::Straight { step: Vec2::y(1) }
+    }
+}
+
+/// The patterns of randomly spawned enemies: mostly straight, sometimes one of the livelier ones.
+impl Distribution<MovementPattern> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> MovementPattern {
+        match rng.gen_range(0..=9) {
+            0..=4 => MovementPattern::default(),
+            5 => MovementPattern::SineWave { amplitude: rng.gen_range(2..=5), period: 16 },
+            6 => MovementPattern::ZigZag { width: rng.gen_range(3..=6) },
+            7 => MovementPattern::Strafe { speed: if rng.gen() { 1 } else { -1 }, descent: default_strafe_descent() },
+            8 => MovementPattern::Circle { radius: 3, period: 16 },
+            _ => MovementPattern::Dive,
+        }
+    }
+}
+
+/// One pattern of a [`Movement`], followed for `moves` ticks. The last pattern is kept until the enemy leaves the field.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PatternPhase {
+    pub pattern: MovementPattern,
+    #[serde(default)]
+    pub moves: Option<usize>,
+}
+
+/// What a pattern needs to know about the game to decide on the next position.
+pub struct MovementContext {
+    pub ship_pos: Vec2,
+    pub dimension: Vec2,
+}
+
+impl MovementContext {
+    /// Moves `pos` into the columns enemies are kept within.
+    pub fn clamp(&self, pos: Vec2) -> Vec2 {
+        Vec2::xy(pos.x.clamp(1, self.dimension.x - 1), pos.y)
+    }
+}
+
+/// A sequence of movement patterns an enemy switches through over its lifetime.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Movement {
+    phases: Vec<PatternPhase>,
+    phase: usize,
+    /// Ticks spent in the current phase.
+    ticks: usize,
+    /// Where the current phase started.
+    origin: Vec2,
+    direction: i32,
+    /// The next waypoint of [`MovementPattern::Path`].
+    waypoint: usize,
+}
+
+impl Movement {
+    pub fn new(phases: Vec<PatternPhase>) -> Self {
+        Self { phases, phase: 0, ticks: 0, origin: Vec2::zero(), direction: 1, waypoint: 0 }
+    }
+
+    pub fn single(pattern: MovementPattern) -> Self {
+        Movement::new(vec![PatternPhase { pattern, moves: None }])
+    }
+
+    pub fn pattern(&self) -> Option<&MovementPattern> {
+        self.phases.get(self.phase).map(|phase| &phase.pattern)
+    }
+
+    /// Moves `pos` by one tick of the current pattern and switches to the next pattern once its moves are used up.
+    pub fn step(&mut self, pos: Vec2, context: &MovementContext) -> Vec2 {
+        let Some(phase) = self.phases.get(self.phase) else { return pos + Vec2::y(1); };
+        let is_last = self.phase + 1 == self.phases.len();
+        if self.ticks == 0 {
+            self.origin = pos;
+            self.waypoint = 0;
+            self.direction = match phase.pattern {
+                MovementPattern::Strafe { speed, .. } => speed.signum(),
+                _ => 1,
+            };
+        }
+        self.ticks += 1;
+        let t = self.ticks;
+        let next = match &phase.pattern {
+            MovementPattern::Straight { step } => pos + *step,
+            MovementPattern::SineWave { amplitude, period } => {
+                Vec2::xy(self.origin.x + sine(*amplitude, t, *period), pos.y + 1)
+            }
+            MovementPattern::ZigZag { width } => {
+                if t > 1 && (t - 1).is_multiple_of(*width) {
+                    self.direction = -self.direction;
+                }
+                pos + Vec2::xy(self.direction, 1)
+            }
+            MovementPattern::Dive => pos + Vec2::xy((context.ship_pos.x + 1 - pos.x).signum(), 1),
+            MovementPattern::Strafe { speed, descent } => {
+                let x = pos.x + self.direction * speed.abs();
+                if x <= 1 || x >= context.dimension.x - 2 {
+                    self.direction = -self.direction;
+                    pos + Vec2::y(1)
+                } else {
+                    Vec2::xy(x, pos.y + t.is_multiple_of(*descent) as i32)
+                }
+            }
+            MovementPattern::Circle { radius, period } => {
+                let center = self.origin + Vec2::xy(-radius, (t * 4 / period) as i32);
+                let quarter = *period / 4;
+                // terminal cells are about twice as high as wide, so the circle is squashed vertically
+                center + Vec2::xy(sine(*radius, t + quarter, *period), sine(*radius, t, *period) / 2)
+            }
+            MovementPattern::Path { waypoints } => {
+                let target = |waypoint: &Vec2| context.clamp(self.origin + *waypoint);
+                while waypoints.get(self.waypoint).is_some_and(|waypoint| target(waypoint) == pos) {
+                    self.waypoint += 1;
+                }
+                match waypoints.get(self.waypoint).map(target) {
+                    Some(target) => {
+                        pos + Vec2::xy((target.x - pos.x).signum(), (target.y - pos.y).signum())
+                    }
+                    None => pos + Vec2::y(1),
+                }
+            }
+        };
+        if !is_last && phase.moves.is_some_and(|moves| self.ticks >= moves) {
+            self.phase += 1;
+            self.ticks = 0;
+        }
+        next
+    }
+}
@@ -0,0 +1,426 @@
+use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
+use std::io::{Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::time::{Duration, Instant};
+use std::{error, io};
+use crate::config::GameConfig;
+use crate::constant::{NET_HANDSHAKE_TIMEOUT_MILLIS, NET_LEAVE_TIMEOUT_MILLIS, NET_TIMEOUT_MILLIS};
+use crate::game_state::GameState;
+use crate::input::FrameInput;
+use crate::SIMULATION_VERSION;
+
+const TAG_JOIN: u8 = 0;
+const TAG_START: u8 = 1;
+const TAG_REJECT: u8 = 2;
+const TAG_INPUT: u8 = 3;
+const TAG_QUIT: u8 = 4;
+
+/// The version of the [`Message`]s, it has to be bumped whenever their encoding changes.
+pub const PROTOCOL_VERSION: u16 = 3;
+
+/// The player index of the hosting side, the joining side flies the other ship.
+pub const HOST_PLAYER: usize = 0;
+pub const GUEST_PLAYER: usize = 1;
+
+#[derive(Debug)]
+pub enum NetError {
+    Io(io::Error),
+    /// The other side closed the connection without leaving the game.
+    Disconnected,
+    /// Nothing arrived from the other side for too long.
+    TimedOut,
+    /// The other side left the game.
+    Left,
+    Rejected(String),
+    Protocol(String),
+    /// The checksums of both games differ before simulating `frame`.
+    Desync { frame: usize },
+}
+
+impl Display for NetError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NetError::Io(err) => write!(f, "network error: {}", err),
+            NetError::Disconnected => write!(f, "the connection was lost"),
+            NetError::TimedOut => write!(f, "the other player stopped responding"),
+            NetError::Left => write!(f, "the other player left the game"),
+            NetError::Rejected(reason) => write!(f, "the host rejected the connection: {}", reason),
+            NetError::Protocol(reason) => write!(f, "invalid message from the other player: {}", reason),
+            NetError::Desync { frame } => write!(f, "the games went out of sync at frame {}", frame),
+        }
+    }
+}
+
+impl error::Error for NetError {}
+
+impl From<io::Error> for NetError {
+    fn from(err: io::Error) -> Self {
+        match err.kind() {
+            io::ErrorKind::UnexpectedEof | io::ErrorKind::ConnectionReset | io::ErrorKind::ConnectionAborted
+            | io::ErrorKind::BrokenPipe => NetError::Disconnected,
+            io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock => NetError::TimedOut,
+            _ => NetError::Io(err),
+        }
+    }
+}
+
+/// What the two sides of a networked game tell each other.
+///
+/// On the wire every message is a tag (`u8`) followed by its fields, all integers little endian:
+/// `Join` carries the [`PROTOCOL_VERSION`] (`u16`) and [`SIMULATION_VERSION`] (`u32`), `Start` the seed (`u64`)
+/// and config (`u32` length + TOML), `Reject` the reason (`u16` length + UTF-8), `Input` the frame (`u32`),
+/// input bits (`u8`) and checksum (`u64`).
+#[derive(Debug, Clone, PartialEq)]
+enum Message {
+    Join { protocol_version: u16, simulation_version: u32 },
+    Start { seed: u64, config: String },
+    Reject { reason: String },
+    /// The input of the sender for `frame`, together with the checksum of its game before simulating that frame.
+    Input { frame: u32, bits: u8, checksum: u64 },
+    Quit,
+}
+
+impl Message {
+    fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        match self {
+            Message::Join { protocol_version, simulation_version } => {
+                bytes.push(TAG_JOIN);
+                bytes.extend(protocol_version.to_le_bytes());
+                bytes.extend(simulation_version.to_le_bytes());
+            }
+            Message::Start { seed, config } => {
+                bytes.push(TAG_START);
+                bytes.extend(seed.to_le_bytes());
+                bytes.extend((config.len() as u32).to_le_bytes());
+                bytes.extend(config.as_bytes());
+            }
+            Message::Reject { reason } => {
+                bytes.push(TAG_REJECT);
+                bytes.extend((reason.len() as u16).to_le_bytes());
+                bytes.extend(reason.as_bytes());
+            }
+            Message::Input { frame, bits, checksum } => {
+                bytes.push(TAG_INPUT);
+                bytes.extend(frame.to_le_bytes());
+                bytes.push(*bits);
+                bytes.extend(checksum.to_le_bytes());
+            }
+            Message::Quit => bytes.push(TAG_QUIT),
+        }
+        bytes
+    }
+
+    /// Decodes the message at the start of `bytes` and returns it with its length,
+    /// or `None` if it did not arrive completely yet.
+    fn decode(bytes: &[u8]) -> Result<Option<(Message, usize)>, NetError> {
+        let mut reader = ByteReader::new(bytes, 1);
+        let Some(&tag) = bytes.first() else { return Ok(None); };
+        let message = match tag {
+            TAG_JOIN => reader.u16().and_then(|protocol_version| {
+                Some(Message::Join { protocol_version, simulation_version: reader.u32()? })
+            }),
+            TAG_START => reader.u64().and_then(|seed| {
+                let len = reader.u32()?;
+                Some(Message::Start { seed, config: reader.string(len as usize)? })
+            }),
+            TAG_REJECT => reader.u16().and_then(|len| reader.string(len as usize)).map(|reason| Message::Reject { reason }),
+            TAG_INPUT => reader.u32().and_then(|frame| {
+                Some(Message::Input { frame, bits: reader.u8()?, checksum: reader.u64()? })
+            }),
+            TAG_QUIT => Some(Message::Quit),
+            _ => return Err(NetError::Protocol(format!("unknown message tag {}", tag))),
+        };
+        if reader.invalid_utf8 {
+            return Err(NetError::Protocol("text is not valid UTF-8".to_string()));
+        }
+        Ok(message.map(|message| (message, reader.pos)))
+    }
+}
+
+/// Reads the little endian fields of a message, running out of bytes yields `None`.
+pub(crate) struct ByteReader<'a> {
+    bytes: &'a [u8],
+    pub(crate) pos: usize,
+    invalid_utf8: bool,
+}
+
+impl<'a> ByteReader<'a> {
+    pub(crate) fn new(bytes: &'a [u8], pos: usize) -> Self {
+        Self { bytes, pos, invalid_utf8: false }
+    }
+
+    fn take<const N: usize>(&mut self) -> Option<[u8; N]> {
+        let bytes = self.bytes.get(self.pos..self.pos + N)?.try_into().ok()?;
+        self.pos += N;
+        Some(bytes)
+    }
+
+    pub(crate) fn u8(&mut self) -> Option<u8> {
+        self.take().map(u8::from_le_bytes)
+    }
+
+    pub(crate) fn u16(&mut self) -> Option<u16> {
+        self.take().map(u16::from_le_bytes)
+    }
+
+    pub(crate) fn u32(&mut self) -> Option<u32> {
+        self.take().map(u32::from_le_bytes)
+    }
+
+    pub(crate) fn u64(&mut self) -> Option<u64> {
+        self.take().map(u64::from_le_bytes)
+    }
+
+    pub(crate) fn i16(&mut self) -> Option<i16> {
+        self.take().map(i16::from_le_bytes)
+    }
+
+    pub(crate) fn string(&mut self, len: usize) -> Option<String> {
+        let bytes = self.bytes.get(self.pos..self.pos + len)?;
+        self.pos += len;
+        let string = String::from_utf8(bytes.to_vec()).ok();
+        self.invalid_utf8 = string.is_none();
+        Some(string.unwrap_or_default())
+    }
+}
+
+/// A TCP connection exchanging [`Message`]s without ever blocking once the game runs.
+struct Connection {
+    stream: TcpStream,
+    buffer: Vec<u8>,
+    /// What couldn't be written yet without blocking.
+    backlog: Vec<u8>,
+    received: VecDeque<Message>,
+    last_received: Instant,
+}
+
+impl Connection {
+    fn new(stream: TcpStream) -> Result<Self, NetError> {
+        stream.set_nodelay(true)?;
+        stream.set_nonblocking(true)?;
+        let (buffer, backlog) = (Vec::new(), Vec::new());
+        Ok(Self { stream, buffer, backlog, received: VecDeque::new(), last_received: Instant::now() })
+    }
+
+    /// Queues `message` and sends as much of the queue as possible without blocking.
+    fn send(&mut self, message: &Message) -> Result<(), NetError> {
+        self.backlog.extend(message.encode());
+        self.flush()
+    }
+
+    /// Writes as much of the backlog as possible, the rest is sent on the next call.
+    fn flush(&mut self) -> Result<(), NetError> {
+        while !self.backlog.is_empty() {
+            match self.stream.write(&self.backlog) {
+                Ok(0) => return Err(NetError::Disconnected),
+                Ok(len) => { self.backlog.drain(..len); }
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err.into()),
+            }
+        }
+        Ok(())
+    }
+
+    /// Keeps writing the backlog until all of it is sent or `timeout` passed, so the last messages arrive
+    /// before the connection is closed.
+    fn flush_within(&mut self, timeout: Duration) -> Result<(), NetError> {
+        let deadline = Instant::now() + timeout;
+        self.flush()?;
+        while !self.backlog.is_empty() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(1));
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Sends what is still queued, reads what arrived so far and returns the next complete message, if there is one.
+    fn poll(&mut self) -> Result<Option<Message>, NetError> {
+        self.flush()?;
+        let mut chunk = [0; 1024];
+        loop {
+            match self.stream.read(&mut chunk) {
+                Ok(0) => {
+                    if self.received.is_empty() && Message::decode(&self.buffer)?.is_none() {
+                        return Err(NetError::Disconnected);
+                    }
+                    break;
+                }
+                Ok(len) => self.buffer.extend_from_slice(&chunk[..len]),
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err.into()),
+            }
+        }
+        while let Some((message, len)) = Message::decode(&self.buffer)? {
+            self.buffer.drain(..len);
+            self.received.push_back(message);
+        }
+        if !self.received.is_empty() {
+            self.last_received = Instant::now();
+        }
+        Ok(self.received.pop_front())
+    }
+
+    /// Waits up to `timeout` for the next message, used while the two sides shake hands.
+    fn receive(&mut self, timeout: Duration) -> Result<Message, NetError> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(message) = self.poll()? {
+                return Ok(message);
+            }
+            if Instant::now() >= deadline {
+                return Err(NetError::TimedOut);
+            }
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+}
+
+/// Listens for the player joining a hosted game.
+pub struct Host {
+    listener: TcpListener,
+    /// The player who connected but didn't ask to join yet, with the time they connected.
+    joining: Option<(Connection, Instant)>,
+}
+
+impl Host {
+    /// Listens on `port` of all interfaces, 0 picks a free port.
+    pub fn bind(port: u16) -> Result<Host, NetError> {
+        let listener = TcpListener::bind(("0.0.0.0", port))?;
+        listener.set_nonblocking(true)?;
+        Ok(Host { listener, joining: None })
+    }
+
+    pub fn local_addr(&self) -> Result<SocketAddr, NetError> {
+        Ok(self.listener.local_addr()?)
+    }
+
+    /// Accepts a waiting player and starts a game of two ships with `seed` and `config` for both sides, without blocking.
+    /// The [`GameMode`](crate::game_state::GameMode) of `config` decides whether it is played co-op or versus.
+    /// Returns `None` while nobody tries to join or the join request didn't arrive yet, so it is called until
+    /// the game starts. A player running a different protocol or simulation version is rejected.
+    pub fn accept(&mut self, seed: u64, config: &GameConfig) -> Result<Option<Lockstep>, NetError> {
+        if self.joining.is_none() {
+            match self.listener.accept() {
+                Ok((stream, _)) => self.joining = Some((Connection::new(stream)?, Instant::now())),
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => return Ok(None),
+                Err(err) => return Err(err.into()),
+            }
+        }
+        let Some((connection, since)) = self.joining.as_mut() else { return Ok(None); };
+        let message = match connection.poll() {
+            Ok(None) if since.elapsed() <= Duration::from_millis(NET_HANDSHAKE_TIMEOUT_MILLIS) => return Ok(None),
+            Ok(None) => Err(NetError::TimedOut),
+            result => result,
+        };
+        let (mut connection, _) = self.joining.take().expect("a player is joining");
+        let Some(Message::Join { protocol_version, simulation_version }) = message? else {
+            return Err(NetError::Protocol("expected a join request".to_string()));
+        };
+        if (protocol_version, simulation_version) != (PROTOCOL_VERSION, SIMULATION_VERSION) {
+            let reason = format!(
+                "the host runs protocol {} and simulation version {}, but the player protocol {} and simulation version {}",
+                PROTOCOL_VERSION, SIMULATION_VERSION, protocol_version, simulation_version,
+            );
+            connection.send(&Message::Reject { reason: reason.clone() })?;
+            return Err(NetError::Rejected(reason));
+        }
+        let config = GameConfig { players: 2, ..config.clone() };
+        connection.send(&Message::Start { seed, config: config.to_toml() })?;
+        Ok(Some(Lockstep::new(connection, HOST_PLAYER, seed, config)))
+    }
+}
+
+/// Joins the game hosted at `addr`, e.g. `"192.168.0.2:7777"`, and receives the seed and config of the game.
+pub fn join(addr: &str) -> Result<Lockstep, NetError> {
+    let timeout = Duration::from_millis(NET_HANDSHAKE_TIMEOUT_MILLIS);
+    let addr = addr.to_socket_addrs()?.next()
+        .ok_or_else(|| NetError::Io(io::Error::new(io::ErrorKind::NotFound, "no address found")))?;
+    let mut connection = Connection::new(TcpStream::connect_timeout(&addr, timeout)?)?;
+    connection.send(&Message::Join { protocol_version: PROTOCOL_VERSION, simulation_version: SIMULATION_VERSION })?;
+    match connection.receive(timeout)? {
+        Message::Start { seed, config } => {
+            let config = GameConfig::from_toml(&config)
+                .map_err(|err| NetError::Protocol(format!("invalid game config: {}", err)))?;
+            Ok(Lockstep::new(connection, GUEST_PLAYER, seed, config))
+        }
+        Message::Reject { reason } => Err(NetError::Rejected(reason)),
+        _ => Err(NetError::Protocol("expected the start of the game".to_string())),
+    }
+}
+
+/// Keeps the games of both sides in lockstep: a frame is only simulated once the inputs of both players for it are known.
+///
+/// Both sides simulate the same seed and config, so exchanging the inputs is enough to play the same game.
+/// Every input carries the checksum of the sender's game, a differing checksum ends the game as desynced.
+pub struct Lockstep {
+    connection: Connection,
+    /// The player flown on this side.
+    pub local_player: usize,
+    pub seed: u64,
+    pub config: GameConfig,
+    /// The local input sent for the frame waiting for the other side's input.
+    sent: Option<FrameInput>,
+}
+
+impl Lockstep {
+    fn new(connection: Connection, local_player: usize, seed: u64, config: GameConfig) -> Self {
+        Self { connection, local_player, seed, config, sent: None }
+    }
+
+    /// The game both sides play.
+    pub fn new_game(&self) -> GameState {
+        GameState::with_seed(self.config.clone(), self.seed)
+    }
+
+    /// Sends the local `input` for the next frame of `game` and simulates it once the other side's input arrived.
+    /// Returns the inputs of both players if the frame was simulated, `None` while waiting for the other side.
+    /// Inputs given while waiting are dropped, the one sent first counts for the frame.
+    pub fn step(&mut self, game: &mut GameState, input: FrameInput) -> Result<Option<Vec<FrameInput>>, NetError> {
+        let frame = game.frame;
+        let checksum = game.checksum();
+        let local = match self.sent {
+            Some(local) => local,
+            None => {
+                self.connection.send(&Message::Input { frame: frame as u32, bits: input.to_bits(), checksum })?;
+                self.sent = Some(input);
+                input
+            }
+        };
+        let remote = match self.connection.poll()? {
+            Some(Message::Input { frame: remote_frame, bits, checksum: remote_checksum }) => {
+                if remote_frame as usize != frame {
+                    return Err(NetError::Protocol(format!("expected frame {}, got {}", frame, remote_frame)));
+                }
+                if remote_checksum != checksum {
+                    return Err(NetError::Desync { frame });
+                }
+                FrameInput::from_bits(bits)
+            }
+            Some(Message::Quit) => return Err(NetError::Left),
+            Some(_) => return Err(NetError::Protocol("expected an input".to_string())),
+            None if self.connection.last_received.elapsed() > Duration::from_millis(NET_TIMEOUT_MILLIS) => {
+                return Err(NetError::TimedOut);
+            }
+            None => return Ok(None),
+        };
+        self.sent = None;
+        let mut inputs = vec![FrameInput::default(); 2];
+        inputs[self.local_player] = local;
+        inputs[1 - self.local_player] = remote;
+        game.update(&inputs);
+        Ok(Some(inputs))
+    }
+
+    /// Tells the other side that this player left and closes the connection once everything queued was sent,
+    /// waiting a moment at most.
+    pub fn leave(mut self) {
+        // the other side notices the closed connection anyway if the message can't be sent
+        let timeout = Duration::from_millis(NET_LEAVE_TIMEOUT_MILLIS);
+        let _ = self.connection.send(&Message::Quit).and_then(|_| self.connection.flush_within(timeout));
+        let _ = self.connection.stream.shutdown(Shutdown::Both);
+    }
+}
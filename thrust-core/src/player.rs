@@ -0,0 +1,107 @@
+use std::cmp::min;
+use crate::config::GameConfig;
+use crate::effect::{EffectKind, Effects};
+use crate::goodie::GoodieType;
+use crate::score::{ScoreKeeper, ScoreSource};
+use crate::ship::Ship;
+use crate::stats::{DamageCause, PlayerStats};
+
+/// A ship together with everything that belongs to the player flying it.
+pub struct Player {
+    pub ship: Ship,
+    pub health: usize,
+    pub shield: usize,
+    pub score: usize,
+    pub scoring: ScoreKeeper,
+    pub effects: Effects,
+    pub bombs: usize,
+    pub stats: PlayerStats,
+}
+
+impl Player {
+    pub fn new(ship: Ship, config: &GameConfig) -> Self {
+        Self {
+            ship,
+            health: config.initial_health,
+            shield: config.initial_shield,
+            score: 0,
+            scoring: ScoreKeeper::default(),
+            effects: Effects::default(),
+            bombs: 0,
+            stats: PlayerStats::default(),
+        }
+    }
+
+    /// Whether the ship got destroyed. It then no longer takes part in the game, but its score still counts.
+    pub fn is_destroyed(&self) -> bool {
+        self.health == 0
+    }
+
+    /// The factor all scored points are multiplied with: the multiplier built up by kills,
+    /// times the one of the score multiplier power-up.
+    pub fn score_factor(&self, config: &GameConfig) -> usize {
+        let power_up = if self.effects.is_active(EffectKind::ScoreMultiplier) { config.effects.score_multiplier } else { 1 };
+        self.scoring.multiplier * power_up
+    }
+
+    pub(crate) fn award(&mut self, source: ScoreSource, points: usize, config: &GameConfig) {
+        let points = points * self.score_factor(config);
+        self.score += points;
+        self.scoring.record(source, points);
+    }
+
+    /// Counts down the power-ups and applies the ones changing how the ship shoots.
+    pub(crate) fn update_effects(&mut self, config: &GameConfig) {
+        self.effects.update();
+        self.ship.shot_interval = if self.effects.is_active(EffectKind::RapidFire) {
+            config.effects.rapid_fire_shot_interval
+        } else {
+            config.shot_interval
+        };
+        self.ship.spread = self.effects.is_active(EffectKind::Spread);
+    }
+
+    /// Takes `damage` from the shield while it lasts, the `direct_damage` of enemies reaching the ground always
+    /// from the health. `cause` is what dealt `damage`.
+    pub(crate) fn damage(&mut self, damage: usize, direct_damage: usize, cause: DamageCause) {
+        if self.effects.is_active(EffectKind::Invincibility) || damage + direct_damage == 0 {
+            return;
+        }
+        self.scoring.register_damage();
+        self.stats.record_damage(cause, damage);
+        self.stats.record_damage(DamageCause::EnemyReachedGround, direct_damage);
+        self.health = self.health.saturating_sub(direct_damage);
+        if self.is_destroyed() {
+            self.stats.destroyed_by.get_or_insert(DamageCause::EnemyReachedGround);
+        }
+        if self.shield > 0 {
+            self.shield = self.shield.saturating_sub(damage);
+        } else {
+            self.health = self.health.saturating_sub(damage);
+        }
+        if self.is_destroyed() {
+            self.stats.destroyed_by.get_or_insert(cause);
+        }
+    }
+
+    pub(crate) fn collect(&mut self, goodie_type: GoodieType, config: &GameConfig) {
+        self.stats.record_goodie(goodie_type);
+        match goodie_type {
+            GoodieType::RepairKit(additional_health) => {
+                self.health = min(self.health + additional_health, config.max_health);
+            }
+            GoodieType::ShieldBoost(additional_shield) => {
+                self.shield = min(self.shield + additional_shield, config.max_shield);
+            }
+            GoodieType::ShipUpgrade(new_ship_type) => {
+                self.ship.change_ship_type(&new_ship_type);
+            }
+            GoodieType::PowerUp(effect) => {
+                self.effects.activate(effect, effect.duration(&config.effects));
+            }
+            GoodieType::Bomb => {
+                self.bombs = min(self.bombs + 1, config.goodies.max_bombs);
+            }
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::{error, io};
+use crate::config::GameConfig;
+use crate::constant::{REPLAY_MAX_CONFIG_LEN, REPLAY_MAX_FRAMES};
+use crate::input::FrameInput;
+use crate::SIMULATION_VERSION;
+
+const MAGIC: &[u8; 6] = b"THRUST";
+const FORMAT_VERSION: u8 = 4;
+
+/// A recorded run: the seed and config of the game and the inputs of every simulated frame, one per player.
+///
+/// On disk the inputs are run-length encoded, as most consecutive frames share the same input.
+/// The layout (all integers little endian) is:
+/// `"THRUST"`, format version (`u8`), simulation version (`u32`), seed (`u64`),
+/// config (`u32` length + TOML), number of runs (`u32`) and finally each run as the input bits of every player (`u8` each) and repetitions (`u16`).
+/// The number of players is taken from the config.
+/// Files with a config of more than [`REPLAY_MAX_CONFIG_LEN`] bytes or more than [`REPLAY_MAX_FRAMES`] frames are refused as broken.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Replay {
+    /// The [`SIMULATION_VERSION`] the run was recorded with, it only replays exactly on the same one.
+    pub simulation_version: u32,
+    pub seed: u64,
+    pub config: GameConfig,
+    pub inputs: Vec<Vec<FrameInput>>,
+}
+
+#[derive(Debug)]
+pub enum ReplayError {
+    Io(io::Error),
+    InvalidFormat(String),
+}
+
+impl Display for ReplayError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReplayError::Io(err) => write!(f, "could not access replay file: {}", err),
+            ReplayError::InvalidFormat(reason) => write!(f, "invalid replay file: {}", reason),
+        }
+    }
+}
+
+impl error::Error for ReplayError {}
+
+impl From<io::Error> for ReplayError {
+    fn from(err: io::Error) -> Self {
+        match err.kind() {
+            io::ErrorKind::UnexpectedEof => ReplayError::InvalidFormat("unexpected end of file".to_string()),
+            _ => ReplayError::Io(err),
+        }
+    }
+}
+
+impl Replay {
+    pub fn new(seed: u64, config: GameConfig) -> Self {
+        Self { simulation_version: SIMULATION_VERSION, seed, config, inputs: Vec::new() }
+    }
+
+    pub fn record(&mut self, inputs: &[FrameInput]) {
+        self.inputs.push(inputs.to_vec());
+    }
+
+    pub fn is_current_version(&self) -> bool {
+        self.simulation_version == SIMULATION_VERSION
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), ReplayError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_to(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    pub fn load(path: &Path) -> Result<Replay, ReplayError> {
+        Replay::read_from(&mut BufReader::new(File::open(path)?))
+    }
+
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<(), ReplayError> {
+        let runs = self.runs();
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[FORMAT_VERSION])?;
+        writer.write_all(&self.simulation_version.to_le_bytes())?;
+        writer.write_all(&self.seed.to_le_bytes())?;
+        let config = self.config.to_toml();
+        writer.write_all(&(config.len() as u32).to_le_bytes())?;
+        writer.write_all(config.as_bytes())?;
+        writer.write_all(&(runs.len() as u32).to_le_bytes())?;
+        for (bits, count) in runs {
+            writer.write_all(&bits)?;
+            writer.write_all(&count.to_le_bytes())?;
+        }
+        Ok(())
+    }
+
+    pub fn read_from<R: Read>(reader: &mut R) -> Result<Replay, ReplayError> {
+        let mut magic = [0; 6];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(ReplayError::InvalidFormat("not a thrust replay".to_string()));
+        }
+        let [format_version] = read_bytes(reader)?;
+        if format_version != FORMAT_VERSION {
+            return Err(ReplayError::InvalidFormat(format!("unsupported format version {}", format_version)));
+        }
+        let simulation_version = u32::from_le_bytes(read_bytes(reader)?);
+        let seed = u64::from_le_bytes(read_bytes(reader)?);
+        let config_len = u32::from_le_bytes(read_bytes(reader)?) as usize;
+        if config_len > REPLAY_MAX_CONFIG_LEN {
+            return Err(ReplayError::InvalidFormat(format!("game config of {} bytes is too large", config_len)));
+        }
+        let mut config = vec![0; config_len];
+        reader.read_exact(&mut config)?;
+        let config = String::from_utf8(config).ok()
+            .and_then(|config| GameConfig::from_toml(&config).ok())
+            .ok_or_else(|| ReplayError::InvalidFormat("invalid game config".to_string()))?;
+        let run_count = u32::from_le_bytes(read_bytes(reader)?) as usize;
+        if run_count > REPLAY_MAX_FRAMES {
+            return Err(ReplayError::InvalidFormat(format!("{} runs of inputs are too many", run_count)));
+        }
+        let mut inputs = Vec::new();
+        for _ in 0..run_count {
+            let mut bits = vec![0; config.players];
+            reader.read_exact(&mut bits)?;
+            let count = u16::from_le_bytes(read_bytes(reader)?);
+            if inputs.len() + count as usize > REPLAY_MAX_FRAMES {
+                return Err(ReplayError::InvalidFormat(format!("more than {} frames", REPLAY_MAX_FRAMES)));
+            }
+            let frame: Vec<FrameInput> = bits.into_iter().map(FrameInput::from_bits).collect();
+            inputs.extend((0..count).map(|_| frame.clone()));
+        }
+        Ok(Replay { simulation_version, seed, config, inputs })
+    }
+
+    fn runs(&self) -> Vec<(Vec<u8>, u16)> {
+        let mut runs: Vec<(Vec<u8>, u16)> = Vec::new();
+        for inputs in &self.inputs {
+            // frames recorded with fewer inputs than players are padded with no input
+            let bits: Vec<u8> = (0..self.config.players)
+                .map(|player| inputs.get(player).map_or(0, |input| input.to_bits()))
+                .collect();
+            match runs.last_mut() {
+                Some((last_bits, count)) if *last_bits == bits && *count < u16::MAX => *count += 1,
+                _ => runs.push((bits, 1)),
+            }
+        }
+        runs
+    }
+}
+
+fn read_bytes<R: Read, const N: usize>(reader: &mut R) -> io::Result<[u8; N]> {
+    let mut buf = [0; N];
+    reader.read_exact(&mut buf)?;
+    Ok(buf)
+}
@@ -0,0 +1,110 @@
+use crate::config::ScoringConfig;
+
+/// Where the points of a game came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScoreSource {
+    Kills,
+    Bosses,
+    Combos,
+    Goodies,
+    Grazes,
+    NoDamageWaves,
+}
+
+impl ScoreSource {
+    pub const ALL: [ScoreSource; 6] = [
+        ScoreSource::Kills,
+        ScoreSource::Bosses,
+        ScoreSource::Combos,
+        ScoreSource::Goodies,
+        ScoreSource::Grazes,
+        ScoreSource::NoDamageWaves,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ScoreSource::Kills => "kills",
+            ScoreSource::Bosses => "bosses",
+            ScoreSource::Combos => "combos",
+            ScoreSource::Goodies => "goodies",
+            ScoreSource::Grazes => "grazes",
+            ScoreSource::NoDamageWaves => "no-damage waves",
+        }
+    }
+
+    fn index(&self) -> usize {
+        ScoreSource::ALL.iter().position(|source| source == self).expect("every source is listed")
+    }
+}
+
+/// Keeps track of the combo chain, the multiplier and the points per [`ScoreSource`].
+///
+/// Kills in quick succession build up a combo. Every `multiplier_kills` kills without taking damage
+/// raise the multiplier, taking damage resets it.
+#[derive(Debug, Clone, Hash)]
+pub struct ScoreKeeper {
+    breakdown: [usize; 6],
+    pub combo: usize,
+    pub multiplier: usize,
+    last_kill_frame: usize,
+    kills_since_damage: usize,
+    damaged_in_wave: bool,
+}
+
+impl Default for ScoreKeeper {
+    fn default() -> Self {
+        Self {
+            breakdown: [0; 6],
+            combo: 0,
+            multiplier: 1,
+            last_kill_frame: 0,
+            kills_since_damage: 0,
+            damaged_in_wave: false,
+        }
+    }
+}
+
+impl ScoreKeeper {
+    pub fn points(&self, source: ScoreSource) -> usize {
+        self.breakdown[source.index()]
+    }
+
+    pub fn total(&self) -> usize {
+        self.breakdown.iter().sum()
+    }
+
+    pub(crate) fn record(&mut self, source: ScoreSource, points: usize) {
+        self.breakdown[source.index()] += points;
+    }
+
+    /// Ends the combo once no kill happened within the combo window.
+    pub fn update(&mut self, frame: usize, config: &ScoringConfig) {
+        if self.last_kill_frame + config.combo_window < frame {
+            self.combo = 0;
+        }
+    }
+
+    /// Counts a kill towards the combo and the multiplier and returns the length of the combo.
+    pub fn register_kill(&mut self, frame: usize, config: &ScoringConfig) -> usize {
+        self.combo += 1;
+        self.last_kill_frame = frame;
+        self.kills_since_damage += 1;
+        if self.kills_since_damage.is_multiple_of(config.multiplier_kills) {
+            self.multiplier = (self.multiplier + 1).min(config.max_multiplier);
+        }
+        self.combo
+    }
+
+    pub fn register_damage(&mut self) {
+        self.multiplier = 1;
+        self.kills_since_damage = 0;
+        self.damaged_in_wave = true;
+    }
+
+    /// Starts the next wave and tells whether the finished one was survived without damage.
+    pub fn finish_wave(&mut self) -> bool {
+        let no_damage = !self.damaged_in_wave;
+        self.damaged_in_wave = false;
+        no_damage
+    }
+}
@@ -0,0 +1,213 @@
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use crate::constant::SHIP_SHOT_INTERVAL;
+use crate::fixed::{Fixed, FixedVec2};
+use crate::hitbox::{Hitbox, Sweep};
+use crate::shot::ShotType;
+use crate::spatial::Vec2;
+use crate::world::{Entity, World};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ShipType {
+    Basic,
+    DiagonalShot,
+    StrongShot,
+}
+
+impl ShipType {
+    pub const ALL: [ShipType; 3] = [ShipType::Basic, ShipType::DiagonalShot, ShipType::StrongShot];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ShipType::Basic => "basic",
+            ShipType::DiagonalShot => "diagonal",
+            ShipType::StrongShot => "strong",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<ShipType> {
+        match name {
+            "basic" => Some(ShipType::Basic),
+            "diagonal" => Some(ShipType::DiagonalShot),
+            "strong" => Some(ShipType::StrongShot),
+            _ => None,
+        }
+    }
+}
+
+/// How the fire input controls the ship's weapon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FireMode {
+    /// Fire toggles auto-shoot on and off, fire shoots a single volley.
+    #[default]
+    Toggle,
+    /// The ship shoots as long as fire is held.
+    Hold,
+    /// Holding fire charges the weapon, releasing it shoots. A fully charged weapon shoots a stronger volley.
+    Charge,
+}
+
+impl Distribution<ShipType> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> ShipType {
+        match rng.gen_range(0..=2) {
+            0 => ShipType::Basic,
+            1 => ShipType::DiagonalShot,
+            _ => ShipType::StrongShot,
+        }
+    }
+}
+
+pub struct Ship {
+    /// The index of the player flying the ship, who gets the points for what its shots destroy.
+    pub player: usize,
+    /// The cell of the ship's left end.
+    pub pos: Vec2,
+    pub exact: FixedVec2,
+    /// Where the ship was at the start of the current frame.
+    pub previous: FixedVec2,
+    pub ship_type: ShipType,
+    pub auto_shoot: bool,
+    pub shot_interval: usize,
+    pub last_shot_frame: usize,
+    /// Adds two diagonal shots to every usual volley, while the spread power-up is active.
+    pub spread: bool,
+    /// The number of frames fire has been held in [`FireMode::Charge`].
+    pub charge: usize,
+}
+
+impl Ship {
+    pub(crate) fn change_ship_type(&mut self, new_ship_type: &ShipType) {
+        self.ship_type = match new_ship_type {
+            ShipType::Basic => ShipType::Basic,
+            ShipType::DiagonalShot => ShipType::DiagonalShot,
+            ShipType::StrongShot => ShipType::StrongShot,
+        };
+    }
+}
+
+impl Ship {
+    pub fn new(player: usize, initial_position: Vec2) -> Self {
+        Self {
+            player,
+            pos: initial_position,
+            exact: FixedVec2::from_cell(initial_position),
+            previous: FixedVec2::from_cell(initial_position),
+            ship_type: ShipType::Basic,
+            last_shot_frame: 0,
+            auto_shoot: false,
+            shot_interval: SHIP_SHOT_INTERVAL,
+            spread: false,
+            charge: 0,
+        }
+    }
+
+    pub fn move_x(&mut self, dx: Fixed, max_x: i32) {
+        let new_x = self.exact.x + dx;
+        if new_x.round() <= 0 || new_x.round() >= max_x {
+            return;
+        }
+        self.exact.x = new_x;
+        self.pos = self.exact.cell();
+    }
+    pub fn move_y(&mut self, dy: Fixed, max_y: i32) {
+        let new_y = self.exact.y + dy;
+        if new_y.round() <= 1 || new_y.round() >= max_y {
+            return;
+        }
+        self.exact.y = new_y;
+        self.pos = self.exact.cell();
+    }
+
+    /// Fires a volley into `world`, unless the last one was less than `shot_interval` frames ago.
+    pub fn shoot(&mut self, shot_frame: usize, world: &mut World) {
+        if self.last_shot_frame + self.shot_interval < shot_frame {
+            let default_movement = Vec2::y(-1);
+            match self.ship_type {
+                ShipType::Basic => {
+                    for x in 0..=2 {
+                        self.fire(world, self.pos + Vec2::x(x), default_movement, ShotType::Basic);
+                    }
+                }
+                ShipType::StrongShot => {
+                    for x in 0..=2 {
+                        self.fire(world, self.pos + Vec2::x(x), default_movement, ShotType::Strong);
+                    }
+                }
+                ShipType::DiagonalShot => {
+                    let dir_diagonal_l = Vec2::xy(-1, -1);
+                    let dir_diagonal_r = Vec2::xy(1, -1);
+                    self.fire(world, self.pos, dir_diagonal_l, ShotType::DiagonalLeft);
+                    self.fire(world, self.pos, dir_diagonal_r, ShotType::DiagonalRight);
+                    self.fire(world, self.pos + Vec2::x(1), default_movement, ShotType::Basic);
+                    self.fire(world, self.pos + Vec2::x(2), dir_diagonal_l, ShotType::DiagonalLeft);
+                    self.fire(world, self.pos + Vec2::x(2), dir_diagonal_r, ShotType::DiagonalRight);
+                }
+            }
+            if self.spread {
+                self.fire(world, self.pos, Vec2::xy(-1, -1), ShotType::DiagonalLeft);
+                self.fire(world, self.pos + Vec2::x(2), Vec2::xy(1, -1), ShotType::DiagonalRight);
+            }
+            self.last_shot_frame = shot_frame;
+        }
+    }
+
+    fn fire(&self, world: &mut World, pos: Vec2, movement: Vec2, shot_type: ShotType) {
+        world.spawn(Entity::shot(pos, movement, shot_type).fired_by(self.player));
+    }
+
+    /// Shoots a wide volley of strong shots, regardless of the shot interval.
+    pub fn shoot_charged(&mut self, shot_frame: usize, world: &mut World) {
+        for x in -1..=3 {
+            self.fire(world, self.pos + Vec2::x(x), Vec2::y(-1), ShotType::Strong);
+        }
+        self.last_shot_frame = shot_frame;
+    }
+
+    /// Charges the weapon while `fire` is held and shoots once it is released.
+    pub fn charge_or_release(&mut self, fire: bool, charge_frames: usize, shot_frame: usize, world: &mut World) {
+        if fire {
+            self.charge += 1;
+            return;
+        }
+        if self.charge >= charge_frames {
+            self.shoot_charged(shot_frame, world);
+        } else if self.charge > 0 {
+            self.shoot(shot_frame, world);
+        }
+        self.charge = 0;
+    }
+
+    pub fn update(&mut self, frame: usize, world: &mut World) {
+        if self.auto_shoot {
+            self.shoot(frame, world);
+        }
+    }
+
+    pub fn hitbox(&self) -> Hitbox {
+        Hitbox::new(self.pos, Vec2::xy(3, 1))
+    }
+
+    /// The way the ship moved during the current frame.
+    pub fn sweep(&self) -> Sweep {
+        Sweep::new(self.previous, self.exact, Vec2::xy(3, 1))
+    }
+
+    pub fn is_hit_by(&self, object: &Hitbox) -> bool {
+        self.hitbox().intersects(object)
+    }
+
+    /// Whether the ship and an object that moved along `sweep` met at any moment of the current frame.
+    pub fn is_swept_by(&self, sweep: &Sweep) -> bool {
+        self.sweep().hits(sweep)
+    }
+
+    pub fn sprite(&self) -> &'static str {
+        match self.ship_type {
+            ShipType::Basic => "/^\\",
+            ShipType::DiagonalShot => "Y+Y",
+            ShipType::StrongShot => "TuT",
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,90 @@
+use crate::config::{DamageConfig, Glyphs};
+use crate::world::Faction;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ShotType {
+    Enemy,
+    EnemyAimed,
+    EnemySpread,
+    EnemyBurst,
+    /// A slow homing missile, which the ship can shoot down.
+    Missile,
+    Boss,
+    Basic,
+    Strong,
+    DiagonalLeft,
+    DiagonalRight,
+}
+
+impl ShotType {
+    pub const ALL: [ShotType; 10] = [
+        ShotType::Enemy,
+        ShotType::EnemyAimed,
+        ShotType::EnemySpread,
+        ShotType::EnemyBurst,
+        ShotType::Missile,
+        ShotType::Boss,
+        ShotType::Basic,
+        ShotType::Strong,
+        ShotType::DiagonalLeft,
+        ShotType::DiagonalRight,
+    ];
+
+    pub fn character(&self, glyphs: &Glyphs) -> char {
+        match self {
+            ShotType::Enemy => glyphs.shot_enemy,
+            ShotType::EnemyAimed => glyphs.shot_enemy_aimed,
+            ShotType::EnemySpread => glyphs.shot_enemy_spread,
+            ShotType::EnemyBurst => glyphs.shot_enemy_burst,
+            ShotType::Missile => glyphs.missile,
+            ShotType::Boss => glyphs.shot_boss,
+            ShotType::Basic => glyphs.shot_ship_basic,
+            ShotType::Strong => glyphs.shot_ship_strong,
+            ShotType::DiagonalLeft => glyphs.shot_ship_diagonal_l,
+            ShotType::DiagonalRight => glyphs.shot_ship_diagonal_r,
+        }
+    }
+
+    pub fn damage(&self, damage: &DamageConfig) -> usize {
+        match self {
+            ShotType::Enemy | ShotType::EnemyAimed | ShotType::EnemySpread | ShotType::EnemyBurst | ShotType::Boss => {
+                damage.shot_hit
+            }
+            ShotType::Missile => damage.missile,
+            ShotType::Strong => damage.shot_ship_strong,
+            ShotType::Basic | ShotType::DiagonalLeft | ShotType::DiagonalRight => damage.shot_ship_basic,
+        }
+    }
+
+    /// The ship fires the basic, strong and diagonal shots, everything else comes from enemies and the boss.
+    pub fn faction(&self) -> Faction {
+        match self {
+            ShotType::Basic | ShotType::Strong | ShotType::DiagonalLeft | ShotType::DiagonalRight => Faction::Player,
+            _ => Faction::Enemy,
+        }
+    }
+
+    /// Whether the shot keeps flying after hitting something.
+    pub fn is_piercing(&self) -> bool {
+        *self == ShotType::Strong
+    }
+}
+
+/// The part of a shot entity only shots have.
+#[derive(Hash)]
+pub struct Shot {
+    pub shot_type: ShotType,
+    /// The player whose ship the shot is passing close by. The graze is only counted once the shot got past
+    /// the ship without hitting it.
+    pub grazing: Option<usize>,
+    /// Whether the shot already passed close by a ship, so a graze is only counted once.
+    pub grazed: bool,
+    /// The index of the player whose ship fired the shot.
+    pub owner: Option<usize>,
+}
+
+impl Shot {
+    pub fn new(shot_type: ShotType) -> Self {
+        Self { shot_type, grazing: None, grazed: false, owner: None }
+    }
+}
@@ -0,0 +1,165 @@
+//! Plays many games without rendering and sums up how they went, so that balancing the values of the config
+//! can be based on numbers: scores, survival times, what destroyed the ships and which goodies got used.
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+use std::thread;
+use serde_json::{json, Map, Value};
+use crate::autopilot;
+use crate::config::GameConfig;
+use crate::game_state::GameState;
+use crate::goodie::GoodieType;
+use crate::input::FrameInput;
+use crate::ship::ShipType;
+use crate::stats::{DamageCause, PlayerStats};
+
+/// Who flies the ship in simulated games.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pilot {
+    /// The scripted pilot of [`autopilot`].
+    Autopilot,
+    /// Nobody: the ship stays put and never fires, a baseline for the others.
+    Idle,
+}
+
+impl Pilot {
+    pub const ALL: [Pilot; 2] = [Pilot::Autopilot, Pilot::Idle];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Pilot::Autopilot => "autopilot",
+            Pilot::Idle => "idle",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Pilot> {
+        Pilot::ALL.into_iter().find(|pilot| pilot.name() == name)
+    }
+
+    fn input(&self, game: &GameState) -> FrameInput {
+        match self {
+            Pilot::Autopilot => FrameInput::from_actions(autopilot::actions(game, 0)),
+            Pilot::Idle => FrameInput::default(),
+        }
+    }
+}
+
+/// How a single simulated game went.
+#[derive(Debug, Clone)]
+pub struct RunResult {
+    pub seed: u64,
+    pub score: usize,
+    /// The frames the ship survived, `max_frames` if the game was cut off.
+    pub frames: usize,
+    pub stats: PlayerStats,
+    pub goodies_dropped: BTreeMap<&'static str, usize>,
+}
+
+/// Plays a single player game with `seed` until the ship is destroyed, the levels are complete or `max_frames` passed.
+pub fn run(config: &GameConfig, seed: u64, pilot: Pilot, max_frames: usize) -> RunResult {
+    let config = GameConfig { players: 1, ..config.clone() };
+    let mut game = GameState::with_seed(config, seed);
+    while game.frame < max_frames && !game.is_game_over() && !game.is_level_complete() {
+        let input = pilot.input(&game);
+        game.update(&[input]);
+    }
+    let player = game.players.swap_remove(0);
+    RunResult { seed, score: player.score, frames: game.frame, stats: player.stats, goodies_dropped: game.goodies_dropped }
+}
+
+/// Plays a game for every seed of `seeds`, spread over all cores. The results are in the order of the seeds.
+pub fn run_all(config: &GameConfig, seeds: Range<u64>, pilot: Pilot, max_frames: usize) -> Vec<RunResult> {
+    let seeds: Vec<u64> = seeds.collect();
+    let threads = thread::available_parallelism().map_or(1, |threads| threads.get());
+    let chunk_size = seeds.len().div_ceil(threads).max(1);
+    thread::scope(|scope| {
+        let handles: Vec<_> = seeds.chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || chunk.iter().map(|&seed| run(config, seed, pilot, max_frames)).collect::<Vec<_>>()))
+            .collect();
+        handles.into_iter().flat_map(|handle| handle.join().expect("simulation thread panicked")).collect()
+    })
+}
+
+/// Sums up `results` as JSON: the distributions of score and survived frames, the deaths and the mean damage
+/// by cause, the drop and pickup counts of every goodie and the share of the frames flown as each ship type.
+pub fn summary(results: &[RunResult]) -> Value {
+    let mut deaths = Map::new();
+    let mut damage = Map::new();
+    for cause in DamageCause::ALL {
+        let count = results.iter().filter(|result| result.stats.destroyed_by == Some(cause)).count();
+        deaths.insert(cause.name().to_string(), json!(count));
+        let total: usize = results.iter().map(|result| result.stats.damage(cause)).sum();
+        damage.insert(cause.name().to_string(), json!(mean(total, results.len())));
+    }
+    deaths.insert("survived".to_string(), json!(results.iter().filter(|result| result.stats.destroyed_by.is_none()).count()));
+    let mut goodies = Map::new();
+    for name in GoodieType::NAMES {
+        let dropped: usize = results.iter().map(|result| result.goodies_dropped.get(name).copied().unwrap_or(0)).sum();
+        let collected: usize = results.iter().map(|result| result.stats.goodies_collected.get(name).copied().unwrap_or(0)).sum();
+        let pickup_rate = if dropped == 0 { Value::Null } else { json!(collected as f64 / dropped as f64) };
+        goodies.insert(name.to_string(), json!({ "dropped": dropped, "collected": collected, "pickup_rate": pickup_rate }));
+    }
+    let total_frames: usize = results.iter().map(|result| result.frames).sum();
+    let mut uptime = Map::new();
+    for ship_type in ShipType::ALL {
+        let frames: usize = results.iter().map(|result| result.stats.frames_as(ship_type)).sum();
+        uptime.insert(ship_type.name().to_string(), json!(mean(frames, total_frames)));
+    }
+    json!({
+        "runs": results.len(),
+        "score": distribution(results.iter().map(|result| result.score).collect()),
+        "frames": distribution(results.iter().map(|result| result.frames).collect()),
+        "deaths": deaths,
+        "mean_damage": damage,
+        "goodies": goodies,
+        "ship_type_uptime": uptime,
+    })
+}
+
+/// One line per game with its seed, score, survived frames, cause of death (empty if it survived), damage by cause,
+/// dropped and collected goodies by name and frames by ship type, after a header line naming the columns.
+pub fn csv(results: &[RunResult]) -> String {
+    let mut header = vec!["seed".to_string(), "score".to_string(), "frames".to_string(), "destroyed_by".to_string()];
+    header.extend(DamageCause::ALL.iter().map(|cause| format!("damage_{}", cause.name())));
+    header.extend(GoodieType::NAMES.iter().map(|name| format!("dropped_{}", name)));
+    header.extend(GoodieType::NAMES.iter().map(|name| format!("collected_{}", name)));
+    header.extend(ShipType::ALL.iter().map(|ship_type| format!("frames_{}", ship_type.name())));
+    let mut lines = vec![header.join(",")];
+    for result in results {
+        let mut row = vec![
+            result.seed.to_string(),
+            result.score.to_string(),
+            result.frames.to_string(),
+            result.stats.destroyed_by.map_or("", |cause| cause.name()).to_string(),
+        ];
+        row.extend(DamageCause::ALL.iter().map(|&cause| result.stats.damage(cause).to_string()));
+        row.extend(GoodieType::NAMES.iter().map(|name| result.goodies_dropped.get(name).copied().unwrap_or(0).to_string()));
+        row.extend(GoodieType::NAMES.iter().map(|name| result.stats.goodies_collected.get(name).copied().unwrap_or(0).to_string()));
+        row.extend(ShipType::ALL.iter().map(|&ship_type| result.stats.frames_as(ship_type).to_string()));
+        lines.push(row.join(","));
+    }
+    lines.join("\n") + "\n"
+}
+
+fn mean(total: usize, count: usize) -> f64 {
+    if count == 0 { 0.0 } else { total as f64 / count as f64 }
+}
+
+/// The mean, the extremes and the percentiles (nearest rank) of `values`.
+fn distribution(mut values: Vec<usize>) -> Value {
+    if values.is_empty() {
+        return Value::Null;
+    }
+    values.sort_unstable();
+    let percentile = |p: usize| values[(p * values.len()).div_ceil(100).max(1) - 1];
+    json!({
+        "mean": mean(values.iter().sum(), values.len()),
+        "min": values[0],
+        "p10": percentile(10),
+        "p25": percentile(25),
+        "median": percentile(50),
+        "p75": percentile(75),
+        "p90": percentile(90),
+        "max": values[values.len() - 1],
+    })
+}
@@ -0,0 +1,285 @@
+use crate::boss::{Boss, Laser};
+use crate::effect::EffectKind;
+use crate::enemy::{Burst, Enemy, EnemyKind};
+use crate::fixed::Fixed;
+use crate::game_state::GameState;
+use crate::goodie::GoodieType;
+use crate::movement::{Movement, MovementPattern};
+use crate::net::ByteReader;
+use crate::player::Player;
+use crate::ship::{Ship, ShipType};
+use crate::shot::ShotType;
+use crate::spatial::Vec2;
+use crate::world::{Entity, Health, Sprite, World};
+
+const TAG_ENEMY: u8 = 0;
+const TAG_SHOT: u8 = 1;
+const TAG_GOODIE: u8 = 2;
+const TAG_SHIP_UPGRADE: u8 = 3;
+const TAG_POWER_UP: u8 = 4;
+
+const GOODIE_REPAIR_KIT: u8 = 0;
+const GOODIE_SHIELD_BOOST: u8 = 1;
+const GOODIE_BOMB: u8 = 2;
+
+/// What a spectator needs to know about a ship to draw it and its HUD.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayerSnapshot {
+    pub pos: Vec2,
+    pub ship_type: ShipType,
+    pub health: usize,
+    pub shield: usize,
+    pub score: usize,
+    pub bombs: usize,
+    pub charge: usize,
+    pub combo: usize,
+    pub multiplier: usize,
+    /// The active power-ups with their remaining frames.
+    pub effects: Vec<(EffectKind, usize)>,
+}
+
+/// An enemy, shot or goodie as it is drawn: its cell, sprite and health.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntitySnapshot {
+    pub pos: Vec2,
+    pub sprite: Sprite,
+    pub health: Option<Health>,
+    /// Whether the enemy is winding up or firing a burst.
+    pub bursting: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BossSnapshot {
+    pub pos: Vec2,
+    pub health: usize,
+    pub max_health: usize,
+    pub laser: Option<Laser>,
+}
+
+/// The drawable part of a [`GameState`] after a frame, sent to spectators.
+///
+/// It leaves out everything only the simulation needs (velocities, timers, the RNG), so it can't be simulated further,
+/// but [`Snapshot::restore`] turns it back into a state the usual drawing code renders.
+/// Encoded, all integers little endian, it is the frame (`u32`), the players (`u8` count), the entities (`u16` count)
+/// and the boss (`u8` flag), with positions as `i16` and most other values clamped to `u16`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    pub frame: usize,
+    pub players: Vec<PlayerSnapshot>,
+    pub entities: Vec<EntitySnapshot>,
+    pub boss: Option<BossSnapshot>,
+}
+
+impl Snapshot {
+    pub fn capture(game: &GameState) -> Self {
+        let players = game.players.iter().map(|player| PlayerSnapshot {
+            pos: player.ship.pos,
+            ship_type: player.ship.ship_type,
+            health: player.health,
+            shield: player.shield,
+            score: player.score,
+            bombs: player.bombs,
+            charge: player.ship.charge,
+            combo: player.scoring.combo,
+            multiplier: player.scoring.multiplier,
+            effects: player.effects.active().iter().map(|effect| (effect.kind, effect.remaining_frames)).collect(),
+        }).collect();
+        let entities = game.world.iter().map(|entity| EntitySnapshot {
+            pos: entity.position.cell,
+            sprite: entity.sprite,
+            health: entity.health,
+            bursting: entity.as_enemy().is_some_and(Enemy::is_bursting),
+        }).collect();
+        let boss = game.boss.as_ref().map(|boss| BossSnapshot {
+            pos: boss.pos,
+            health: boss.health,
+            max_health: boss.max_health,
+            laser: boss.laser,
+        });
+        Self { frame: game.frame, players, entities, boss }
+    }
+
+    /// Replaces the drawable part of `game` with the snapshot. `game` only serves for drawing afterwards.
+    pub fn restore(&self, game: &mut GameState) {
+        game.frame = self.frame;
+        game.players = self.players.iter().enumerate().map(|(i, snapshot)| {
+            let mut player = Player::new(Ship::new(i, snapshot.pos), &game.config);
+            player.ship.ship_type = snapshot.ship_type;
+            player.ship.charge = snapshot.charge;
+            player.health = snapshot.health;
+            player.shield = snapshot.shield;
+            player.score = snapshot.score;
+            player.bombs = snapshot.bombs;
+            player.scoring.combo = snapshot.combo;
+            player.scoring.multiplier = snapshot.multiplier;
+            for &(kind, remaining_frames) in &snapshot.effects {
+                player.effects.activate(kind, remaining_frames);
+            }
+            player
+        }).collect();
+        game.world = World::default();
+        for snapshot in &self.entities {
+            let mut entity = match snapshot.sprite {
+                Sprite::Enemy(kind) => {
+                    let mut enemy = Enemy::new(kind, snapshot.pos, Movement::single(MovementPattern::default()), 0);
+                    enemy.burst = snapshot.bursting.then_some(Burst { remaining_shots: 0, next_shot_frame: 0 });
+                    Entity::enemy(enemy)
+                }
+                Sprite::Shot(shot_type) => Entity::shot(snapshot.pos, Vec2::default(), shot_type),
+                Sprite::Goodie(goodie_type) => Entity::goodie(snapshot.pos, goodie_type, Fixed::ZERO),
+            };
+            entity.health = snapshot.health;
+            game.world.spawn(entity);
+        }
+        game.boss = self.boss.as_ref().map(|snapshot| {
+            let mut boss = Boss::new(0, &game.config.boss, game.dimension, self.frame);
+            boss.pos = snapshot.pos;
+            boss.health = snapshot.health;
+            boss.max_health = snapshot.max_health;
+            boss.laser = snapshot.laser;
+            boss
+        });
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend((self.frame as u32).to_le_bytes());
+        bytes.push(self.players.len() as u8);
+        for player in &self.players {
+            push_pos(&mut bytes, player.pos);
+            bytes.push(index_of(&ShipType::ALL, &player.ship_type));
+            for value in [player.health, player.shield] {
+                push_u16(&mut bytes, value);
+            }
+            bytes.extend((player.score.min(u32::MAX as usize) as u32).to_le_bytes());
+            for value in [player.bombs, player.charge, player.combo, player.multiplier] {
+                push_u16(&mut bytes, value);
+            }
+            bytes.push(player.effects.len() as u8);
+            for (kind, remaining_frames) in &player.effects {
+                bytes.push(index_of(&EffectKind::ALL, kind));
+                push_u16(&mut bytes, *remaining_frames);
+            }
+        }
+        push_u16(&mut bytes, self.entities.len());
+        for entity in &self.entities {
+            push_pos(&mut bytes, entity.pos);
+            bytes.extend(encode_sprite(entity.sprite));
+            bytes.push(entity.bursting as u8);
+            let (current, max) = entity.health.map_or((0, 0), |health| (health.current, health.max));
+            push_u16(&mut bytes, current);
+            push_u16(&mut bytes, max);
+        }
+        match &self.boss {
+            Some(boss) => {
+                bytes.push(1);
+                push_pos(&mut bytes, boss.pos);
+                push_u16(&mut bytes, boss.health);
+                push_u16(&mut bytes, boss.max_health);
+                match boss.laser {
+                    Some(laser) => {
+                        bytes.push(1);
+                        bytes.extend((laser.x as i16).to_le_bytes());
+                        bytes.extend((laser.direction as i16).to_le_bytes());
+                        push_u16(&mut bytes, laser.remaining_frames);
+                    }
+                    None => bytes.push(0),
+                }
+            }
+            None => bytes.push(0),
+        }
+        bytes
+    }
+
+    /// Decodes an encoded snapshot, `None` if it is incomplete or invalid.
+    pub fn decode(bytes: &[u8]) -> Option<Snapshot> {
+        let mut reader = ByteReader::new(bytes, 0);
+        let frame = reader.u32()? as usize;
+        let mut players = Vec::new();
+        for _ in 0..reader.u8()? {
+            let pos = read_pos(&mut reader)?;
+            let ship_type = *ShipType::ALL.get(reader.u8()? as usize)?;
+            let (health, shield) = (reader.u16()? as usize, reader.u16()? as usize);
+            let score = reader.u32()? as usize;
+            let (bombs, charge) = (reader.u16()? as usize, reader.u16()? as usize);
+            let (combo, multiplier) = (reader.u16()? as usize, reader.u16()? as usize);
+            let mut effects = Vec::new();
+            for _ in 0..reader.u8()? {
+                effects.push((*EffectKind::ALL.get(reader.u8()? as usize)?, reader.u16()? as usize));
+            }
+            players.push(PlayerSnapshot { pos, ship_type, health, shield, score, bombs, charge, combo, multiplier, effects });
+        }
+        let mut entities = Vec::new();
+        for _ in 0..reader.u16()? {
+            let pos = read_pos(&mut reader)?;
+            let sprite = decode_sprite(reader.u8()?, reader.u8()?)?;
+            let bursting = reader.u8()? != 0;
+            let (current, max) = (reader.u16()? as usize, reader.u16()? as usize);
+            let health = (max > 0).then_some(Health { current, max });
+            entities.push(EntitySnapshot { pos, sprite, health, bursting });
+        }
+        let boss = match reader.u8()? {
+            0 => None,
+            _ => {
+                let pos = read_pos(&mut reader)?;
+                let (health, max_health) = (reader.u16()? as usize, reader.u16()? as usize);
+                let laser = match reader.u8()? {
+                    0 => None,
+                    _ => Some(Laser {
+                        x: reader.i16()? as i32,
+                        direction: reader.i16()? as i32,
+                        remaining_frames: reader.u16()? as usize,
+                    }),
+                };
+                Some(BossSnapshot { pos, health, max_health, laser })
+            }
+        };
+        Some(Snapshot { frame, players, entities, boss })
+    }
+}
+
+fn push_u16(bytes: &mut Vec<u8>, value: usize) {
+    bytes.extend((value.min(u16::MAX as usize) as u16).to_le_bytes());
+}
+
+fn push_pos(bytes: &mut Vec<u8>, pos: Vec2) {
+    bytes.extend((pos.x as i16).to_le_bytes());
+    bytes.extend((pos.y as i16).to_le_bytes());
+}
+
+fn read_pos(reader: &mut ByteReader) -> Option<Vec2> {
+    Some(Vec2::xy(reader.i16()? as i32, reader.i16()? as i32))
+}
+
+fn index_of<T: PartialEq>(all: &[T], value: &T) -> u8 {
+    all.iter().position(|other| other == value).expect("every variant is listed") as u8
+}
+
+/// The sprite as a tag and an index into the variants of its type. The amounts of repair kits and shield boosts
+/// are left out, as they are not drawn.
+fn encode_sprite(sprite: Sprite) -> [u8; 2] {
+    match sprite {
+        Sprite::Enemy(kind) => [TAG_ENEMY, index_of(&EnemyKind::ALL, &kind)],
+        Sprite::Shot(shot_type) => [TAG_SHOT, index_of(&ShotType::ALL, &shot_type)],
+        Sprite::Goodie(GoodieType::RepairKit(_)) => [TAG_GOODIE, GOODIE_REPAIR_KIT],
+        Sprite::Goodie(GoodieType::ShieldBoost(_)) => [TAG_GOODIE, GOODIE_SHIELD_BOOST],
+        Sprite::Goodie(GoodieType::Bomb) => [TAG_GOODIE, GOODIE_BOMB],
+        Sprite::Goodie(GoodieType::ShipUpgrade(ship_type)) => [TAG_SHIP_UPGRADE, index_of(&ShipType::ALL, &ship_type)],
+        Sprite::Goodie(GoodieType::PowerUp(kind)) => [TAG_POWER_UP, index_of(&EffectKind::ALL, &kind)],
+    }
+}
+
+fn decode_sprite(tag: u8, index: u8) -> Option<Sprite> {
+    let index = index as usize;
+    let sprite = match (tag, index as u8) {
+        (TAG_ENEMY, _) => Sprite::Enemy(*EnemyKind::ALL.get(index)?),
+        (TAG_SHOT, _) => Sprite::Shot(*ShotType::ALL.get(index)?),
+        (TAG_GOODIE, GOODIE_REPAIR_KIT) => Sprite::Goodie(GoodieType::RepairKit(0)),
+        (TAG_GOODIE, GOODIE_SHIELD_BOOST) => Sprite::Goodie(GoodieType::ShieldBoost(0)),
+        (TAG_GOODIE, GOODIE_BOMB) => Sprite::Goodie(GoodieType::Bomb),
+        (TAG_SHIP_UPGRADE, _) => Sprite::Goodie(GoodieType::ShipUpgrade(*ShipType::ALL.get(index)?)),
+        (TAG_POWER_UP, _) => Sprite::Goodie(GoodieType::PowerUp(*EffectKind::ALL.get(index)?)),
+        _ => return None,
+    };
+    Some(sprite)
+}
@@ -0,0 +1,115 @@
+use std::ops::{Add, AddAssign, Sub};
+use serde::Deserialize;
+use crate::hitbox::Hitbox;
+
+/// A position or movement on the game field, measured in whole cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Deserialize)]
+pub struct Vec2 {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Vec2 {
+    pub fn zero() -> Vec2 {
+        Vec2 { x: 0, y: 0 }
+    }
+
+    pub fn xy(x: i32, y: i32) -> Vec2 {
+        Vec2 { x, y }
+    }
+
+    pub fn x(x: i32) -> Vec2 {
+        Vec2 { x, y: 0 }
+    }
+
+    pub fn y(y: i32) -> Vec2 {
+        Vec2 { x: 0, y }
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Vec2;
+
+    fn add(self, other: Vec2) -> Vec2 {
+        Vec2::xy(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Vec2;
+
+    fn sub(self, other: Vec2) -> Vec2 {
+        Vec2::xy(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl AddAssign for Vec2 {
+    fn add_assign(&mut self, other: Vec2) {
+        self.x += other.x;
+        self.y += other.y;
+    }
+}
+
+/// A uniform grid over the field, listing for each of its buckets the objects overlapping it.
+///
+/// It is rebuilt every frame, so collision queries only check the objects near an area instead of all of them.
+/// Objects outside the field are put into the buckets at its border.
+#[derive(Debug, Clone)]
+pub struct SpatialGrid {
+    bucket_size: i32,
+    columns: i32,
+    rows: i32,
+    buckets: Vec<Vec<usize>>,
+}
+
+impl SpatialGrid {
+    /// A grid covering a field of `dimension`, with square buckets of `bucket_size` cells.
+    pub fn new(dimension: Vec2, bucket_size: i32) -> Self {
+        let count = |length: i32| (length.max(1) as u32).div_ceil(bucket_size as u32) as i32;
+        let (columns, rows) = (count(dimension.x), count(dimension.y));
+        Self { bucket_size, columns, rows, buckets: vec![Vec::new(); (columns * rows) as usize] }
+    }
+
+    /// Replaces the content of the grid with `areas`, each one known by its position in the iterator.
+    pub fn rebuild(&mut self, areas: impl IntoIterator<Item = Hitbox>) {
+        self.clear();
+        for (index, area) in areas.into_iter().enumerate() {
+            self.insert(index, &area);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.buckets.iter_mut().for_each(Vec::clear);
+    }
+
+    pub fn insert(&mut self, index: usize, area: &Hitbox) {
+        let (first, last) = self.bucket_range(area);
+        for row in first.y..=last.y {
+            for column in first.x..=last.x {
+                self.buckets[(row * self.columns + column) as usize].push(index);
+            }
+        }
+    }
+
+    /// Collects the objects that might overlap `area` into `found`, in ascending order and without duplicates.
+    pub fn query(&self, area: &Hitbox, found: &mut Vec<usize>) {
+        found.clear();
+        let (first, last) = self.bucket_range(area);
+        for row in first.y..=last.y {
+            for column in first.x..=last.x {
+                found.extend(&self.buckets[(row * self.columns + column) as usize]);
+            }
+        }
+        found.sort_unstable();
+        found.dedup();
+    }
+
+    /// The first and the last bucket covered by `area`.
+    fn bucket_range(&self, area: &Hitbox) -> (Vec2, Vec2) {
+        let bucket = |pos: Vec2| Vec2::xy(
+            pos.x.div_euclid(self.bucket_size).clamp(0, self.columns - 1),
+            pos.y.div_euclid(self.bucket_size).clamp(0, self.rows - 1),
+        );
+        (bucket(area.pos), bucket(area.pos + area.size - Vec2::xy(1, 1)))
+    }
+}
@@ -0,0 +1,329 @@
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::time::{Duration, Instant};
+use crate::config::GameConfig;
+use crate::constant::{NET_HANDSHAKE_TIMEOUT_MILLIS, SPECTATOR_MAX_BACKLOG, SPECTATOR_MAX_MESSAGE_LEN};
+use crate::game_state::GameState;
+use crate::net::{ByteReader, NetError};
+use crate::VERSION;
+use crate::snapshot::Snapshot;
+
+/// Sent by TCP spectators to start watching, WebSocket spectators start with their HTTP upgrade request instead.
+pub const HELLO: &[u8; 6] = b"THRUST";
+const WEBSOCKET_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+enum Protocol {
+    /// Connected, but the first bytes telling the protocol didn't arrive yet.
+    Pending { since: Instant, request: Vec<u8> },
+    /// Every message is prefixed with its length (`u32`).
+    Tcp,
+    /// Every message is a binary WebSocket frame.
+    WebSocket,
+}
+
+struct Client {
+    stream: TcpStream,
+    protocol: Protocol,
+    /// What couldn't be written yet without blocking.
+    backlog: Vec<u8>,
+}
+
+impl Client {
+    fn queue(&mut self, message: &[u8]) {
+        match self.protocol {
+            Protocol::Pending { .. } => {}
+            Protocol::Tcp => {
+                self.backlog.extend((message.len() as u32).to_le_bytes());
+                self.backlog.extend_from_slice(message);
+            }
+            Protocol::WebSocket => {
+                // a final binary frame, unmasked as sent by a server
+                self.backlog.push(0x82);
+                match message.len() {
+                    len if len < 126 => self.backlog.push(len as u8),
+                    len if len <= u16::MAX as usize => {
+                        self.backlog.push(126);
+                        self.backlog.extend((len as u16).to_be_bytes());
+                    }
+                    len => {
+                        self.backlog.push(127);
+                        self.backlog.extend((len as u64).to_be_bytes());
+                    }
+                }
+                self.backlog.extend_from_slice(message);
+            }
+        }
+    }
+
+    /// Writes as much of the backlog as possible, fails if the spectator is gone or can't keep up.
+    fn flush(&mut self) -> io::Result<()> {
+        while !self.backlog.is_empty() {
+            match self.stream.write(&self.backlog) {
+                Ok(0) => return Err(io::ErrorKind::WriteZero.into()),
+                Ok(len) => { self.backlog.drain(..len); }
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                Err(err) => return Err(err),
+            }
+        }
+        if self.backlog.len() > SPECTATOR_MAX_BACKLOG {
+            return Err(io::Error::other("spectator can't keep up"));
+        }
+        Ok(())
+    }
+
+    /// Reads what the spectator sent, which only matters during the handshake. Fails once the spectator is gone.
+    fn read(&mut self) -> io::Result<Vec<u8>> {
+        let mut received = Vec::new();
+        let mut chunk = [0; 1024];
+        loop {
+            match self.stream.read(&mut chunk) {
+                Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+                Ok(len) => received.extend_from_slice(&chunk[..len]),
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => return Ok(received),
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Picks the protocol from the first bytes and answers the handshake. `Ok(false)` while they are still missing.
+    fn handshake(&mut self, header: &[u8]) -> io::Result<bool> {
+        let received = self.read()?;
+        let Protocol::Pending { since, request } = &mut self.protocol else { return Ok(true); };
+        request.extend(received);
+        if request.starts_with(HELLO) {
+            self.protocol = Protocol::Tcp;
+        } else if request.starts_with(b"GET ") && request.windows(4).any(|window| window == b"\r\n\r\n") {
+            let key = websocket_key(request)
+                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing Sec-WebSocket-Key"))?;
+            let response = format!(
+                "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+                websocket_accept(&key),
+            );
+            self.backlog.extend(response.as_bytes());
+            self.protocol = Protocol::WebSocket;
+        } else if (request.len() >= HELLO.len() && !request.starts_with(b"GET ")) || request.len() > 8192 {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "unknown protocol"));
+        } else if since.elapsed() > Duration::from_millis(NET_HANDSHAKE_TIMEOUT_MILLIS) {
+            return Err(io::ErrorKind::TimedOut.into());
+        } else {
+            return Ok(false);
+        }
+        self.queue(header);
+        Ok(true)
+    }
+}
+
+/// Broadcasts a [`Snapshot`] of every frame to any number of spectators, connected by TCP or WebSocket.
+///
+/// Both receive the same messages: first a header with the stream version (`u8` length + UTF-8)
+/// and the game config (`u32` length + TOML), then one encoded snapshot per frame.
+/// Spectators that disconnect or fall too far behind are dropped without affecting the game.
+pub struct SpectatorServer {
+    listener: TcpListener,
+    clients: Vec<Client>,
+}
+
+impl SpectatorServer {
+    /// Listens on `port` of all interfaces, 0 picks a free port.
+    pub fn bind(port: u16) -> Result<SpectatorServer, NetError> {
+        let listener = TcpListener::bind(("0.0.0.0", port))?;
+        listener.set_nonblocking(true)?;
+        Ok(SpectatorServer { listener, clients: Vec::new() })
+    }
+
+    pub fn local_addr(&self) -> Result<SocketAddr, NetError> {
+        Ok(self.listener.local_addr()?)
+    }
+
+    /// The number of spectators currently watching.
+    pub fn spectators(&self) -> usize {
+        self.clients.iter().filter(|client| !matches!(client.protocol, Protocol::Pending { .. })).count()
+    }
+
+    /// Accepts new spectators and sends the current frame of `game` to all of them without blocking.
+    pub fn broadcast(&mut self, game: &GameState) {
+        while let Ok((stream, _)) = self.listener.accept() {
+            if stream.set_nonblocking(true).is_ok() && stream.set_nodelay(true).is_ok() {
+                let protocol = Protocol::Pending { since: Instant::now(), request: Vec::new() };
+                self.clients.push(Client { stream, protocol, backlog: Vec::new() });
+            }
+        }
+        let pending = self.clients.iter().any(|client| matches!(client.protocol, Protocol::Pending { .. }));
+        let header = if pending { encode_header(&game.config) } else { Vec::new() };
+        let snapshot = Snapshot::capture(game).encode();
+        self.clients.retain_mut(|client| {
+            let ready = match client.protocol {
+                Protocol::Pending { .. } => client.handshake(&header),
+                _ => client.read().map(|_| true),
+            };
+            match ready {
+                Ok(true) => {
+                    client.queue(&snapshot);
+                    client.flush().is_ok()
+                }
+                Ok(false) => true,
+                Err(_) => false,
+            }
+        });
+    }
+}
+
+fn encode_header(config: &GameConfig) -> Vec<u8> {
+    let config = config.to_toml();
+    let mut header = vec![VERSION.len() as u8];
+    header.extend(VERSION.as_bytes());
+    header.extend((config.len() as u32).to_le_bytes());
+    header.extend(config.as_bytes());
+    header
+}
+
+/// Watches the stream of a [`SpectatorServer`] over TCP.
+pub struct Spectator {
+    stream: TcpStream,
+    buffer: Vec<u8>,
+    /// The config of the watched game, needed to draw it.
+    pub config: GameConfig,
+}
+
+impl Spectator {
+    /// Connects to the server at `addr` and waits for the header of the stream.
+    pub fn connect(addr: &str) -> Result<Spectator, NetError> {
+        let timeout = Duration::from_millis(NET_HANDSHAKE_TIMEOUT_MILLIS);
+        let addr = addr.to_socket_addrs()?.next()
+            .ok_or_else(|| NetError::Io(io::Error::new(io::ErrorKind::NotFound, "no address found")))?;
+        let mut stream = TcpStream::connect_timeout(&addr, timeout)?;
+        stream.set_read_timeout(Some(timeout))?;
+        stream.write_all(HELLO)?;
+        let mut len = [0; 4];
+        stream.read_exact(&mut len)?;
+        let len = checked_len(len, "stream header")?;
+        let mut header = vec![0; len];
+        stream.read_exact(&mut header)?;
+        let config = decode_header(&header)?;
+        stream.set_nonblocking(true)?;
+        Ok(Spectator { stream, buffer: Vec::new(), config })
+    }
+
+    /// Reads what arrived so far and returns the latest complete snapshot, if a new one arrived.
+    /// Once the server closed the stream, the snapshots still buffered are returned before it counts as disconnected.
+    pub fn poll(&mut self) -> Result<Option<Snapshot>, NetError> {
+        let mut chunk = [0; 4096];
+        let mut closed = false;
+        loop {
+            match self.stream.read(&mut chunk) {
+                Ok(0) => {
+                    closed = true;
+                    break;
+                }
+                Ok(len) => self.buffer.extend_from_slice(&chunk[..len]),
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+        let mut latest = None;
+        while let Some(len) = self.buffer.get(..4) {
+            let len = checked_len(len.try_into().expect("4 bytes"), "snapshot")?;
+            if self.buffer.len() < 4 + len {
+                break;
+            }
+            let snapshot = Snapshot::decode(&self.buffer[4..4 + len])
+                .ok_or_else(|| NetError::Protocol("invalid snapshot".to_string()))?;
+            latest = Some(snapshot);
+            self.buffer.drain(..4 + len);
+        }
+        if closed && latest.is_none() {
+            return Err(NetError::Disconnected);
+        }
+        Ok(latest)
+    }
+}
+
+/// The length of a message from its 4 byte prefix, refused when it is too large to come from a thrust server.
+fn checked_len(len: [u8; 4], what: &str) -> Result<usize, NetError> {
+    let len = u32::from_le_bytes(len) as usize;
+    if len > SPECTATOR_MAX_MESSAGE_LEN {
+        return Err(NetError::Protocol(format!("{} of {} bytes is too large", what, len)));
+    }
+    Ok(len)
+}
+
+fn decode_header(header: &[u8]) -> Result<GameConfig, NetError> {
+    let mut reader = ByteReader::new(header, 0);
+    let invalid = || NetError::Protocol("invalid stream header".to_string());
+    let version = reader.u8().and_then(|len| reader.string(len as usize)).ok_or_else(invalid)?;
+    if version != VERSION {
+        return Err(NetError::Rejected(format!("the game runs version {}, but this is version {}", version, VERSION)));
+    }
+    let config = reader.u32().and_then(|len| reader.string(len as usize)).ok_or_else(invalid)?;
+    GameConfig::from_toml(&config).map_err(|err| NetError::Protocol(format!("invalid game config: {}", err)))
+}
+
+/// The value of the `Sec-WebSocket-Key` header of an HTTP upgrade request.
+fn websocket_key(request: &[u8]) -> Option<String> {
+    String::from_utf8_lossy(request).lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("Sec-WebSocket-Key"))
+        .map(|(_, value)| value.trim().to_string())
+}
+
+/// The `Sec-WebSocket-Accept` answer to `key`, see RFC 6455 section 4.2.2.
+pub fn websocket_accept(key: &str) -> String {
+    base64(&sha1(format!("{}{}", key, WEBSOCKET_GUID).as_bytes()))
+}
+
+/// SHA-1, only used for the WebSocket handshake.
+fn sha1(message: &[u8]) -> [u8; 20] {
+    let mut state: [u32; 5] = [0x6745_2301, 0xEFCD_AB89, 0x98BA_DCFE, 0x1032_5476, 0xC3D2_E1F0];
+    let mut padded = message.to_vec();
+    padded.push(0x80);
+    while padded.len() % 64 != 56 {
+        padded.push(0);
+    }
+    padded.extend((message.len() as u64 * 8).to_be_bytes());
+    for block in padded.chunks(64) {
+        let mut words = [0u32; 80];
+        for (i, word) in block.chunks(4).enumerate() {
+            words[i] = u32::from_be_bytes(word.try_into().expect("4 bytes"));
+        }
+        for i in 16..80 {
+            words[i] = (words[i - 3] ^ words[i - 8] ^ words[i - 14] ^ words[i - 16]).rotate_left(1);
+        }
+        let [mut a, mut b, mut c, mut d, mut e] = state;
+        for (i, word) in words.iter().enumerate() {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5A82_7999),
+                20..=39 => (b ^ c ^ d, 0x6ED9_EBA1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1B_BCDC),
+                _ => (b ^ c ^ d, 0xCA62_C1D6),
+            };
+            let temp = a.rotate_left(5).wrapping_add(f).wrapping_add(e).wrapping_add(k).wrapping_add(*word);
+            (e, d, c, b, a) = (d, c, b.rotate_left(30), a, temp);
+        }
+        for (value, add) in state.iter_mut().zip([a, b, c, d, e]) {
+            *value = value.wrapping_add(add);
+        }
+    }
+    let mut digest = [0; 20];
+    for (i, value) in state.iter().enumerate() {
+        digest[i * 4..i * 4 + 4].copy_from_slice(&value.to_be_bytes());
+    }
+    digest
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::new();
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (i, byte)| group | ((*byte as u32) << (16 - 8 * i)));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[((group >> (18 - 6 * i)) & 0x3F) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
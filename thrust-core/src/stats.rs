@@ -0,0 +1,77 @@
+use std::collections::BTreeMap;
+use crate::goodie::GoodieType;
+use crate::ship::ShipType;
+
+/// What damaged a ship.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DamageCause {
+    /// The ship ran into an enemy.
+    Collision,
+    /// An enemy got past the ships, which damages all of them.
+    EnemyReachedGround,
+    /// A shot or missile of an enemy or the boss.
+    Shot,
+    /// Touching the boss or its laser.
+    Boss,
+}
+
+impl DamageCause {
+    pub const ALL: [DamageCause; 4] = [
+        DamageCause::Collision,
+        DamageCause::EnemyReachedGround,
+        DamageCause::Shot,
+        DamageCause::Boss,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            DamageCause::Collision => "collision",
+            DamageCause::EnemyReachedGround => "enemy_reached_ground",
+            DamageCause::Shot => "shot",
+            DamageCause::Boss => "boss",
+        }
+    }
+
+    fn index(&self) -> usize {
+        DamageCause::ALL.iter().position(|cause| cause == self).expect("every cause is listed")
+    }
+}
+
+/// Counts what happened to a player during a game. It doesn't affect the game, but tells how it went for balancing.
+#[derive(Debug, Clone, Default)]
+pub struct PlayerStats {
+    damage: [usize; 4],
+    ship_type_frames: [usize; 3],
+    /// The collected goodies by [`GoodieType::name`].
+    pub goodies_collected: BTreeMap<&'static str, usize>,
+    /// The cause of the damage that destroyed the ship.
+    pub destroyed_by: Option<DamageCause>,
+}
+
+impl PlayerStats {
+    /// The damage taken from `cause`, including what the shield absorbed.
+    pub fn damage(&self, cause: DamageCause) -> usize {
+        self.damage[cause.index()]
+    }
+
+    /// The number of frames the ship flew as `ship_type`.
+    pub fn frames_as(&self, ship_type: ShipType) -> usize {
+        self.ship_type_frames[ship_type_index(ship_type)]
+    }
+
+    pub(crate) fn record_damage(&mut self, cause: DamageCause, damage: usize) {
+        self.damage[cause.index()] += damage;
+    }
+
+    pub(crate) fn record_frame(&mut self, ship_type: ShipType) {
+        self.ship_type_frames[ship_type_index(ship_type)] += 1;
+    }
+
+    pub(crate) fn record_goodie(&mut self, goodie_type: GoodieType) {
+        *self.goodies_collected.entry(goodie_type.name()).or_default() += 1;
+    }
+}
+
+fn ship_type_index(ship_type: ShipType) -> usize {
+    ShipType::ALL.iter().position(|other| *other == ship_type).expect("every ship type is listed")
+}
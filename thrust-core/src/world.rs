@@ -0,0 +1,402 @@
+//! The entities populating the field and the components they are made of.
+//!
+//! Enemies, shots and goodies are all [`Entity`]s. Moving, damaging and despawning them are shared systems
+//! working on their components, so a new kind of object only needs a new [`Role`] and constructor.
+
+use std::ops::{Index, IndexMut};
+use std::slice;
+use crate::boss::Boss;
+use crate::config::{DamageConfig, Glyphs};
+use crate::constant::BORDER_SIZE;
+use crate::enemy::{Enemy, EnemyKind};
+use crate::fixed::{Fixed, FixedVec2};
+use crate::goodie::GoodieType;
+use crate::hitbox::{Hitbox, Sweep};
+use crate::shot::{Shot, ShotType};
+use crate::spatial::{SpatialGrid, Vec2};
+
+/// The side an entity fights on. Shots only damage entities of the opposing faction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Faction {
+    /// The ship's shots.
+    Player,
+    /// Enemies and everything they fire.
+    Enemy,
+    /// Goodies, which nobody fights over.
+    Neutral,
+}
+
+impl Faction {
+    pub fn opposes(self, other: Faction) -> bool {
+        matches!((self, other), (Faction::Player, Faction::Enemy) | (Faction::Enemy, Faction::Player))
+    }
+}
+
+/// Where an entity is: exactly, and the cell it is drawn in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub cell: Vec2,
+    pub exact: FixedVec2,
+}
+
+impl Position {
+    pub fn at(cell: Vec2) -> Self {
+        Self { cell, exact: FixedVec2::from_cell(cell) }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Health {
+    pub current: usize,
+    pub max: usize,
+}
+
+impl Health {
+    pub fn full(max: usize) -> Self {
+        Self { current: max, max }
+    }
+
+    /// Applies `damage` and returns whether the entity got destroyed.
+    pub fn hit(&mut self, damage: usize) -> bool {
+        self.current = self.current.saturating_sub(damage);
+        self.current == 0
+    }
+}
+
+/// What frontends draw for an entity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sprite {
+    Enemy(EnemyKind),
+    Shot(ShotType),
+    Goodie(GoodieType),
+}
+
+impl Sprite {
+    pub fn character(&self, glyphs: &Glyphs) -> char {
+        match self {
+            Sprite::Enemy(kind) => kind.character(glyphs),
+            Sprite::Shot(shot_type) => shot_type.character(glyphs),
+            Sprite::Goodie(goodie_type) => goodie_type.character(glyphs),
+        }
+    }
+}
+
+/// The area an entity stays in. It is despawned once it leaves it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bounds {
+    /// Between the borders of the field, like shots.
+    Field,
+    /// Above the ground, which falling goodies vanish into.
+    AboveGround,
+    /// Anywhere, for entities the game rules take care of, like enemies reaching the ground.
+    Unbounded,
+}
+
+impl Bounds {
+    pub fn contains(&self, pos: Vec2, dimension: Vec2) -> bool {
+        match self {
+            Bounds::Field => 0 < pos.x && pos.x < dimension.x && 0 < pos.y && pos.y < dimension.y - BORDER_SIZE,
+            Bounds::AboveGround => pos.y < dimension.y - BORDER_SIZE,
+            Bounds::Unbounded => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lifetime {
+    /// Frames the entity has been moving.
+    pub age: usize,
+    pub bounds: Bounds,
+}
+
+impl Lifetime {
+    pub fn new(bounds: Bounds) -> Self {
+        Self { age: 0, bounds }
+    }
+}
+
+/// The state only the systems of one kind of entity care about.
+#[derive(Hash)]
+pub enum Role {
+    /// Boxed, as it is much bigger than the other roles and would bloat every entity.
+    Enemy(Box<Enemy>),
+    Shot(Shot),
+    Goodie(GoodieType),
+}
+
+pub struct Entity {
+    pub position: Position,
+    /// The movement per frame, in cells.
+    pub velocity: FixedVec2,
+    /// The size of the hitbox, whose top left cell is the entity's cell.
+    pub size: Vec2,
+    /// Entities without health can't be damaged.
+    pub health: Option<Health>,
+    pub sprite: Sprite,
+    pub lifetime: Lifetime,
+    pub faction: Faction,
+    pub role: Role,
+}
+
+impl Entity {
+    /// An entity for `enemy`, placed at the cell its movement starts from.
+    pub fn enemy(enemy: Enemy) -> Self {
+        Self {
+            position: Position::at(enemy.target),
+            velocity: FixedVec2::default(),
+            size: Vec2::xy(1, 1),
+            health: Some(Health::full(enemy.kind.max_health())),
+            sprite: Sprite::Enemy(enemy.kind),
+            lifetime: Lifetime::new(Bounds::Unbounded),
+            faction: Faction::Enemy,
+            role: Role::Enemy(Box::new(enemy)),
+        }
+    }
+
+    /// A shot moving by whole cells each frame.
+    pub fn shot(pos: Vec2, movement: Vec2, shot_type: ShotType) -> Self {
+        Entity::shot_with_velocity(pos, FixedVec2::from_cell(movement), shot_type)
+    }
+
+    /// A shot moving by `velocity` each frame. Missiles have a single point of health, so any hit shoots them down.
+    pub fn shot_with_velocity(pos: Vec2, velocity: FixedVec2, shot_type: ShotType) -> Self {
+        Self {
+            position: Position::at(pos),
+            velocity,
+            size: Vec2::xy(1, 1),
+            health: (shot_type == ShotType::Missile).then(|| Health::full(1)),
+            sprite: Sprite::Shot(shot_type),
+            lifetime: Lifetime::new(Bounds::Field),
+            faction: shot_type.faction(),
+            role: Role::Shot(Shot::new(shot_type)),
+        }
+    }
+
+    /// A shot flying from `pos` towards `target` with `speed` cells per frame.
+    pub fn aimed_shot(pos: Vec2, target: Vec2, speed: Fixed, shot_type: ShotType) -> Self {
+        let velocity = FixedVec2::towards(FixedVec2::from_cell(pos), FixedVec2::from_cell(target), speed);
+        Entity::shot_with_velocity(pos, velocity, shot_type)
+    }
+
+    /// Marks a shot as fired by `player`, who gets the points for what it destroys.
+    pub fn fired_by(mut self, player: usize) -> Self {
+        if let Role::Shot(shot) = &mut self.role {
+            shot.owner = Some(player);
+        }
+        self
+    }
+
+    /// A goodie falling `speed` cells per frame.
+    pub fn goodie(pos: Vec2, goodie_type: GoodieType, speed: Fixed) -> Self {
+        Self {
+            position: Position::at(pos),
+            velocity: FixedVec2::new(Fixed::ZERO, speed),
+            size: Vec2::xy(1, 1),
+            health: None,
+            sprite: Sprite::Goodie(goodie_type),
+            lifetime: Lifetime::new(Bounds::AboveGround),
+            faction: Faction::Neutral,
+            role: Role::Goodie(goodie_type),
+        }
+    }
+
+    pub fn as_enemy(&self) -> Option<&Enemy> {
+        match &self.role {
+            Role::Enemy(enemy) => Some(enemy),
+            _ => None,
+        }
+    }
+
+    pub fn as_shot(&self) -> Option<&Shot> {
+        match &self.role {
+            Role::Shot(shot) => Some(shot),
+            _ => None,
+        }
+    }
+
+    pub fn as_goodie(&self) -> Option<&GoodieType> {
+        match &self.role {
+            Role::Goodie(goodie_type) => Some(goodie_type),
+            _ => None,
+        }
+    }
+
+    pub fn hitbox(&self) -> Hitbox {
+        Hitbox::new(self.position.cell, self.size)
+    }
+
+    /// The way the entity moved during its last update.
+    pub fn sweep(&self) -> Sweep {
+        Sweep::new(self.position.exact - self.velocity, self.position.exact, self.size)
+    }
+
+    /// Turns the entity a quarter of the way towards `target`, keeping roughly `speed` cells per frame.
+    pub fn steer(&mut self, target: Vec2, speed: Fixed) {
+        let wanted = FixedVec2::towards(self.position.exact, FixedVec2::from_cell(target), speed);
+        self.velocity = (self.velocity * 3 + wanted) / 4;
+    }
+}
+
+/// What the shots hit during one run of the damage system.
+pub struct Hits {
+    /// The destroyed entities in the order they were destroyed, each with the owner of the shot that destroyed it.
+    pub destroyed: Vec<(Entity, Option<usize>)>,
+    pub boss_destroyed: bool,
+    /// The owner of the shot that destroyed the boss.
+    pub boss_destroyed_by: Option<usize>,
+}
+
+/// All entities on the field, in the order they were spawned.
+#[derive(Default)]
+pub struct World {
+    entities: Vec<Entity>,
+}
+
+impl World {
+    pub fn spawn(&mut self, entity: Entity) {
+        self.entities.push(entity);
+    }
+
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    pub fn iter(&self) -> slice::Iter<'_, Entity> {
+        self.entities.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, Entity> {
+        self.entities.iter_mut()
+    }
+
+    pub fn enemies(&self) -> impl Iterator<Item = &Entity> {
+        self.entities.iter().filter(|entity| entity.as_enemy().is_some())
+    }
+
+    /// The shots fired by `faction`.
+    pub fn shots(&self, faction: Faction) -> impl Iterator<Item = &Entity> {
+        self.entities.iter().filter(move |entity| entity.as_shot().is_some() && entity.faction == faction)
+    }
+
+    pub fn goodies(&self) -> impl Iterator<Item = &Entity> {
+        self.entities.iter().filter(|entity| entity.as_goodie().is_some())
+    }
+
+    /// Keeps only the entities `keep` returns true for.
+    pub fn retain(&mut self, keep: impl FnMut(&Entity) -> bool) {
+        self.entities.retain(keep);
+    }
+
+    /// The movement system: moves every entity by its velocity and ages it by a frame.
+    pub fn move_entities(&mut self) {
+        for entity in &mut self.entities {
+            entity.position.exact += entity.velocity;
+            entity.position.cell = entity.position.exact.cell();
+            entity.lifetime.age += 1;
+        }
+    }
+
+    /// The despawn system: removes the entities marked in `removed` and those that left their bounds
+    /// on a field of `dimension`, keeping the order of the others.
+    pub fn despawn(&mut self, removed: &[bool], dimension: Vec2) {
+        let mut index = 0;
+        self.entities.retain(|entity| {
+            index += 1;
+            !removed[index - 1] && entity.lifetime.bounds.contains(entity.position.cell, dimension)
+        });
+    }
+
+    /// The damage system: every shot damages the `boss` and the entities with health of the opposing faction it met
+    /// during the frame. Destroyed entities and shots that hit something, unless they pierce, are removed.
+    /// `grid` is rebuilt with the entities that can be damaged.
+    pub fn resolve_hits(&mut self, mut boss: Option<&mut Boss>, damage: &DamageConfig, grid: &mut SpatialGrid) -> Hits {
+        let mut target_factions = Vec::new();
+        grid.clear();
+        for (index, entity) in self.entities.iter().enumerate() {
+            if entity.health.is_some() {
+                grid.insert(index, &entity.sweep().bounds());
+                if !target_factions.contains(&entity.faction) {
+                    target_factions.push(entity.faction);
+                }
+            }
+        }
+        if boss.is_some() && !target_factions.contains(&Faction::Enemy) {
+            target_factions.push(Faction::Enemy);
+        }
+        let mut removed = vec![false; self.entities.len()];
+        let mut destroyed = Vec::new();
+        let mut boss_destroyed = false;
+        let mut boss_destroyed_by = None;
+        let mut candidates = Vec::new();
+        for index in 0..self.entities.len() {
+            let shooter = &self.entities[index];
+            let Some((shot_type, owner)) = shooter.as_shot().map(|shot| (shot.shot_type, shot.owner)) else { continue; };
+            let (faction, sweep) = (shooter.faction, shooter.sweep());
+            if removed[index] || !target_factions.iter().any(|target| faction.opposes(*target)) {
+                continue;
+            }
+            let shot_damage = shot_type.damage(damage);
+            let mut hit = false;
+            if let Some(boss) = boss.as_deref_mut().filter(|boss| faction.opposes(Faction::Enemy) && sweep.intersects(&boss.hitbox())) {
+                hit = true;
+                if !boss_destroyed && boss.hit(shot_damage) {
+                    boss_destroyed = true;
+                    boss_destroyed_by = owner;
+                }
+            }
+            grid.query(&sweep.bounds(), &mut candidates);
+            for &target_index in &candidates {
+                let target = &mut self.entities[target_index];
+                if removed[target_index] || !faction.opposes(target.faction) || !sweep.hits(&target.sweep()) {
+                    continue;
+                }
+                hit = true;
+                if target.health.as_mut().is_some_and(|health| health.hit(shot_damage)) {
+                    removed[target_index] = true;
+                    destroyed.push((target_index, owner));
+                }
+            }
+            removed[index] |= hit && !shot_type.is_piercing();
+        }
+        if destroyed.is_empty() {
+            let mut index = 0;
+            self.entities.retain(|_| {
+                index += 1;
+                !removed[index - 1]
+            });
+            return Hits { destroyed: Vec::new(), boss_destroyed, boss_destroyed_by };
+        }
+        let mut slots: Vec<Option<Entity>> = self.entities.drain(..).map(Some).collect();
+        let destroyed = destroyed.into_iter()
+            .map(|(index, owner)| (slots[index].take().expect("entities are destroyed only once"), owner))
+            .collect();
+        self.entities = slots.into_iter().zip(removed)
+            .filter_map(|(entity, removed)| entity.filter(|_| !removed))
+            .collect();
+        Hits { destroyed, boss_destroyed, boss_destroyed_by }
+    }
+}
+
+impl Index<usize> for World {
+    type Output = Entity;
+
+    fn index(&self, index: usize) -> &Entity {
+        &self.entities[index]
+    }
+}
+
+impl IndexMut<usize> for World {
+    fn index_mut(&mut self, index: usize) -> &mut Entity {
+        &mut self.entities[index]
+    }
+}
+
+impl Extend<Entity> for World {
+    fn extend<T: IntoIterator<Item = Entity>>(&mut self, entities: T) {
+        self.entities.extend(entities);
+    }
+}
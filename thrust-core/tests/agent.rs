@@ -0,0 +1,99 @@
+use serde_json::Value;
+use thrust_core::action::Action;
+use thrust_core::agent::{self, Env, EntityKind};
+use thrust_core::config::GameConfig;
+
+fn play(env: &mut Env, frames: usize) -> i64 {
+    let mut reward = 0;
+    for frame in 0..frames {
+        let actions = [if frame / 20 % 2 == 0 { Action::MoveLeft } else { Action::MoveRight }, Action::Fire];
+        let step = env.step(&actions);
+        reward += step.reward;
+        if step.done {
+            break;
+        }
+    }
+    reward
+}
+
+#[test]
+fn same_seed_and_actions_give_the_same_game() {
+    let mut env = Env::new(GameConfig::default());
+    env.reset(5);
+    let reward = play(&mut env, 500);
+    let observation = env.step(&[]).observation;
+    env.reset(5);
+    assert_eq!(play(&mut env, 500), reward);
+    assert_eq!(env.step(&[]).observation, observation);
+    assert!(observation.entities.iter().any(|entity| entity.kind == EntityKind::Enemy));
+}
+
+#[test]
+fn reward_is_the_score_minus_the_damage_taken() {
+    let mut env = Env::new(GameConfig::default());
+    let mut ship = env.reset(9).ship;
+    let (mut reward, mut damage) = (0, 0);
+    for frame in 0..3000 {
+        let step = env.step(&[if frame / 20 % 2 == 0 { Action::MoveLeft } else { Action::MoveRight }, Action::Fire]);
+        damage += (ship.health + ship.shield).saturating_sub(step.observation.ship.health + step.observation.ship.shield) as i64;
+        reward += step.reward;
+        ship = step.observation.ship;
+    }
+    assert!(ship.score > 0 && damage > 0);
+    assert_eq!(reward, ship.score as i64 - damage);
+}
+
+#[test]
+fn game_ends_when_the_ship_is_destroyed() {
+    let mut env = Env::new(GameConfig::from_toml("initial_health = 1").unwrap());
+    env.reset(3);
+    let steps = (0..100_000).take_while(|_| !env.step(&[]).done).count();
+    assert!(steps < 100_000);
+    assert!(env.is_done());
+}
+
+#[test]
+fn json_lines_protocol_answers_every_request() {
+    let requests = [
+        r#"{"cmd": "reset", "seed": 7}"#,
+        r#"{"cmd": "step", "actions": ["move_left", "fire"], "repeat": 4}"#,
+        r#"{"cmd": "step"}"#,
+        "",
+        r#"{"cmd": "jump"}"#,
+    ];
+    let mut output = Vec::new();
+    agent::serve(&mut Env::new(GameConfig::default()), requests.join("\n").as_bytes(), &mut output).unwrap();
+    let answers: Vec<Value> = String::from_utf8(output).unwrap().lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+    assert_eq!(answers.len(), 4);
+    assert_eq!(answers[0]["observation"]["frame"], 0);
+    assert_eq!(answers[1]["observation"]["frame"], 4);
+    assert_eq!(answers[1]["observation"]["ship"]["x"].as_i64().unwrap() + 8, answers[0]["observation"]["ship"]["x"].as_i64().unwrap());
+    assert_eq!(answers[2]["observation"]["frame"], 5);
+    assert_eq!(answers[2]["done"], false);
+    assert!(answers[3]["error"].as_str().unwrap().starts_with("invalid request"));
+}
+
+#[test]
+fn finished_game_is_not_played_any_further() {
+    let config = GameConfig::from_toml("initial_health = 1").unwrap();
+    let mut env = Env::new(config.clone());
+    env.reset(3);
+    let last = (0..100_000).map(|_| env.step(&[])).find(|step| step.done).unwrap();
+    let after = env.step(&[Action::MoveLeft]);
+    assert_eq!((after.observation, after.reward, after.done), (last.observation.clone(), 0, true));
+
+    let mut env = Env::new(config);
+    env.handle_request(r#"{"cmd": "reset", "seed": 3}"#).unwrap();
+    let step = env.handle_request(r#"{"cmd": "step", "repeat": 100000}"#).unwrap();
+    assert_eq!((step.observation.frame, step.done), (last.observation.frame, true));
+    assert!(matches!(env.handle_request(r#"{"cmd": "step"}"#), Err(agent::AgentError::GameOver)));
+}
+
+#[test]
+fn random_reset_tells_its_seed() {
+    let mut env = Env::new(GameConfig::default());
+    let random = env.handle_request(r#"{"cmd": "reset"}"#).unwrap().observation;
+    let first = env.step(&[Action::Fire]).observation;
+    assert_eq!(env.reset(random.seed), random);
+    assert_eq!(env.step(&[Action::Fire]).observation, first);
+}
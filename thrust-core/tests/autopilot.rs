@@ -0,0 +1,56 @@
+mod common;
+
+use thrust_core::autopilot;
+use thrust_core::config::GameConfig;
+use thrust_core::fixed::Fixed;
+use thrust_core::game_state::GameState;
+use thrust_core::goodie::GoodieType;
+use thrust_core::input::FrameInput;
+use thrust_core::shot::ShotType;
+use thrust_core::spatial::Vec2;
+use thrust_core::world::Entity;
+use common::quiet_game;
+
+/// Lets the autopilot fly the ship for `frames` frames.
+fn fly(game: &mut GameState, frames: usize) {
+    for _ in 0..frames {
+        if game.is_game_over() {
+            break;
+        }
+        let input = FrameInput::from_actions(autopilot::actions(game, 0));
+        game.update(&[input]);
+    }
+}
+
+#[test]
+fn autopilot_dodges_a_shot_falling_onto_the_ship() {
+    let mut game = quiet_game();
+    let ship = game.players[0].ship.pos;
+    game.world.spawn(Entity::shot(ship + Vec2::xy(1, -6), Vec2::y(1), ShotType::Enemy));
+    fly(&mut game, 10);
+    assert_eq!(game.players[0].health, game.config.initial_health);
+}
+
+#[test]
+fn autopilot_catches_a_wanted_goodie() {
+    let mut game = quiet_game();
+    game.players[0].health = game.config.max_health / 2;
+    let ship = game.players[0].ship.pos;
+    game.world.spawn(Entity::goodie(ship + Vec2::xy(8, -12), GoodieType::RepairKit(20), Fixed::ONE));
+    fly(&mut game, 20);
+    assert_eq!(game.players[0].health, game.config.max_health / 2 + 20);
+}
+
+#[test]
+fn autopilot_outlives_and_outscores_an_idle_ship() {
+    for seed in 0..3 {
+        let mut idle = GameState::with_seed(GameConfig::default(), seed);
+        while !idle.is_game_over() {
+            idle.update(&[]);
+        }
+        let mut piloted = GameState::with_seed(GameConfig::default(), seed);
+        fly(&mut piloted, idle.frame * 4);
+        assert!(!piloted.is_game_over(), "seed {}", seed);
+        assert!(piloted.score() > idle.score(), "seed {}", seed);
+    }
+}
@@ -0,0 +1,130 @@
+mod common;
+
+use thrust_core::boss::{Boss, Laser};
+use thrust_core::config::GameConfig;
+use thrust_core::enemy::{Enemy, EnemyKind};
+use thrust_core::game_state::GameState;
+use thrust_core::input::FrameInput;
+use thrust_core::movement::{Movement, MovementPattern};
+use thrust_core::score::ScoreSource;
+use thrust_core::shot::ShotType;
+use thrust_core::spatial::Vec2;
+use thrust_core::world::Entity;
+
+/// A quiet game whose bosses come at the given milestones and neither move nor attack on their own.
+fn boss_game(score_interval: usize, frame_interval: usize, spawn_interval: usize) -> GameState {
+    let config = GameConfig::from_toml(&format!(
+        "enemy_shot_probability = 0.0\n\
+         spawn = {{ initial_interval = {}, min_interval = 1, speedup_after_frames = 100000 }}\n\
+         boss = {{ score_interval = {}, frame_interval = {}, move_interval = 1000, attack_interval = 1000 }}",
+        spawn_interval, score_interval, frame_interval,
+    )).unwrap();
+    GameState::with_seed(config, 7)
+}
+
+/// A game whose first boss arrives with the first frame.
+fn game_with_boss() -> GameState {
+    let mut game = boss_game(100000, 0, 100000);
+    game.update(&[]);
+    assert!(game.boss.is_some());
+    game
+}
+
+fn shoot_boss_at(game: &mut GameState, pos: Vec2) {
+    game.world.spawn(Entity::shot(pos, Vec2::xy(0, 0), ShotType::Basic).fired_by(0));
+    game.update(&[]);
+}
+
+#[test]
+fn boss_arrives_once_the_score_reaches_the_interval() {
+    let mut game = boss_game(5, 100000, 100000);
+    let mut enemy = Enemy::new(EnemyKind::Drone, Vec2::xy(20, 9), Movement::single(MovementPattern::default()), 1000);
+    enemy.move_interval = 1000;
+    game.world.spawn(Entity::enemy(enemy));
+    game.update(&[]);
+    assert!(game.boss.is_none());
+    game.world.spawn(Entity::shot(Vec2::xy(20, 10), Vec2::y(-1), ShotType::Basic).fired_by(0));
+    game.update(&[]);
+    assert_eq!(game.score(), game.config.scoring.drone);
+    assert!(game.boss.is_none());
+    game.update(&[]);
+    assert!(game.boss.is_some());
+    assert_eq!(game.boss_encounters, 1);
+}
+
+#[test]
+fn boss_arrives_once_the_frame_interval_passed() {
+    let mut game = boss_game(100000, 10, 100000);
+    for _ in 0..10 {
+        game.update(&[]);
+        assert!(game.boss.is_none());
+    }
+    game.update(&[]);
+    let boss = game.boss.as_ref().unwrap();
+    assert_eq!(boss.health, game.config.boss.base_health);
+    assert_eq!(boss.pos, Vec2::xy(game.dimension.x / 2 - Boss::size().x / 2, 1));
+}
+
+#[test]
+fn no_enemies_spawn_while_a_boss_is_alive() {
+    let mut without_boss = boss_game(100000, 100000, 2);
+    let mut with_boss = boss_game(100000, 0, 2);
+    for _ in 0..30 {
+        without_boss.update(&[]);
+        with_boss.update(&[]);
+    }
+    assert!(without_boss.world.enemies().count() > 0);
+    assert!(with_boss.boss.is_some());
+    assert_eq!(with_boss.world.enemies().count(), 0);
+}
+
+#[test]
+fn shots_damage_the_boss_anywhere_on_its_hitbox() {
+    let hitbox = game_with_boss().boss.unwrap().hitbox();
+    for x in 0..hitbox.size.x {
+        for y in 0..hitbox.size.y {
+            let mut game = game_with_boss();
+            shoot_boss_at(&mut game, hitbox.pos + Vec2::xy(x, y));
+            let boss = game.boss.as_ref().unwrap();
+            assert_eq!(boss.health, boss.max_health - game.config.damage.shot_ship_basic, "cell {}, {}", x, y);
+        }
+    }
+    let mut game = game_with_boss();
+    shoot_boss_at(&mut game, hitbox.pos + Vec2::x(hitbox.size.x));
+    let boss = game.boss.as_ref().unwrap();
+    assert_eq!(boss.health, boss.max_health);
+}
+
+#[test]
+fn defeated_boss_pays_its_score_times_the_encounters_and_drops_three_goodies() {
+    let mut game = boss_game(100000, 0, 100000);
+    game.config.boss.frame_interval = 5;
+    for encounter in 1..=2 {
+        while game.boss.is_none() {
+            game.update(&[]);
+        }
+        let boss = game.boss.as_mut().unwrap();
+        assert_eq!(boss.max_health, game.config.boss.base_health + (encounter - 1) * game.config.boss.health_increase);
+        boss.health = 1;
+        let (pos, score) = (boss.pos, game.players[0].score);
+        shoot_boss_at(&mut game, pos);
+        assert!(game.boss.is_none());
+        assert_eq!(game.players[0].score - score, encounter * game.config.boss.score);
+        assert_eq!(game.world.goodies().count(), 3);
+        game.world.retain(|_| false);
+    }
+    assert_eq!(game.players[0].scoring.points(ScoreSource::Bosses), 3 * game.config.boss.score);
+}
+
+#[test]
+fn laser_damages_the_ship_under_it() {
+    for (offset, damaged) in [(1, true), (5, false)] {
+        let mut game = game_with_boss();
+        let ship_x = game.players[0].ship.pos.x;
+        game.boss.as_mut().unwrap().laser = Some(Laser { x: ship_x + offset, direction: 0, remaining_frames: 10 });
+        let before = game.players[0].health + game.players[0].shield;
+        game.update(&[FrameInput::default()]);
+        let expected = if damaged { before - game.config.damage.laser } else { before };
+        assert_eq!(game.players[0].health + game.players[0].shield, expected);
+    }
+}
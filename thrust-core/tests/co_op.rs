@@ -0,0 +1,42 @@
+mod common;
+
+use thrust_core::enemy::{Enemy, EnemyKind};
+use thrust_core::fixed::Fixed;
+use thrust_core::goodie::GoodieType;
+use thrust_core::input::FrameInput;
+use thrust_core::movement::{Movement, MovementPattern};
+use thrust_core::shot::ShotType;
+use thrust_core::spatial::Vec2;
+use thrust_core::world::Entity;
+use common::quiet_game_with;
+
+#[test]
+fn goodie_goes_to_the_ship_touching_it() {
+    let mut game = quiet_game_with(2);
+    let pos = game.players[1].ship.pos;
+    game.world.spawn(Entity::goodie(pos + Vec2::xy(1, -1), GoodieType::Bomb, Fixed::ONE));
+    game.update(&[FrameInput::default(), FrameInput::default()]);
+    assert_eq!((game.players[0].bombs, game.players[1].bombs), (0, 1));
+}
+
+#[test]
+fn kill_is_scored_for_the_player_who_fired() {
+    let mut game = quiet_game_with(2);
+    let mut enemy = Enemy::new(EnemyKind::Drone, Vec2::xy(20, 9), Movement::single(MovementPattern::default()), 1000);
+    enemy.move_interval = 1000;
+    game.world.spawn(Entity::enemy(enemy));
+    game.world.spawn(Entity::shot(Vec2::xy(20, 10), Vec2::y(-1), ShotType::Basic).fired_by(1));
+    game.update(&[]);
+    assert_eq!(game.players[0].score, 0);
+    assert_eq!(game.players[1].score, EnemyKind::Drone.score(&game.config.scoring));
+}
+
+#[test]
+fn co_op_game_ends_once_both_ships_are_destroyed() {
+    let mut game = quiet_game_with(2);
+    game.players[0].health = 0;
+    game.update(&[]);
+    assert!(!game.is_game_over());
+    game.players[1].health = 0;
+    assert!(game.is_game_over());
+}
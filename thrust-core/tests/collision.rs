@@ -0,0 +1,126 @@
+mod common;
+
+use thrust_core::enemy::{Enemy, EnemyKind};
+use thrust_core::fixed::{Fixed, FixedVec2};
+use thrust_core::goodie::GoodieType;
+use thrust_core::hitbox::{Hitbox, Sweep};
+use thrust_core::input::FrameInput;
+use thrust_core::movement::{Movement, MovementPattern};
+use thrust_core::shot::ShotType;
+use thrust_core::spatial::Vec2;
+use thrust_core::world::{Entity, Faction};
+use common::quiet_game;
+
+fn point(x: f64, y: f64) -> FixedVec2 {
+    FixedVec2::new(Fixed::from_f64(x), Fixed::from_f64(y))
+}
+
+fn cell_sweep(from: (f64, f64), to: (f64, f64)) -> Sweep {
+    Sweep::new(point(from.0, from.1), point(to.0, to.1), Vec2::xy(1, 1))
+}
+
+#[test]
+fn hitboxes_intersect_only_when_sharing_a_cell() {
+    let ship = Hitbox::new(Vec2::xy(10, 10), Vec2::xy(3, 1));
+    assert!(ship.intersects(&Hitbox::cell(Vec2::xy(12, 10))));
+    assert!(!ship.intersects(&Hitbox::cell(Vec2::xy(13, 10))));
+    assert!(!ship.intersects(&Hitbox::cell(Vec2::xy(11, 9))));
+}
+
+#[test]
+fn stationary_objects_hit_when_overlapping() {
+    let still = cell_sweep((5.0, 5.0), (5.0, 5.0));
+    assert!(still.hits(&cell_sweep((5.0, 5.0), (5.0, 5.0))));
+    assert!(!still.hits(&cell_sweep((6.0, 5.0), (6.0, 5.0))));
+}
+
+#[test]
+fn objects_swapping_cells_hit() {
+    let shot = cell_sweep((5.0, 10.0), (5.0, 9.0));
+    let enemy = cell_sweep((5.0, 9.0), (5.0, 10.0));
+    assert!(shot.hits(&enemy));
+    assert!(enemy.hits(&shot));
+}
+
+#[test]
+fn fast_objects_passing_through_hit() {
+    let shot = cell_sweep((5.0, 12.0), (5.0, 8.0));
+    assert!(shot.intersects(&Hitbox::cell(Vec2::xy(5, 10))));
+}
+
+#[test]
+fn parallel_paths_in_neighbouring_columns_miss() {
+    let shot = cell_sweep((5.0, 12.0), (5.0, 8.0));
+    let enemy = cell_sweep((6.0, 8.0), (6.0, 12.0));
+    assert!(!shot.hits(&enemy));
+}
+
+#[test]
+fn touching_edges_do_not_count() {
+    let left = cell_sweep((4.0, 5.0), (4.0, 5.0));
+    let right = cell_sweep((6.0, 5.0), (5.0, 5.0));
+    assert!(!left.hits(&right));
+}
+
+#[test]
+fn crossing_paths_hit_only_when_there_at_the_same_time() {
+    let horizontal = cell_sweep((3.0, 5.0), (7.0, 5.0));
+    assert!(horizontal.hits(&cell_sweep((5.0, 3.0), (5.0, 7.0))));
+    // leaves the crossing cell before the horizontal one arrives
+    assert!(!horizontal.hits(&cell_sweep((5.0, 5.0), (5.0, 9.0))));
+}
+
+#[test]
+fn ship_moving_two_cells_collects_goodie_it_passes() {
+    let mut game = quiet_game();
+    let pos = game.players[0].ship.pos;
+    game.world.spawn(Entity::goodie(pos + Vec2::xy(4, -1), GoodieType::Bomb, Fixed::ONE));
+    game.update(&[FrameInput { right: true, ..Default::default() }]);
+    assert_eq!(game.world.goodies().count(), 0);
+    assert_eq!(game.players[0].bombs, 1);
+}
+
+#[test]
+fn ship_shot_and_enemy_swapping_cells_kill_the_enemy() {
+    let mut game = quiet_game();
+    let mut enemy = Enemy::new(EnemyKind::Drone, Vec2::xy(20, 9), Movement::single(MovementPattern::default()), 1000);
+    enemy.move_interval = 1000;
+    let mut enemy = Entity::enemy(enemy);
+    enemy.velocity = FixedVec2::from_cell(Vec2::y(1));
+    game.world.spawn(enemy);
+    game.world.spawn(Entity::shot(Vec2::xy(20, 10), Vec2::y(-1), ShotType::Basic));
+    game.update(&[FrameInput::default()]);
+    assert_eq!(game.world.enemies().count(), 0);
+    assert_eq!(game.world.shots(Faction::Player).count(), 0);
+}
+
+#[test]
+fn ship_shot_shoots_down_a_missile() {
+    let mut game = quiet_game();
+    game.world.spawn(Entity::shot_with_velocity(Vec2::xy(20, 9), FixedVec2::from_cell(Vec2::y(1)), ShotType::Missile));
+    game.world.spawn(Entity::shot(Vec2::xy(20, 10), Vec2::y(-1), ShotType::Basic).fired_by(0));
+    game.update(&[FrameInput::default()]);
+    assert!(game.world.is_empty());
+    assert_eq!(game.players[0].score, game.config.scoring.missile);
+}
+
+#[test]
+fn enemy_shot_passing_through_the_ship_hits() {
+    let mut game = quiet_game();
+    let pos = game.players[0].ship.pos;
+    game.world.spawn(Entity::shot(pos + Vec2::xy(1, -1), Vec2::y(2), ShotType::Enemy));
+    game.update(&[FrameInput::default()]);
+    assert_eq!(game.world.shots(Faction::Enemy).count(), 0);
+    assert_eq!(game.players[0].health, game.config.initial_health - game.config.damage.shot_hit);
+}
+
+#[test]
+fn ship_moving_into_a_shot_is_hit_and_moving_away_is_not() {
+    for (left, hit) in [(true, true), (false, false)] {
+        let mut game = quiet_game();
+        let pos = game.players[0].ship.pos;
+        game.world.spawn(Entity::shot(pos + Vec2::xy(-1, -1), Vec2::y(1), ShotType::Enemy));
+        game.update(&[FrameInput { left, right: !left, ..Default::default() }]);
+        assert_eq!(game.players[0].health < game.config.initial_health, hit);
+    }
+}
@@ -0,0 +1,21 @@
+//! Fixtures shared by the integration tests, every test file only uses some of them.
+#![allow(dead_code)]
+
+use thrust_core::config::GameConfig;
+use thrust_core::game_state::GameState;
+
+/// A game of `players` ships without random spawns and enemy fire, so only the objects placed by a test interact.
+pub fn quiet_game_with(players: usize) -> GameState {
+    let config = GameConfig::from_toml(&format!(
+        "players = {}\n\
+         enemy_shot_probability = 0.0\n\
+         spawn = {{ initial_interval = 100000, speedup_after_frames = 100000 }}\n\
+         boss = {{ score_interval = 100000, frame_interval = 100000 }}",
+        players,
+    )).unwrap();
+    GameState::with_seed(config, 7)
+}
+
+pub fn quiet_game() -> GameState {
+    quiet_game_with(1)
+}
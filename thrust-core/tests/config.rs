@@ -0,0 +1,119 @@
+use thrust_core::config::{ConfigError, GameConfig, KeyBindings};
+
+fn invalid_reason(content: &str) -> String {
+    match GameConfig::from_toml(content) {
+        Err(ConfigError::Invalid(reason)) => reason,
+        other => panic!("expected an invalid config, got {:?}", other),
+    }
+}
+
+#[test]
+fn default_config_is_valid_and_survives_toml() {
+    let config = GameConfig::default();
+    config.validate().unwrap();
+    assert_eq!(GameConfig::from_toml(&config.to_toml()).unwrap(), config);
+    assert_eq!(GameConfig::from_toml("").unwrap(), config);
+}
+
+#[test]
+fn example_config_is_valid() {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../config.example.toml");
+    GameConfig::load(Some(&path), &[]).unwrap();
+}
+
+#[test]
+fn values_out_of_range_are_rejected_with_the_key() {
+    assert_eq!(invalid_reason("fps_limit = 0"), "fps_limit must be between 1 and 240");
+    assert_eq!(invalid_reason("initial_health = 150\nmax_health = 100"), "initial_health must be between 1 and max_health");
+    assert_eq!(invalid_reason("enemy_shot_probability = 1.5"), "enemy_shot_probability must be between 0 and 1");
+    assert_eq!(
+        invalid_reason("weapons = { random_weapon_probability = -0.1 }"),
+        "weapons.random_weapon_probability must be between 0 and 1",
+    );
+    assert!(invalid_reason("spawn = { initial_interval = 5, min_interval = 10 }").starts_with("spawn.initial_interval"));
+    let no_goodies = "goodies.weights = { repair_kit = 0, shield_boost = 0, ship_upgrade = 0, rapid_fire = 0, \
+                      spread = 0, score_multiplier = 0, invincibility = 0, bomb = 0 }";
+    assert_eq!(invalid_reason(no_goodies), "goodies.weights must not all be 0");
+}
+
+#[test]
+fn zero_intervals_are_rejected() {
+    for key in ["shot_interval", "boss.move_interval", "boss.attack_interval", "effects.rapid_fire_shot_interval",
+                "weapons.burst_delay", "scoring.combo_window", "spawn.min_interval"] {
+        let content = match key.split_once('.') {
+            Some((table, field)) => format!("[{}]\n{} = 0", table, field),
+            None => format!("{} = 0", key),
+        };
+        assert_eq!(invalid_reason(&content), format!("{} must be at least 1", key));
+    }
+}
+
+#[test]
+fn unknown_keys_and_wrong_types_fail_to_parse() {
+    assert!(matches!(GameConfig::from_toml("fps_limt = 30"), Err(ConfigError::Parse(..))));
+    assert!(matches!(GameConfig::from_toml("damage = { colission = 40 }"), Err(ConfigError::Parse(..))));
+    assert!(matches!(GameConfig::from_toml("width = \"wide\""), Err(ConfigError::Parse(..))));
+    assert!(matches!(GameConfig::from_toml("width = "), Err(ConfigError::Parse(..))));
+}
+
+#[test]
+fn overrides_set_nested_values_and_are_validated() {
+    let overrides = ["damage.collision=40".to_string(), "glyphs.enemy=X".to_string()];
+    let config = GameConfig::load(None, &overrides).unwrap();
+    assert_eq!(config.damage.collision, 40);
+    assert_eq!(config.glyphs.enemy, 'X');
+    assert!(matches!(GameConfig::load(None, &["damage.collision".to_string()]), Err(ConfigError::InvalidOverride(_))));
+    assert!(matches!(GameConfig::load(None, &["=5".to_string()]), Err(ConfigError::InvalidOverride(_))));
+    assert!(matches!(GameConfig::load(None, &["height=3".to_string()]), Err(ConfigError::Invalid(_))));
+}
+
+#[test]
+fn missing_config_file_is_reported() {
+    let err = GameConfig::load(Some(std::path::Path::new("/nonexistent/config.toml")), &[]).unwrap_err();
+    assert!(matches!(err, ConfigError::Io(..)));
+    assert!(err.to_string().contains("/nonexistent/config.toml"));
+}
+
+#[test]
+fn keys_bound_twice_are_rejected() {
+    assert_eq!(invalid_reason("keys = { fire = [\"W\"] }"), "key 'W' is bound to both keys.move_up and keys.fire");
+    assert_eq!(invalid_reason("keys = { bomb = [\"space\"] }"), "key 'Space' is bound to both keys.toggle_auto_shoot and keys.bomb");
+}
+
+#[test]
+fn stored_keys_keep_the_rest_of_the_file() {
+    let path = std::env::temp_dir().join(format!("thrust-config-keys-{}.toml", std::process::id()));
+    let content = "# my settings\nfps_limit = 30 # smoother on my laptop\n\n[keys]\n# AZERTY\nmove_up = [\"Z\", \"Up\"] # like W\n";
+    std::fs::write(&path, content).unwrap();
+    let keys = KeyBindings { move_up: vec!["Z".to_string()], fire: vec!["G".to_string()], ..KeyBindings::default() };
+    GameConfig::store_keys(&path, &keys, &KeyBindings::player2()).unwrap();
+    let stored = std::fs::read_to_string(&path).unwrap();
+    assert!(stored.starts_with("# my settings\nfps_limit = 30 # smoother on my laptop\n"));
+    assert!(stored.contains("# AZERTY\nmove_up = [\"Z\"] # like W\n"));
+    let config = GameConfig::load(Some(&path), &[]).unwrap();
+    assert_eq!((config.fps_limit, config.keys), (30, keys));
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn keys_saved_before_the_fire_modes_still_load() {
+    let config = GameConfig::from_toml("keys = { fire_once = [\"G\"] }").unwrap();
+    assert_eq!(config.keys.fire, ["G"]);
+}
+
+#[test]
+fn keys_clashing_with_the_other_player_are_not_stored() {
+    let path = std::env::temp_dir().join(format!("thrust-config-clash-{}.toml", std::process::id()));
+    let content = "fps_limit = 30\n";
+    std::fs::write(&path, content).unwrap();
+    let keys = KeyBindings { bomb: vec!["J".to_string()], ..KeyBindings::default() };
+    let err = GameConfig::store_keys(&path, &keys, &KeyBindings::player2()).unwrap_err();
+    assert_eq!(err.to_string(), "invalid config: key 'J' is bound to both keys.bomb and keys_player2.move_left");
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), content);
+
+    let keys_player2 = KeyBindings { bomb: vec!["M".to_string()], ..KeyBindings::player2() };
+    GameConfig::store_keys(&path, &KeyBindings::default(), &keys_player2).unwrap();
+    let config = GameConfig::load(Some(&path), &[]).unwrap();
+    assert_eq!((config.fps_limit, config.keys_player2), (30, keys_player2));
+    std::fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,79 @@
+use thrust_core::config::GameConfig;
+use thrust_core::effect::EffectKind;
+use thrust_core::enemy::{Enemy, EnemyKind, EnemyWeapon};
+use thrust_core::game_state::GameState;
+use thrust_core::input::FrameInput;
+use thrust_core::movement::Movement;
+use thrust_core::spatial::Vec2;
+use thrust_core::world::{Entity, Role};
+
+/// A different input every few frames, so the ship moves, shoots and picks up goodies.
+fn scripted_input(frame: usize) -> FrameInput {
+    FrameInput::from_bits((frame / 5 % 128) as u8)
+}
+
+/// Plays `frames` frames of `seed` with the scripted inputs and returns the checksum after every frame.
+fn play(seed: u64, frames: usize) -> (GameState, Vec<u64>) {
+    let mut game = GameState::with_seed(GameConfig::default(), seed);
+    let mut checksums = Vec::new();
+    for frame in 0..frames {
+        game.update(&[scripted_input(frame)]);
+        checksums.push(game.checksum());
+    }
+    (game, checksums)
+}
+
+#[test]
+fn same_seed_and_inputs_give_the_same_game() {
+    let (game, checksums) = play(1234, 2000);
+    let (again, checksums_again) = play(1234, 2000);
+    assert_eq!(checksums, checksums_again);
+    assert_eq!((game.score(), game.players[0].health), (again.score(), again.players[0].health));
+    assert_eq!(game.world.len(), again.world.len());
+}
+
+#[test]
+fn different_seeds_give_different_games() {
+    let (_, checksums) = play(1, 2000);
+    let (_, other) = play(2, 2000);
+    assert_ne!(checksums, other);
+}
+
+#[test]
+fn random_games_remember_their_seed() {
+    let mut game = GameState::new(GameConfig::default());
+    let mut seeded = GameState::with_seed(GameConfig::default(), game.seed);
+    for _ in 0..500 {
+        game.update(&[]);
+        seeded.update(&[]);
+    }
+    assert_eq!(game.checksum(), seeded.checksum());
+}
+
+#[test]
+fn checksum_covers_more_than_the_positions() {
+    let game = || {
+        let mut game = GameState::with_seed(GameConfig::default(), 1);
+        game.world.spawn(Entity::enemy(Enemy::new(EnemyKind::Drone, Vec2::xy(20, 5), Movement::new(Vec::new()), 20)));
+        game
+    };
+    let changes: [fn(&mut GameState); 5] = [
+        |game| game.players[0].effects.activate(EffectKind::Spread, 10),
+        |game| game.players[0].scoring.multiplier = 2,
+        |game| game.start_levels(Vec::new()),
+        |game| game.last_spawn = 5,
+        |game| {
+            for entity in game.world.iter_mut() {
+                if let Role::Enemy(enemy) = &mut entity.role {
+                    enemy.weapon = EnemyWeapon::Missile;
+                }
+            }
+        },
+    ];
+    let checksum = game().checksum();
+    for change in changes {
+        let mut changed = game();
+        change(&mut changed);
+        assert_ne!(changed.checksum(), checksum);
+    }
+}
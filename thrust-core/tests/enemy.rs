@@ -0,0 +1,55 @@
+mod common;
+
+use thrust_core::enemy::{Enemy, EnemyKind};
+use thrust_core::game_state::GameState;
+use thrust_core::movement::{Movement, MovementPattern};
+use thrust_core::shot::ShotType;
+use thrust_core::spatial::Vec2;
+use thrust_core::world::Entity;
+use common::quiet_game;
+
+const ENEMY_POS: Vec2 = Vec2 { x: 20, y: 9 };
+
+/// A game with a single enemy of `kind` that stays where it was placed.
+fn game_with(kind: EnemyKind) -> GameState {
+    let mut game = quiet_game();
+    let mut enemy = Enemy::new(kind, ENEMY_POS, Movement::single(MovementPattern::default()), 1000);
+    enemy.move_interval = 1000;
+    game.world.spawn(Entity::enemy(enemy));
+    game
+}
+
+/// Fires a shot of `shot_type` at the enemy from right below it and returns its remaining health, if it survived.
+fn shoot(game: &mut GameState, shot_type: ShotType) -> Option<usize> {
+    game.world.spawn(Entity::shot(ENEMY_POS + Vec2::y(1), Vec2::y(-1), shot_type).fired_by(0));
+    game.update(&[]);
+    game.world.enemies().next().map(|entity| entity.health.unwrap().current)
+}
+
+#[test]
+fn tank_survives_three_basic_hits_and_dies_on_the_fourth() {
+    let mut game = game_with(EnemyKind::Tank);
+    let health: Vec<Option<usize>> = (0..4).map(|_| shoot(&mut game, ShotType::Basic)).collect();
+    assert_eq!(health, [Some(3), Some(2), Some(1), None]);
+}
+
+#[test]
+fn strong_shot_deals_two_damage() {
+    let mut game = game_with(EnemyKind::Tank);
+    assert_eq!(shoot(&mut game, ShotType::Strong), Some(2));
+    let mut game = game_with(EnemyKind::Fighter);
+    assert_eq!(shoot(&mut game, ShotType::Strong), None);
+}
+
+#[test]
+fn each_kind_scores_its_configured_points() {
+    for (kind, points) in [(EnemyKind::Drone, 7), (EnemyKind::Fighter, 13), (EnemyKind::Tank, 29)] {
+        let mut game = game_with(kind);
+        game.config.scoring.drone = 7;
+        game.config.scoring.fighter = 13;
+        game.config.scoring.tank = 29;
+        while shoot(&mut game, ShotType::Strong).is_some() {}
+        assert_eq!(kind.score(&game.config.scoring), points);
+        assert_eq!(game.players[0].score, points);
+    }
+}
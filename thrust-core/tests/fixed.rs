@@ -0,0 +1,54 @@
+use thrust_core::fixed::{Fixed, FixedVec2};
+use thrust_core::spatial::Vec2;
+
+fn vec(x: i32, y: i32) -> FixedVec2 {
+    FixedVec2::from_cell(Vec2::xy(x, y))
+}
+
+#[test]
+fn halves_round_up_on_both_sides_of_zero() {
+    let rounded: Vec<i32> = [-2.5, -1.5, -0.75, -0.5, -0.25, 0.0, 0.25, 0.5, 1.5, 2.49]
+        .into_iter()
+        .map(|value| Fixed::from_f64(value).round())
+        .collect();
+    assert_eq!(rounded, [-2, -1, -1, 0, 0, 0, 0, 1, 2, 2]);
+    assert_eq!(Fixed::from_raw(-129).round(), -1);
+    assert_eq!(Fixed::from_raw(-128).round(), 0);
+}
+
+#[test]
+fn config_values_are_rounded_to_the_nearest_step() {
+    assert_eq!(Fixed::from_f64(0.3).raw(), 77);
+    assert_eq!(Fixed::from_f64(-0.3).raw(), -77);
+    assert_eq!(Fixed::from_f64(1.0 / 512.0).raw(), 1);
+    assert_eq!(Fixed::from_f64(-1.0 / 512.0).raw(), -1);
+    assert_eq!(Fixed::from_f64(2.0), Fixed::from_int(2));
+    assert_eq!(Fixed::from_f64(0.75).to_f64(), 0.75);
+}
+
+#[test]
+fn ratio_truncates_towards_zero() {
+    assert_eq!(Fixed::ratio(1, 4).raw(), 64);
+    assert_eq!(Fixed::ratio(3, 2), Fixed::from_f64(1.5));
+    assert_eq!(Fixed::ratio(1, 3).raw(), 85);
+    assert_eq!(Fixed::ratio(-1, 3).raw(), -85);
+    assert_eq!(Fixed::ratio(5, 1), Fixed::from_int(5));
+}
+
+#[test]
+fn towards_has_the_given_speed_in_the_direction_of_the_target() {
+    let velocity = FixedVec2::towards(vec(0, 0), vec(3, 4), Fixed::ONE);
+    assert_eq!((velocity.x.raw(), velocity.y.raw()), (153, 204));
+    let length = ((velocity.x.raw() as f64).powi(2) + (velocity.y.raw() as f64).powi(2)).sqrt();
+    assert!((254.0..=256.0).contains(&length), "{}", length);
+    let up = FixedVec2::towards(vec(5, 10), vec(5, 0), Fixed::from_int(2));
+    assert_eq!(up, FixedVec2::new(Fixed::ZERO, Fixed::from_int(-2)));
+    let left = FixedVec2::towards(vec(0, 0), vec(-1000, 0), Fixed::ratio(1, 2));
+    assert_eq!(left, FixedVec2::new(Fixed::ratio(-1, 2), Fixed::ZERO));
+}
+
+#[test]
+fn towards_the_own_position_points_down() {
+    let velocity = FixedVec2::towards(vec(7, 7), vec(7, 7), Fixed::ratio(3, 4));
+    assert_eq!(velocity, FixedVec2::new(Fixed::ZERO, Fixed::ratio(3, 4)));
+}
@@ -0,0 +1,154 @@
+mod common;
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use thrust_core::boss::Boss;
+use thrust_core::config::GameConfig;
+use thrust_core::effect::EffectKind;
+use thrust_core::enemy::{Enemy, EnemyKind};
+use thrust_core::fixed::Fixed;
+use thrust_core::game_state::GameState;
+use thrust_core::goodie::GoodieType;
+use thrust_core::input::FrameInput;
+use thrust_core::movement::{Movement, MovementPattern};
+use thrust_core::score::ScoreSource;
+use thrust_core::shot::ShotType;
+use thrust_core::spatial::Vec2;
+use thrust_core::stats::DamageCause;
+use thrust_core::world::{Entity, Faction};
+use common::quiet_game;
+
+const WEIGHTS: [&str; 8] =
+    ["repair_kit", "shield_boost", "ship_upgrade", "rapid_fire", "spread", "score_multiplier", "invincibility", "bomb"];
+
+/// Lets the ship of the first player collect a goodie of `goodie_type` falling onto it.
+fn pick_up(game: &mut GameState, goodie_type: GoodieType) {
+    let pos = game.players[0].ship.pos;
+    game.world.spawn(Entity::goodie(pos + Vec2::xy(1, -1), goodie_type, Fixed::ONE));
+    game.update(&[]);
+    assert_eq!(game.world.goodies().count(), 0);
+}
+
+fn still_enemy(kind: EnemyKind, pos: Vec2) -> Entity {
+    let mut enemy = Enemy::new(kind, pos, Movement::single(MovementPattern::default()), 1000);
+    enemy.move_interval = 1000;
+    Entity::enemy(enemy)
+}
+
+/// The number of frames between the volleys the auto-shooting ship fires in `frames` frames.
+fn volley_gaps(game: &mut GameState, frames: usize) -> Vec<usize> {
+    game.players[0].ship.auto_shoot = true;
+    let mut volley_frames = Vec::new();
+    for frame in 0..frames {
+        let last_shot_frame = game.players[0].ship.last_shot_frame;
+        game.update(&[]);
+        if game.players[0].ship.last_shot_frame != last_shot_frame {
+            volley_frames.push(frame);
+        }
+    }
+    volley_frames.windows(2).map(|pair| pair[1] - pair[0]).collect()
+}
+
+#[test]
+fn rapid_fire_shortens_the_shot_interval_until_it_ends() {
+    let mut game = quiet_game();
+    game.config.shot_interval = 9;
+    game.config.effects.rapid_fire_shot_interval = 2;
+    game.config.effects.rapid_fire_duration = 30;
+    pick_up(&mut game, GoodieType::PowerUp(EffectKind::RapidFire));
+    let gaps = volley_gaps(&mut game, 60);
+    assert!(!game.players[0].effects.is_active(EffectKind::RapidFire));
+    assert!(gaps[..8].iter().all(|&gap| gap == 3), "{:?}", gaps);
+    assert!(gaps[gaps.len() - 2..].iter().all(|&gap| gap == 10), "{:?}", gaps);
+}
+
+#[test]
+fn invincible_ship_takes_no_damage() {
+    let mut game = quiet_game();
+    pick_up(&mut game, GoodieType::PowerUp(EffectKind::Invincibility));
+    let pos = game.players[0].ship.pos;
+    let (health, shield) = (game.players[0].health, game.players[0].shield);
+    game.world.spawn(Entity::shot(pos + Vec2::xy(1, -1), Vec2::y(1), ShotType::Enemy));
+    game.world.spawn(still_enemy(EnemyKind::Drone, pos + Vec2::x(1)));
+    game.update(&[]);
+    assert_eq!((game.players[0].health, game.players[0].shield), (health, shield));
+    let stats = &game.players[0].stats;
+    assert_eq!(stats.damage(DamageCause::Shot) + stats.damage(DamageCause::Collision), 0);
+}
+
+#[test]
+fn picking_up_an_active_power_up_restarts_it() {
+    let mut game = quiet_game();
+    let kind = EffectKind::Spread;
+    pick_up(&mut game, GoodieType::PowerUp(kind));
+    let remaining = game.players[0].effects.active()[0].remaining_frames;
+    assert_eq!(remaining, kind.duration(&game.config.effects));
+    for _ in 0..10 {
+        game.update(&[]);
+    }
+    assert_eq!(game.players[0].effects.active()[0].remaining_frames, remaining - 10);
+    pick_up(&mut game, GoodieType::PowerUp(kind));
+    assert_eq!(game.players[0].effects.active().len(), 1);
+    assert_eq!(game.players[0].effects.active()[0].remaining_frames, remaining);
+}
+
+#[test]
+fn bombs_are_stored_up_to_the_maximum() {
+    let mut game = quiet_game();
+    for _ in 0..game.config.goodies.max_bombs + 2 {
+        pick_up(&mut game, GoodieType::Bomb);
+    }
+    assert_eq!(game.players[0].bombs, game.config.goodies.max_bombs);
+}
+
+#[test]
+fn bomb_clears_the_enemies_and_scores_them_without_a_combo() {
+    let mut game = quiet_game();
+    game.players[0].bombs = 1;
+    game.world.spawn(still_enemy(EnemyKind::Drone, Vec2::xy(10, 5)));
+    game.world.spawn(still_enemy(EnemyKind::Tank, Vec2::xy(30, 5)));
+    game.world.spawn(Entity::shot(Vec2::xy(40, 5), Vec2::y(1), ShotType::Enemy));
+    game.world.spawn(Entity::shot(Vec2::xy(50, 10), Vec2::y(-1), ShotType::Basic).fired_by(0));
+    game.world.spawn(Entity::goodie(Vec2::xy(20, 5), GoodieType::Bomb, Fixed::ONE));
+    game.update(&[FrameInput { bomb: true, ..Default::default() }]);
+    assert_eq!(game.players[0].bombs, 0);
+    assert!(game.world.iter().all(|entity| entity.faction != Faction::Enemy));
+    assert_eq!(game.world.shots(Faction::Player).count(), 1);
+    assert_eq!(game.world.goodies().count(), 1);
+    let scoring = &game.players[0].scoring;
+    assert_eq!(scoring.points(ScoreSource::Kills), game.config.scoring.drone + game.config.scoring.tank);
+    assert_eq!(scoring.points(ScoreSource::Combos), 0);
+    assert_eq!((scoring.combo, scoring.multiplier), (0, 1));
+    game.update(&[FrameInput { bomb: true, ..Default::default() }]);
+    assert_eq!(game.players[0].score, game.config.scoring.drone + game.config.scoring.tank);
+}
+
+#[test]
+fn bomb_damages_the_boss_and_can_defeat_it() {
+    let mut game = quiet_game();
+    let bomb_damage = game.config.goodies.bomb_boss_damage;
+    game.boss = Some(Boss::new(0, &game.config.boss, game.dimension, 0));
+    game.boss_encounters = 1;
+    game.players[0].bombs = 2;
+    game.update(&[FrameInput { bomb: true, ..Default::default() }]);
+    let boss = game.boss.as_mut().unwrap();
+    assert_eq!(boss.health, boss.max_health - bomb_damage);
+    boss.health = bomb_damage;
+    game.update(&[FrameInput { bomb: true, ..Default::default() }]);
+    assert!(game.boss.is_none());
+    assert_eq!(game.players[0].scoring.points(ScoreSource::Bosses), game.config.boss.score);
+}
+
+#[test]
+fn single_weight_always_drops_its_goodie() {
+    let mut rng = ChaCha8Rng::seed_from_u64(7);
+    for name in WEIGHTS {
+        let weights: Vec<String> = WEIGHTS.iter()
+            .map(|other| format!("{} = {}", other, if *other == name { 3 } else { 0 }))
+            .collect();
+        let config = GameConfig::from_toml(&format!("[goodies.weights]\n{}", weights.join("\n"))).unwrap();
+        for _ in 0..50 {
+            assert_eq!(rng.sample(&config.goodies).name(), name);
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use thrust_core::config::GameConfig;
+use thrust_core::level::{Level, LevelError};
+
+/// An empty directory for the level files of test `name`.
+fn level_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("thrust-levels-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Writes `content` as a level file into a fresh directory and loads it.
+fn load(name: &str, content: &str) -> Result<Level, LevelError> {
+    let path = level_dir(name).join("level.toml");
+    fs::write(&path, content).unwrap();
+    Level::load(&path)
+}
+
+fn validate(name: &str, content: &str) -> String {
+    let level = load(name, content).unwrap();
+    level.validate(GameConfig::default().dimension()).unwrap_err().to_string()
+}
+
+#[test]
+fn bundled_levels_are_valid() {
+    let levels = Level::load_all(&Path::new(env!("CARGO_MANIFEST_DIR")).join("../levels")).unwrap();
+    assert!(levels.len() >= 2);
+    for level in &levels {
+        level.validate(GameConfig::default().dimension()).unwrap();
+    }
+}
+
+#[test]
+fn broken_toml_names_the_file() {
+    let err = load("syntax", "name = \"Broken\"\n[[waves]\n").unwrap_err();
+    assert!(matches!(err, LevelError::Parse(..)));
+    assert!(err.to_string().contains("level.toml"));
+}
+
+#[test]
+fn unknown_keys_and_values_are_rejected() {
+    let unknown_field = "name = \"A\"\n[[waves]]\nspawns = [{ kind = \"drone\", formation = { type = \"single\", x = 5 }, speed = 3 }]";
+    assert!(matches!(load("field", unknown_field), Err(LevelError::Parse(..))));
+    let unknown_kind = "name = \"A\"\n[[waves]]\nspawns = [{ kind = \"dragon\", formation = { type = \"single\", x = 5 } }]";
+    assert!(matches!(load("kind", unknown_kind), Err(LevelError::Parse(..))));
+    let unknown_formation = "name = \"A\"\n[[waves]]\nspawns = [{ kind = \"drone\", formation = { type = \"circle\", x = 5 } }]";
+    assert!(matches!(load("formation", unknown_formation), Err(LevelError::Parse(..))));
+}
+
+#[test]
+fn missing_files_and_empty_directories_are_reported() {
+    assert!(matches!(Level::load(Path::new("/nonexistent/level.toml")), Err(LevelError::Io(..))));
+    let dir = level_dir("empty");
+    assert!(matches!(Level::load_all(&dir), Err(LevelError::Invalid(_))));
+}
+
+#[test]
+fn unplayable_levels_fail_validation() {
+    assert!(validate("no_waves", "name = \"A\"\nwaves = []").contains("at least one wave"));
+    assert!(validate("empty_wave", "name = \"A\"\n[[waves]]\n").contains("wave 1: a wave needs at least one spawn"));
+    let outside = "name = \"A\"\n[[waves]]\nspawns = [{ kind = \"drone\", formation = { type = \"line\", x = 40, count = 5, spacing = 10 } }]";
+    assert!(validate("outside", outside).contains("outside of the field"));
+    let frozen = "name = \"A\"\n[[waves]]\nspawns = [{ kind = \"drone\", formation = { type = \"single\", x = 5 }, move_interval = 0 }]";
+    assert!(validate("frozen", frozen).contains("move_interval"));
+    let firing = "name = \"A\"\n[[waves]]\nspawns = [{ kind = \"drone\", formation = { type = \"single\", x = 5 }, shot_interval = 0 }]";
+    assert!(validate("firing", firing).contains("wave 1: shot_interval must be at least 1"));
+}
+
+#[test]
+fn levels_of_a_directory_load_in_file_name_order() {
+    let dir = level_dir("order");
+    let level = |name: &str| format!("name = \"{}\"\n[[waves]]\nboss = true\n", name);
+    fs::write(dir.join("02_second.toml"), level("Second")).unwrap();
+    fs::write(dir.join("01_first.toml"), level("First")).unwrap();
+    fs::write(dir.join("notes.txt"), "not a level").unwrap();
+    let names: Vec<String> = Level::load_all(&dir).unwrap().into_iter().map(|level| level.name).collect();
+    assert_eq!(names, ["First", "Second"]);
+}
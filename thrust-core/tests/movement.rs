@@ -0,0 +1,111 @@
+use thrust_core::movement::{Movement, MovementContext, MovementPattern, PatternPhase};
+use thrust_core::spatial::Vec2;
+
+fn context() -> MovementContext {
+    MovementContext { ship_pos: Vec2::xy(30, 25), dimension: Vec2::xy(60, 30) }
+}
+
+/// The positions an enemy starting at `start` moves through in `ticks` ticks, kept within the columns as enemies are.
+fn positions(mut movement: Movement, start: Vec2, ticks: usize) -> Vec<Vec2> {
+    let context = context();
+    let mut pos = start;
+    (0..ticks).map(|_| {
+        pos = context.clamp(movement.step(pos, &context));
+        pos
+    }).collect()
+}
+
+fn positions_of(pattern: MovementPattern, start: Vec2, ticks: usize) -> Vec<Vec2> {
+    positions(Movement::single(pattern), start, ticks)
+}
+
+#[test]
+fn straight_moves_by_its_step() {
+    let moved = positions_of(MovementPattern::Straight { step: Vec2::xy(1, 2) }, Vec2::xy(20, 5), 3);
+    assert_eq!(moved, [Vec2::xy(21, 7), Vec2::xy(22, 9), Vec2::xy(23, 11)]);
+}
+
+#[test]
+fn sine_wave_swings_around_its_starting_column() {
+    let moved = positions_of(MovementPattern::SineWave { amplitude: 4, period: 16 }, Vec2::xy(20, 5), 16);
+    let columns: Vec<i32> = moved.iter().map(|pos| pos.x).collect();
+    assert_eq!((columns[3], columns[7], columns[11], columns[15]), (24, 20, 16, 20));
+    assert!(columns.iter().all(|x| (16..=24).contains(x)));
+    assert!(moved.iter().enumerate().all(|(i, pos)| pos.y == 6 + i as i32));
+}
+
+#[test]
+fn zig_zag_turns_around_after_its_width() {
+    let moved = positions_of(MovementPattern::ZigZag { width: 3 }, Vec2::xy(20, 5), 7);
+    assert_eq!(moved.iter().map(|pos| pos.x).collect::<Vec<_>>(), [21, 22, 23, 22, 21, 20, 21]);
+    assert_eq!(moved[6].y, 12);
+}
+
+#[test]
+fn dive_steers_towards_the_ship() {
+    let context = context();
+    for (x, expected) in [(20, 21), (40, 39), (31, 31)] {
+        let mut movement = Movement::single(MovementPattern::Dive);
+        assert_eq!(movement.step(Vec2::xy(x, 5), &context), Vec2::xy(expected, 6));
+    }
+}
+
+#[test]
+fn strafe_descends_while_flying_sideways_and_turns_at_the_border() {
+    let moved = positions_of(MovementPattern::Strafe { speed: 2, descent: 4 }, Vec2::xy(50, 5), 8);
+    assert_eq!(moved, [
+        Vec2::xy(52, 5), Vec2::xy(54, 5), Vec2::xy(56, 5), Vec2::xy(56, 6),
+        Vec2::xy(54, 6), Vec2::xy(52, 6), Vec2::xy(50, 6), Vec2::xy(48, 7),
+    ]);
+}
+
+#[test]
+fn strafe_reaches_the_ground() {
+    let moved = positions_of(MovementPattern::Strafe { speed: 1, descent: 4 }, Vec2::xy(20, 5), 200);
+    assert!(moved.last().unwrap().y >= context().dimension.y);
+}
+
+#[test]
+fn circle_comes_back_to_its_column_after_a_period() {
+    let moved = positions_of(MovementPattern::Circle { radius: 3, period: 16 }, Vec2::xy(20, 5), 16);
+    assert_eq!(moved[15], Vec2::xy(20, 9));
+    assert!(moved.iter().all(|pos| (14..=20).contains(&pos.x)));
+}
+
+#[test]
+fn path_flies_through_its_waypoints_and_falls_afterwards() {
+    let pattern = MovementPattern::Path { waypoints: vec![Vec2::xy(3, 0), Vec2::xy(3, 2)] };
+    let moved = positions_of(pattern, Vec2::xy(20, 5), 6);
+    assert_eq!(moved, [
+        Vec2::xy(21, 5), Vec2::xy(22, 5), Vec2::xy(23, 5), Vec2::xy(23, 6), Vec2::xy(23, 7), Vec2::xy(23, 8),
+    ]);
+}
+
+#[test]
+fn path_waypoint_beyond_the_border_is_clamped() {
+    let pattern = MovementPattern::Path { waypoints: vec![Vec2::xy(10, 0)] };
+    let moved = positions_of(pattern, Vec2::xy(55, 5), 6);
+    assert_eq!(moved[3], Vec2::xy(59, 5));
+    assert_eq!(moved[5], Vec2::xy(59, 7));
+}
+
+#[test]
+fn phases_switch_after_their_moves() {
+    let movement = Movement::new(vec![
+        PatternPhase { pattern: MovementPattern::Straight { step: Vec2::x(1) }, moves: Some(2) },
+        PatternPhase { pattern: MovementPattern::default(), moves: Some(1) },
+    ]);
+    let moved = positions(movement, Vec2::xy(20, 5), 4);
+    assert_eq!(moved, [Vec2::xy(21, 5), Vec2::xy(22, 5), Vec2::xy(22, 6), Vec2::xy(22, 7)]);
+}
+
+#[test]
+fn strafe_descent_defaults_and_must_be_positive() {
+    let phase: PatternPhase = toml::from_str("pattern = { type = \"strafe\", speed = 1 }").unwrap();
+    assert_eq!(phase.pattern, MovementPattern::Strafe { speed: 1, descent: 4 });
+    let err = MovementPattern::Strafe { speed: 1, descent: 0 }.validate().unwrap_err();
+    assert_eq!(err, "the descent of a strafe pattern must be at least 1");
+    assert!(MovementPattern::Strafe { speed: 0, descent: 4 }.validate().is_err());
+    assert!(MovementPattern::ZigZag { width: 0 }.validate().is_err());
+    assert!(MovementPattern::Circle { radius: 3, period: 3 }.validate().is_err());
+}
@@ -0,0 +1,133 @@
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::thread;
+use std::time::{Duration, Instant};
+use thrust_core::config::GameConfig;
+use thrust_core::game_state::{GameMode, GameState};
+use thrust_core::input::FrameInput;
+use thrust_core::net::{self, Host, Lockstep, NetError, GUEST_PLAYER, HOST_PLAYER, PROTOCOL_VERSION};
+use thrust_core::SIMULATION_VERSION;
+
+/// Hosts a game on a free loopback port and joins it from a second thread.
+fn connect() -> (Lockstep, Lockstep) {
+    connect_with(&GameConfig::default())
+}
+
+fn connect_with(config: &GameConfig) -> (Lockstep, Lockstep) {
+    let mut host = Host::bind(0).unwrap();
+    let addr = format!("127.0.0.1:{}", host.local_addr().unwrap().port());
+    let guest = thread::spawn(move || net::join(&addr).unwrap());
+    let hosting = loop {
+        if let Some(lockstep) = host.accept(42, config).unwrap() {
+            break lockstep;
+        }
+        thread::yield_now();
+    };
+    (hosting, guest.join().unwrap())
+}
+
+/// Steps `game` until the frame is simulated, returns the inputs it was simulated with.
+fn step(lockstep: &mut Lockstep, game: &mut GameState, input: FrameInput) -> Result<Vec<FrameInput>, NetError> {
+    loop {
+        if let Some(inputs) = lockstep.step(game, input)? {
+            return Ok(inputs);
+        }
+        thread::yield_now();
+    }
+}
+
+/// A different input every few frames, so both ships move and shoot.
+fn scripted_input(player: usize, frame: usize) -> FrameInput {
+    FrameInput::from_bits(((frame / 7 + player * 3) % 128) as u8)
+}
+
+#[test]
+fn host_and_guest_play_the_same_game() {
+    let (mut host, mut guest) = connect();
+    assert_eq!((host.local_player, guest.local_player), (HOST_PLAYER, GUEST_PLAYER));
+    assert_eq!(host.config, guest.config);
+    assert_eq!(guest.config.players, 2);
+    let guest = thread::spawn(move || {
+        let mut game = guest.new_game();
+        for frame in 0..300 {
+            step(&mut guest, &mut game, scripted_input(GUEST_PLAYER, frame)).unwrap();
+        }
+        game.checksum()
+    });
+    let mut game = host.new_game();
+    for frame in 0..300 {
+        let inputs = step(&mut host, &mut game, scripted_input(HOST_PLAYER, frame)).unwrap();
+        assert_eq!(inputs, vec![scripted_input(HOST_PLAYER, frame), scripted_input(GUEST_PLAYER, frame)]);
+    }
+    assert_eq!(game.checksum(), guest.join().unwrap());
+}
+
+#[test]
+fn diverging_games_are_detected() {
+    let (mut host, mut guest) = connect();
+    let guest = thread::spawn(move || {
+        let mut game = guest.new_game();
+        game.players[0].health -= 1;
+        step(&mut guest, &mut game, FrameInput::default()).map(|_| ())
+    });
+    let mut game = host.new_game();
+    assert!(matches!(step(&mut host, &mut game, FrameInput::default()), Err(NetError::Desync { frame: 0 })));
+    assert!(matches!(guest.join().unwrap(), Err(NetError::Desync { frame: 0 })));
+}
+
+#[test]
+fn leaving_ends_the_game_of_the_other_side() {
+    let (mut host, guest) = connect();
+    guest.leave();
+    let mut game = host.new_game();
+    assert!(matches!(step(&mut host, &mut game, FrameInput::default()), Err(NetError::Left)));
+}
+
+#[test]
+fn dropped_connection_is_reported() {
+    let (mut host, guest) = connect();
+    drop(guest);
+    let mut game = host.new_game();
+    assert!(matches!(step(&mut host, &mut game, FrameInput::default()), Err(NetError::Disconnected)));
+}
+
+#[test]
+fn host_does_not_wait_for_a_silent_player() {
+    let mut host = Host::bind(0).unwrap();
+    let _silent = TcpStream::connect(("127.0.0.1", host.local_addr().unwrap().port())).unwrap();
+    let start = Instant::now();
+    for _ in 0..10 {
+        assert!(host.accept(42, &GameConfig::default()).unwrap().is_none());
+        thread::sleep(Duration::from_millis(5));
+    }
+    assert!(start.elapsed() < Duration::from_secs(1));
+}
+
+#[test]
+fn player_with_another_simulation_version_is_rejected() {
+    let mut host = Host::bind(0).unwrap();
+    let mut stream = TcpStream::connect(("127.0.0.1", host.local_addr().unwrap().port())).unwrap();
+    let mut join = vec![0];
+    join.extend(PROTOCOL_VERSION.to_le_bytes());
+    join.extend((SIMULATION_VERSION + 1).to_le_bytes());
+    stream.write_all(&join).unwrap();
+    let result = loop {
+        match host.accept(42, &GameConfig::default()) {
+            Ok(None) => thread::yield_now(),
+            result => break result,
+        }
+    };
+    assert!(matches!(result, Err(NetError::Rejected(reason)) if reason.contains("simulation version")));
+    let mut tag = [0];
+    stream.read_exact(&mut tag).unwrap();
+    assert_eq!(tag, [2]);
+}
+
+#[test]
+fn hosts_mode_is_played_on_both_sides() {
+    let (host, guest) = connect_with(&GameConfig { mode: GameMode::Versus, ..Default::default() });
+    assert_eq!((host.config.mode, guest.config.mode), (GameMode::Versus, GameMode::Versus));
+    assert!(host.new_game().is_versus() && guest.new_game().is_versus());
+    let (host, guest) = connect();
+    assert_eq!((host.config.mode, guest.config.mode), (GameMode::CoOp, GameMode::CoOp));
+}
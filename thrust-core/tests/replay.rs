@@ -0,0 +1,90 @@
+use thrust_core::config::GameConfig;
+use thrust_core::game_state::GameState;
+use thrust_core::input::FrameInput;
+use thrust_core::replay::{Replay, ReplayError};
+use thrust_core::SIMULATION_VERSION;
+
+/// Plays `frames` frames of a game with `players` ships and records it.
+fn record(players: usize, frames: usize) -> (GameState, Replay) {
+    let config = GameConfig { players, ..GameConfig::default() };
+    let mut game = GameState::with_seed(config.clone(), 77);
+    let mut replay = Replay::new(game.seed, config);
+    for frame in 0..frames {
+        let inputs: Vec<FrameInput> = (0..players)
+            .map(|player| FrameInput::from_bits(((frame / 9 + player * 5) % 128) as u8))
+            .collect();
+        game.update(&inputs);
+        replay.record(&inputs);
+    }
+    (game, replay)
+}
+
+fn round_trip(replay: &Replay) -> Result<Replay, ReplayError> {
+    let mut bytes = Vec::new();
+    replay.write_to(&mut bytes)?;
+    Replay::read_from(&mut bytes.as_slice())
+}
+
+#[test]
+fn replay_survives_a_round_trip() {
+    for players in [1, 2] {
+        let (_, replay) = record(players, 1500);
+        assert_eq!(round_trip(&replay).unwrap(), replay);
+    }
+}
+
+#[test]
+fn loaded_replay_plays_the_same_game() {
+    let (game, replay) = record(2, 1500);
+    let loaded = round_trip(&replay).unwrap();
+    let mut replayed = GameState::with_seed(loaded.config.clone(), loaded.seed);
+    for inputs in &loaded.inputs {
+        replayed.update(inputs);
+    }
+    assert_eq!(replayed.checksum(), game.checksum());
+    assert_eq!(replayed.score(), game.score());
+}
+
+#[test]
+fn replay_of_another_simulation_version_is_recognized() {
+    let (_, mut replay) = record(1, 10);
+    assert!(replay.is_current_version());
+    replay.simulation_version = SIMULATION_VERSION + 1;
+    let loaded = round_trip(&replay).unwrap();
+    assert_eq!(loaded.simulation_version, SIMULATION_VERSION + 1);
+    assert!(!loaded.is_current_version());
+}
+
+#[test]
+fn broken_replays_are_rejected() {
+    let (_, replay) = record(1, 100);
+    let mut bytes = Vec::new();
+    replay.write_to(&mut bytes).unwrap();
+    let truncated = &bytes[..bytes.len() - 1];
+    assert!(matches!(Replay::read_from(&mut &truncated[..]), Err(ReplayError::InvalidFormat(_))));
+    let mut wrong_magic = bytes.clone();
+    wrong_magic[0] = b'X';
+    assert!(matches!(Replay::read_from(&mut wrong_magic.as_slice()), Err(ReplayError::InvalidFormat(_))));
+}
+
+#[test]
+fn replays_with_oversized_lengths_are_rejected() {
+    let (_, replay) = record(1, 100);
+    let mut bytes = Vec::new();
+    replay.write_to(&mut bytes).unwrap();
+    let config_len = u32::from_le_bytes(bytes[19..23].try_into().unwrap()) as usize;
+    let mut huge_config = bytes.clone();
+    huge_config[19..23].copy_from_slice(&u32::MAX.to_le_bytes());
+    assert!(matches!(Replay::read_from(&mut huge_config.as_slice()), Err(ReplayError::InvalidFormat(_))));
+    let runs = 23 + config_len;
+    let mut many_runs = bytes.clone();
+    many_runs[runs..runs + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+    assert!(matches!(Replay::read_from(&mut many_runs.as_slice()), Err(ReplayError::InvalidFormat(_))));
+    let mut long_runs = bytes[..runs].to_vec();
+    long_runs.extend(100u32.to_le_bytes());
+    for _ in 0..100 {
+        long_runs.push(0);
+        long_runs.extend(u16::MAX.to_le_bytes());
+    }
+    assert!(matches!(Replay::read_from(&mut long_runs.as_slice()), Err(ReplayError::InvalidFormat(_))));
+}
@@ -0,0 +1,87 @@
+mod common;
+
+use thrust_core::config::ScoringConfig;
+use thrust_core::enemy::{Enemy, EnemyKind};
+use thrust_core::input::FrameInput;
+use thrust_core::movement::{Movement, MovementPattern};
+use thrust_core::score::{ScoreKeeper, ScoreSource};
+use thrust_core::shot::ShotType;
+use thrust_core::spatial::Vec2;
+use thrust_core::world::Entity;
+use common::quiet_game;
+
+fn still_drone(pos: Vec2) -> Entity {
+    let mut enemy = Enemy::new(EnemyKind::Drone, pos, Movement::single(MovementPattern::default()), 1000);
+    enemy.move_interval = 1000;
+    Entity::enemy(enemy)
+}
+
+#[test]
+fn combo_ends_once_the_window_passed_without_a_kill() {
+    let config = ScoringConfig::default();
+    let mut scoring = ScoreKeeper::default();
+    assert_eq!(scoring.register_kill(10, &config), 1);
+    scoring.update(10 + config.combo_window, &config);
+    assert_eq!(scoring.register_kill(10 + config.combo_window, &config), 2);
+    scoring.update(11 + 2 * config.combo_window, &config);
+    assert_eq!(scoring.combo, 0);
+    assert_eq!(scoring.register_kill(11 + 2 * config.combo_window, &config), 1);
+}
+
+#[test]
+fn multiplier_grows_with_kills_up_to_the_maximum_and_resets_on_damage() {
+    let config = ScoringConfig { multiplier_kills: 2, max_multiplier: 3, ..Default::default() };
+    let mut scoring = ScoreKeeper::default();
+    let multipliers: Vec<usize> = (0..6).map(|frame| {
+        scoring.register_kill(frame, &config);
+        scoring.multiplier
+    }).collect();
+    assert_eq!(multipliers, [1, 2, 2, 3, 3, 3]);
+    scoring.register_damage();
+    assert_eq!(scoring.multiplier, 1);
+    scoring.register_kill(6, &config);
+    assert_eq!(scoring.multiplier, 1);
+}
+
+#[test]
+fn breakdown_adds_up_to_the_score() {
+    let mut game = quiet_game();
+    for x in [20, 30] {
+        game.world.spawn(still_drone(Vec2::xy(x, 9)));
+        game.world.spawn(Entity::shot(Vec2::xy(x, 10), Vec2::y(-1), ShotType::Basic).fired_by(0));
+    }
+    game.update(&[FrameInput::default()]);
+    let scoring = &game.players[0].scoring;
+    assert_eq!(scoring.points(ScoreSource::Kills), 2 * game.config.scoring.drone);
+    assert_eq!(scoring.points(ScoreSource::Combos), game.config.scoring.combo_bonus);
+    assert_eq!(scoring.total(), game.players[0].score);
+}
+
+#[test]
+fn shot_passing_next_to_the_ship_grazes_once_it_got_past() {
+    let mut game = quiet_game();
+    let pos = game.players[0].ship.pos;
+    game.world.spawn(Entity::shot(pos + Vec2::xy(-1, -2), Vec2::y(1), ShotType::Enemy));
+    game.update(&[FrameInput::default()]);
+    assert_eq!(game.players[0].scoring.points(ScoreSource::Grazes), 0);
+    for _ in 0..4 {
+        game.update(&[FrameInput::default()]);
+    }
+    assert_eq!(game.players[0].scoring.points(ScoreSource::Grazes), game.config.scoring.graze_bonus);
+    assert_eq!(game.players[0].health, game.config.initial_health);
+}
+
+#[test]
+fn shot_grazing_the_ship_before_hitting_it_scores_no_graze() {
+    let mut game = quiet_game();
+    let pos = game.players[0].ship.pos;
+    game.world.spawn(Entity::shot(pos + Vec2::xy(-1, -2), Vec2::y(1), ShotType::Enemy));
+    game.update(&[FrameInput::default()]);
+    game.update(&[FrameInput { left: true, ..Default::default() }]);
+    for _ in 0..4 {
+        game.update(&[FrameInput::default()]);
+    }
+    assert_eq!(game.players[0].health, game.config.initial_health - game.config.damage.shot_hit);
+    assert_eq!(game.players[0].scoring.points(ScoreSource::Grazes), 0);
+    assert_eq!(game.players[0].score, 0);
+}
@@ -0,0 +1,88 @@
+mod common;
+
+use thrust_core::game_state::GameState;
+use thrust_core::input::FrameInput;
+use thrust_core::ship::FireMode;
+use thrust_core::shot::ShotType;
+use thrust_core::world::Faction;
+use common::quiet_game;
+
+/// The number of the ship's shots on the field after each of `frames` frames with the same `input`.
+fn shots_per_frame(game: &mut GameState, input: FrameInput, frames: usize) -> Vec<usize> {
+    (0..frames).map(|_| {
+        game.update(&[input]);
+        game.world.shots(Faction::Player).count()
+    }).collect()
+}
+
+/// A game in the charge fire mode, past the first shot interval so the ship is ready to fire.
+fn charging_game() -> GameState {
+    let mut game = quiet_game();
+    game.config.fire_mode = FireMode::Charge;
+    game.config.charge_frames = 10;
+    for _ in 0..20 {
+        game.update(&[]);
+    }
+    game
+}
+
+/// Holds fire for `frames` frames, releases it and returns the types and columns of the shots fired on release.
+fn release_after(game: &mut GameState, frames: usize) -> Vec<(ShotType, i32)> {
+    let fire = FrameInput { fire: true, ..Default::default() };
+    assert_eq!(shots_per_frame(game, fire, frames), vec![0; frames]);
+    game.update(&[]);
+    game.world.shots(Faction::Player)
+        .map(|entity| (entity.as_shot().unwrap().shot_type, entity.position.cell.x - game.players[0].ship.pos.x))
+        .collect()
+}
+
+#[test]
+fn held_fire_shoots_on_the_same_frames_as_auto_shoot() {
+    let mut auto_shoot = quiet_game();
+    auto_shoot.players[0].ship.auto_shoot = true;
+    let mut held = quiet_game();
+    held.config.fire_mode = FireMode::Hold;
+    let fire = FrameInput { fire: true, ..Default::default() };
+    assert_eq!(shots_per_frame(&mut held, fire, 100), shots_per_frame(&mut auto_shoot, FrameInput::default(), 100));
+}
+
+#[test]
+fn shot_interval_comes_from_the_config() {
+    let mut game = quiet_game();
+    game.config.shot_interval = 3;
+    game.players[0].ship.auto_shoot = true;
+    let mut volley_frames = Vec::new();
+    for frame in 0..20 {
+        let last_shot_frame = game.players[0].ship.last_shot_frame;
+        game.update(&[]);
+        if game.players[0].ship.last_shot_frame != last_shot_frame {
+            volley_frames.push(frame);
+        }
+    }
+    // the next volley comes once more than the interval passed
+    assert_eq!(volley_frames, [4, 8, 12, 16]);
+}
+
+#[test]
+fn charging_fires_nothing_while_held() {
+    let mut game = charging_game();
+    let fire = FrameInput { fire: true, ..Default::default() };
+    assert_eq!(shots_per_frame(&mut game, fire, 50), vec![0; 50]);
+    assert_eq!(game.players[0].ship.charge, 50);
+}
+
+#[test]
+fn releasing_early_fires_a_normal_volley() {
+    let mut game = charging_game();
+    let shots = release_after(&mut game, 3);
+    assert_eq!(shots, [(ShotType::Basic, 0), (ShotType::Basic, 1), (ShotType::Basic, 2)]);
+    assert_eq!(game.players[0].ship.charge, 0);
+}
+
+#[test]
+fn releasing_a_full_charge_fires_a_wide_strong_volley() {
+    let mut game = charging_game();
+    let shots = release_after(&mut game, 10);
+    assert_eq!(shots, (-1..=3).map(|x| (ShotType::Strong, x)).collect::<Vec<_>>());
+    assert_eq!(game.players[0].ship.charge, 0);
+}
@@ -0,0 +1,56 @@
+use thrust_core::config::GameConfig;
+use thrust_core::goodie::GoodieType;
+use thrust_core::ship::ShipType;
+use thrust_core::sim::{self, Pilot};
+use thrust_core::stats::DamageCause;
+
+#[test]
+fn idle_ship_is_destroyed_by_the_enemies_reaching_the_ground() {
+    let config = GameConfig::from_toml("enemy_shot_probability = 0.0\ndamage = { enemy_reached_ground = 40 }").unwrap();
+    let result = sim::run(&config, 3, Pilot::Idle, 100_000);
+    assert_eq!(result.stats.destroyed_by, Some(DamageCause::EnemyReachedGround));
+    assert_eq!(result.stats.damage(DamageCause::EnemyReachedGround) % 40, 0);
+    assert_eq!(result.stats.damage(DamageCause::Shot), 0);
+}
+
+#[test]
+fn games_are_cut_off_after_max_frames() {
+    let result = sim::run(&GameConfig::default(), 3, Pilot::Autopilot, 500);
+    assert_eq!(result.frames, 500);
+    assert_eq!(result.stats.destroyed_by, None);
+    assert_eq!(ShipType::ALL.iter().map(|&ship_type| result.stats.frames_as(ship_type)).sum::<usize>(), 500);
+}
+
+#[test]
+fn goodies_are_counted_when_dropped_and_collected() {
+    let result = sim::run(&GameConfig::default(), 5, Pilot::Autopilot, 4000);
+    let dropped: usize = result.goodies_dropped.values().sum();
+    let collected: usize = result.stats.goodies_collected.values().sum();
+    assert!(0 < collected && collected <= dropped);
+    for (name, count) in &result.stats.goodies_collected {
+        assert!(GoodieType::NAMES.contains(name));
+        assert!(*count <= result.goodies_dropped[name]);
+    }
+}
+
+#[test]
+fn reports_cover_every_run_in_seed_order() {
+    let config = GameConfig::default();
+    let results = sim::run_all(&config, 10..14, Pilot::Idle, 2000);
+    assert_eq!(results.iter().map(|result| result.seed).collect::<Vec<_>>(), [10, 11, 12, 13]);
+    assert_eq!(results[2].score, sim::run(&config, 12, Pilot::Idle, 2000).score);
+
+    let summary = sim::summary(&results);
+    assert_eq!(summary["runs"], 4);
+    let deaths: u64 = summary["deaths"].as_object().unwrap().values().map(|count| count.as_u64().unwrap()).sum();
+    assert_eq!(deaths, 4);
+    let max_frames = results.iter().map(|result| result.frames).max().unwrap();
+    assert_eq!(summary["frames"]["max"], max_frames);
+
+    let csv = sim::csv(&results);
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 5);
+    assert!(lines[0].starts_with("seed,score,frames,destroyed_by,"));
+    assert!(lines.iter().all(|line| line.split(',').count() == lines[0].split(',').count()));
+    assert!(lines[1].starts_with("10,"));
+}
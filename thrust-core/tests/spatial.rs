@@ -0,0 +1,62 @@
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use thrust_core::hitbox::Hitbox;
+use thrust_core::spatial::{SpatialGrid, Vec2};
+
+/// A grid over a 20x20 field with buckets of 4x4 cells.
+fn grid_with(areas: &[Hitbox]) -> SpatialGrid {
+    let mut grid = SpatialGrid::new(Vec2::xy(20, 20), 4);
+    grid.rebuild(areas.iter().copied());
+    grid
+}
+
+fn query(grid: &SpatialGrid, area: Hitbox) -> Vec<usize> {
+    let mut found = Vec::new();
+    grid.query(&area, &mut found);
+    found
+}
+
+#[test]
+fn object_straddling_bucket_edges_is_found_from_every_bucket_it_covers() {
+    let grid = grid_with(&[Hitbox::new(Vec2::xy(3, 3), Vec2::xy(2, 2)), Hitbox::cell(Vec2::xy(4, 0))]);
+    for corner in [Vec2::xy(3, 3), Vec2::xy(4, 3), Vec2::xy(3, 4), Vec2::xy(4, 4)] {
+        assert!(query(&grid, Hitbox::cell(corner)).contains(&0), "{:?}", corner);
+    }
+    assert_eq!(query(&grid, Hitbox::cell(Vec2::xy(0, 0))), [0]);
+    assert_eq!(query(&grid, Hitbox::cell(Vec2::xy(7, 0))), [0, 1]);
+    assert_eq!(query(&grid, Hitbox::cell(Vec2::xy(8, 8))), Vec::<usize>::new());
+}
+
+#[test]
+fn objects_outside_the_field_go_into_the_border_buckets() {
+    let grid = grid_with(&[
+        Hitbox::cell(Vec2::xy(-5, -5)),
+        Hitbox::cell(Vec2::xy(100, 30)),
+        Hitbox::new(Vec2::xy(-10, 9), Vec2::xy(40, 1)),
+    ]);
+    assert_eq!(query(&grid, Hitbox::cell(Vec2::xy(0, 0))), [0]);
+    assert_eq!(query(&grid, Hitbox::cell(Vec2::xy(19, 19))), [1]);
+    assert_eq!(query(&grid, Hitbox::cell(Vec2::xy(-3, 10))), [2]);
+    assert_eq!(query(&grid, Hitbox::cell(Vec2::xy(50, 8))), [2]);
+}
+
+#[test]
+fn query_agrees_with_checking_every_pair() {
+    let mut rng = ChaCha8Rng::seed_from_u64(18);
+    let random_area = |rng: &mut ChaCha8Rng| Hitbox::new(
+        Vec2::xy(rng.gen_range(-5..65), rng.gen_range(-5..37)),
+        Vec2::xy(rng.gen_range(1..7), rng.gen_range(1..4)),
+    );
+    let areas: Vec<Hitbox> = (0..300).map(|_| random_area(&mut rng)).collect();
+    let mut grid = SpatialGrid::new(Vec2::xy(60, 32), 4);
+    grid.rebuild(areas.iter().copied());
+    let mut found = Vec::new();
+    for _ in 0..300 {
+        let area = random_area(&mut rng);
+        grid.query(&area, &mut found);
+        assert!(found.windows(2).all(|pair| pair[0] < pair[1]));
+        let overlapping: Vec<usize> = found.iter().copied().filter(|&index| areas[index].intersects(&area)).collect();
+        let expected: Vec<usize> = (0..areas.len()).filter(|&index| areas[index].intersects(&area)).collect();
+        assert_eq!(overlapping, expected);
+    }
+}
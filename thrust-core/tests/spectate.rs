@@ -0,0 +1,120 @@
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+use std::time::Duration;
+use thrust_core::config::GameConfig;
+use thrust_core::game_state::GameState;
+use thrust_core::input::FrameInput;
+use thrust_core::net::NetError;
+use thrust_core::snapshot::Snapshot;
+use thrust_core::spectate::{websocket_accept, Spectator, SpectatorServer};
+
+/// A game some frames in, with enemies, shots and goodies on the field.
+fn running_game() -> GameState {
+    let mut game = GameState::with_seed(GameConfig::from_toml("initial_health = 1000\nmax_health = 1000").unwrap(), 3);
+    let input = FrameInput { fire: true, ..Default::default() };
+    for _ in 0..400 {
+        game.update(&[input]);
+    }
+    game
+}
+
+#[test]
+fn restored_snapshot_draws_the_same_game() {
+    let game = running_game();
+    let snapshot = Snapshot::decode(&Snapshot::capture(&game).encode()).unwrap();
+    assert_eq!(snapshot.entities.len(), game.world.len());
+    let mut view = GameState::with_seed(game.config.clone(), 0);
+    snapshot.restore(&mut view);
+    assert_eq!(Snapshot::capture(&view), snapshot);
+    assert_eq!((view.frame, view.score()), (game.frame, game.score()));
+    assert_eq!(view.players[0].ship.pos, game.players[0].ship.pos);
+}
+
+#[test]
+fn tcp_spectator_receives_every_frame() {
+    let mut server = SpectatorServer::bind(0).unwrap();
+    let addr = format!("127.0.0.1:{}", server.local_addr().unwrap().port());
+    let spectator = thread::spawn(move || Spectator::connect(&addr).unwrap());
+    let mut game = running_game();
+    while !spectator.is_finished() {
+        server.broadcast(&game);
+        thread::sleep(Duration::from_millis(1));
+    }
+    let mut spectator = spectator.join().unwrap();
+    assert_eq!(server.spectators(), 1);
+    assert_eq!(spectator.config, game.config);
+    game.update(&[]);
+    server.broadcast(&game);
+    let snapshot = loop {
+        if let Some(snapshot) = spectator.poll().unwrap() {
+            break snapshot;
+        }
+        thread::sleep(Duration::from_millis(1));
+    };
+    assert_eq!(snapshot, Snapshot::capture(&game));
+}
+
+#[test]
+fn spectator_gets_the_last_frames_before_the_server_closed() {
+    let mut server = SpectatorServer::bind(0).unwrap();
+    let addr = format!("127.0.0.1:{}", server.local_addr().unwrap().port());
+    let spectator = thread::spawn(move || Spectator::connect(&addr).unwrap());
+    let mut game = running_game();
+    while !spectator.is_finished() {
+        server.broadcast(&game);
+        thread::sleep(Duration::from_millis(1));
+    }
+    let mut spectator = spectator.join().unwrap();
+    game.update(&[]);
+    server.broadcast(&game);
+    drop(server);
+    thread::sleep(Duration::from_millis(50));
+    assert_eq!(spectator.poll().unwrap(), Some(Snapshot::capture(&game)));
+    assert!(matches!(spectator.poll(), Err(NetError::Disconnected)));
+}
+
+#[test]
+fn oversized_stream_header_is_refused() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = format!("127.0.0.1:{}", listener.local_addr().unwrap().port());
+    let server = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut hello = [0; 6];
+        stream.read_exact(&mut hello).unwrap();
+        stream.write_all(&u32::MAX.to_le_bytes()).unwrap();
+        stream
+    });
+    assert!(matches!(Spectator::connect(&addr), Err(NetError::Protocol(_))));
+    server.join().unwrap();
+}
+
+#[test]
+fn websocket_accept_matches_the_rfc_example() {
+    assert_eq!(websocket_accept("dGhlIHNhbXBsZSBub25jZQ=="), "s3pPLMBiTxaQ9kYGzzhZRbK+xOo=");
+}
+
+#[test]
+fn websocket_spectator_receives_binary_frames() {
+    let mut server = SpectatorServer::bind(0).unwrap();
+    let mut stream = TcpStream::connect(("127.0.0.1", server.local_addr().unwrap().port())).unwrap();
+    stream.write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\
+                       Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\nSec-WebSocket-Version: 13\r\n\r\n").unwrap();
+    let game = running_game();
+    while server.spectators() == 0 {
+        server.broadcast(&game);
+        thread::sleep(Duration::from_millis(1));
+    }
+    let mut received = Vec::new();
+    let mut chunk = [0; 4096];
+    while !received.windows(4).any(|window| window == b"\r\n\r\n") || received.len() < 200 {
+        let len = stream.read(&mut chunk).unwrap();
+        received.extend_from_slice(&chunk[..len]);
+    }
+    let response = String::from_utf8_lossy(&received);
+    assert!(response.starts_with("HTTP/1.1 101 Switching Protocols\r\n"));
+    assert!(response.contains("Sec-WebSocket-Accept: s3pPLMBiTxaQ9kYGzzhZRbK+xOo=\r\n"));
+    let frames = &received[received.windows(4).position(|window| window == b"\r\n\r\n").unwrap() + 4..];
+    // the header with the config is the first binary frame
+    assert_eq!(frames[0], 0x82);
+}
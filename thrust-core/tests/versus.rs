@@ -0,0 +1,63 @@
+mod common;
+
+use thrust_core::config::GameConfig;
+use thrust_core::game_state::{GameMode, GameState};
+use common::quiet_game_with;
+
+fn versus_game() -> GameState {
+    let mut game = quiet_game_with(2);
+    game.config.mode = GameMode::Versus;
+    game
+}
+
+#[test]
+fn mode_is_read_from_the_config() {
+    assert_eq!(GameConfig::default().mode, GameMode::CoOp);
+    assert_eq!(GameConfig::from_toml("mode = \"versus\"").unwrap().mode, GameMode::Versus);
+    assert!(GameConfig::from_toml("mode = \"deathmatch\"").is_err());
+}
+
+#[test]
+fn player_with_more_points_wins_once_both_ships_are_destroyed() {
+    let mut game = versus_game();
+    game.players[1].score = 30;
+    game.players[1].health = 0;
+    game.update(&[]);
+    assert_eq!(game.winner(), None);
+    game.players[0].score = 20;
+    game.players[0].health = 0;
+    assert!(game.is_game_over());
+    assert_eq!(game.winner(), Some(1));
+    game.players[0].score = 30;
+    assert_eq!(game.winner(), None);
+}
+
+#[test]
+fn co_op_and_single_ship_games_have_no_winner() {
+    let mut co_op = quiet_game_with(2);
+    co_op.players[0].score = 10;
+    let mut single = quiet_game_with(1);
+    single.config.mode = GameMode::Versus;
+    for game in [&mut co_op, &mut single] {
+        game.players.iter_mut().for_each(|player| player.health = 0);
+        assert!(game.is_game_over());
+        assert!(!game.is_versus());
+        assert_eq!(game.winner(), None);
+    }
+}
+
+#[test]
+fn boss_comes_at_the_leaders_score_instead_of_the_sum() {
+    for (mode, boss_at_six_each) in [(GameMode::CoOp, true), (GameMode::Versus, false)] {
+        let mut game = quiet_game_with(2);
+        game.config.mode = mode;
+        game.next_boss_score = 10;
+        game.players[0].score = 6;
+        game.players[1].score = 6;
+        game.update(&[]);
+        assert_eq!(game.boss.is_some(), boss_at_six_each, "{:?}", mode);
+        game.players[1].score = 10;
+        game.update(&[]);
+        assert!(game.boss.is_some(), "{:?}", mode);
+    }
+}